@@ -1,8 +1,8 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{Attribute, Visibility};
+use syn::{punctuated::Punctuated, Attribute, Path, Token, Visibility};
 
-use crate::parse::Metadata;
+use crate::parse::{Metadata, ParsedFields};
 
 /// Generate ApiBuilder
 pub(crate) fn build_builder(
@@ -11,7 +11,9 @@ pub(crate) fn build_builder(
     api_name: Ident,
     fields_init: TokenStream,
 ) -> (Ident, TokenStream) {
-    let Metadata { base_url, default } = metadata;
+    let Metadata {
+        base_url, default, ..
+    } = metadata;
     let name = Ident::new(format!("{}Builder", api_name).as_str(), Span::call_site());
 
     let mut builder = quote! {
@@ -34,6 +36,13 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Try to construct a new builder with base_url
+            pub fn try_new(base_url: impl apisdk::IntoUrl + std::fmt::Debug) -> apisdk::ApiResult<Self> {
+                Ok(Self {
+                    inner: apisdk::ApiBuilder::new(base_url)?,
+                })
+            }
+
             // Set ClientBuilder
             pub fn with_client(self, client: apisdk::ClientBuilder) -> Self {
                 Self {
@@ -41,6 +50,76 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Only use HTTP/1 (ignored on wasm32)
+            pub fn with_http1_only(self) -> Self {
+                Self {
+                    inner: self.inner.with_http1_only()
+                }
+            }
+
+            /// Only use HTTP/2 without negotiation (ignored on wasm32)
+            pub fn with_http2_prior_knowledge(self) -> Self {
+                Self {
+                    inner: self.inner.with_http2_prior_knowledge()
+                }
+            }
+
+            /// Set the maximum idle connections per host in pool (ignored on wasm32)
+            pub fn with_pool_max_idle_per_host(self, max: usize) -> Self {
+                Self {
+                    inner: self.inner.with_pool_max_idle_per_host(max)
+                }
+            }
+
+            /// Set the timeout for idle connections in pool (ignored on wasm32)
+            pub fn with_pool_idle_timeout(self, timeout: std::time::Duration) -> Self {
+                Self {
+                    inner: self.inner.with_pool_idle_timeout(timeout)
+                }
+            }
+
+            /// Set the interval of TCP keepalive (ignored on wasm32)
+            pub fn with_tcp_keepalive(self, interval: std::time::Duration) -> Self {
+                Self {
+                    inner: self.inner.with_tcp_keepalive(interval)
+                }
+            }
+
+            /// Set the policy of redirects (ignored on wasm32)
+            pub fn with_redirect_policy(self, policy: apisdk::redirect::Policy) -> Self {
+                Self {
+                    inner: self.inner.with_redirect_policy(policy)
+                }
+            }
+
+            /// Don't follow redirects (ignored on wasm32)
+            pub fn with_no_redirects(self) -> Self {
+                Self {
+                    inner: self.inner.with_no_redirects()
+                }
+            }
+
+            /// Add proxy
+            pub fn with_proxy(self, proxy: apisdk::Proxy) -> Self {
+                Self {
+                    inner: self.inner.with_proxy(proxy)
+                }
+            }
+
+            /// Add proxy for all traffic
+            pub fn with_proxy_url(self, url: impl apisdk::IntoUrl) -> apisdk::ApiResult<Self> {
+                Ok(Self {
+                    inner: self.inner.with_proxy_url(url)?
+                })
+            }
+
+            /// Bypass proxies for hosts
+            pub fn with_no_proxy(self, hosts: impl ToString) -> Self {
+                Self {
+                    inner: self.inner.with_no_proxy(hosts)
+                }
+            }
+
             /// Set UrlRewriter
             pub fn with_rewriter<T>(self, rewriter: T) -> Self where T: apisdk::UrlRewriter {
                 Self {
@@ -48,6 +127,13 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Set PathRewriter
+            pub fn with_path_rewriter(self, path_rewriter: apisdk::PathRewriter) -> Self {
+                Self {
+                    inner: self.inner.with_path_rewriter(path_rewriter)
+                }
+            }
+
             /// Set DnsResolver
             pub fn with_resolver<T>(self, resolver: T) -> Self where T: apisdk::DnsResolver {
                 Self {
@@ -55,6 +141,13 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Set the preference of address family for dual-stack hosts
+            pub fn with_addr_preference(self, preference: apisdk::AddrPreference) -> Self {
+                Self {
+                    inner: self.inner.with_addr_preference(preference)
+                }
+            }
+
             /// Set ApiAuthenticator
             pub fn with_authenticator<T>(self, authenticator: T) -> Self where T: apisdk::ApiAuthenticator {
                 Self {
@@ -62,6 +155,69 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Set the name of api, which labels logs and spans
+            pub fn with_name(self, name: impl ToString) -> Self {
+                Self {
+                    inner: self.inner.with_name(name)
+                }
+            }
+
+            /// Set LogSink, which receives log records instead of `log` facade
+            pub fn with_log_sink<F>(self, sink: F) -> Self where F: Fn(apisdk::LogRecord) + Send + Sync + 'static {
+                Self {
+                    inner: self.inner.with_log_sink(sink)
+                }
+            }
+
+            /// Set the names of headers, which carry request id, trace id and span id
+            pub fn with_trace_headers(self, names: apisdk::TraceHeaderNames) -> Self {
+                Self {
+                    inner: self.inner.with_trace_headers(names)
+                }
+            }
+
+            /// Set JsonOptions
+            pub fn with_json_options(self, json_options: apisdk::JsonOptions) -> Self {
+                Self {
+                    inner: self.inner.with_json_options(json_options)
+                }
+            }
+
+            /// Enable the in-memory cache of responses by ETag
+            pub fn with_response_cache(self, capacity: usize) -> Self {
+                Self {
+                    inner: self.inner.with_response_cache(capacity)
+                }
+            }
+
+            /// Enable or disable sniffing payload when `Content-Type` is absent
+            pub fn with_content_sniffing(self, enabled: bool) -> Self {
+                Self {
+                    inner: self.inner.with_content_sniffing(enabled)
+                }
+            }
+
+            /// Set `Accept` header of all requests, if the request doesn't have one
+            pub fn with_default_accept(self, mime: apisdk::MimeType) -> Self {
+                Self {
+                    inner: self.inner.with_default_accept(mime)
+                }
+            }
+
+            /// Set the checksum header of all requests
+            pub fn with_body_checksum(self, checksum: apisdk::BodyChecksum) -> Self {
+                Self {
+                    inner: self.inner.with_body_checksum(checksum)
+                }
+            }
+
+            /// Add default header
+            pub fn with_default_header(self, name: impl ToString, value: impl ToString) -> Self {
+                Self {
+                    inner: self.inner.with_default_header(name, value)
+                }
+            }
+
             /// Set initialiser
             pub fn with_initialiser<T>(self, initialiser: T) -> Self where T: apisdk::Initialiser {
                 Self {
@@ -76,6 +232,21 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Add middleware by using closure
+            pub fn with_middleware_fn<F>(self, middleware: F) -> Self
+            where
+                F: Send + Sync + 'static,
+                F: for<'a> Fn(
+                    apisdk::Request,
+                    &'a mut apisdk::Extensions,
+                    apisdk::Next<'a>,
+                ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<apisdk::Response, apisdk::MiddlewareError>> + Send + 'a>>,
+            {
+                Self {
+                    inner: self.inner.with_middleware_fn(middleware)
+                }
+            }
+
             /// Set log filter
             pub fn with_log<L>(self, level: L) -> Self where L: apisdk::IntoFilter {
                 Self {
@@ -83,6 +254,20 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Set log filter, and write logs as JSON lines
+            pub fn with_log_json<L>(self, level: L) -> Self where L: apisdk::IntoFilter {
+                Self {
+                    inner: self.inner.with_logger(apisdk::LogConfig::json(level))
+                }
+            }
+
+            /// Set LogConfig
+            pub fn with_log_config(self, config: apisdk::LogConfig) -> Self {
+                Self {
+                    inner: self.inner.with_logger(config)
+                }
+            }
+
             /// Disable log
             pub fn disable_log(self) -> Self {
                 Self {
@@ -90,10 +275,22 @@ pub(crate) fn build_builder(
                 }
             }
 
+            /// Omit the middleware to log requests and responses
+            pub fn without_log_middleware(self) -> Self {
+                Self {
+                    inner: self.inner.without_log_middleware()
+                }
+            }
+
             /// Build the api core
             pub fn build_core(self) -> std::sync::Arc<apisdk::ApiCore> {
                 std::sync::Arc::new(self.inner.build())
             }
+
+            /// Try to build the api core
+            pub fn try_build_core(self) -> apisdk::ApiResult<std::sync::Arc<apisdk::ApiCore>> {
+                Ok(std::sync::Arc::new(self.inner.try_build()?))
+            }
         }
     };
 
@@ -107,6 +304,14 @@ pub(crate) fn build_builder(
                         #fields_init
                     }
                 }
+
+                /// Try to build the api instance
+                pub fn try_build(self) -> apisdk::ApiResult<#api_name> {
+                    Ok(#api_name {
+                        core: std::sync::Arc::new(self.inner.try_build()?),
+                        #fields_init
+                    })
+                }
             }
         });
     }
@@ -120,11 +325,18 @@ pub(crate) fn build_api_impl(
     vis: Visibility,
     api_name: Ident,
     api_attrs: Vec<Attribute>,
-    fields_decl: TokenStream,
-    _fields_clone: TokenStream,
+    fields: ParsedFields,
     builder_name: Ident,
 ) -> TokenStream {
-    let Metadata { default, .. } = metadata;
+    let Metadata { default, clone, .. } = metadata;
+    let ParsedFields {
+        decl: fields_decl,
+        clone: fields_clone,
+        ..
+    } = fields;
+    let derive_clone = api_attrs.iter().any(is_derive_clone);
+    // Clone is generated only when `clone` is set in `http_api`, so the manual impl won't conflict
+    let impl_clone = !derive_clone && *clone;
 
     let mut api = quote! {
         #(#api_attrs)*
@@ -163,9 +375,64 @@ pub(crate) fn build_api_impl(
             ) -> apisdk::ApiResult<apisdk::RequestBuilder> {
                 self.core.build_request(method, path).await
             }
+
+            /// Build a new HTTP request to absolute url
+            /// - method: HTTP method
+            /// - url: absolute url
+            /// - authenticate: false to skip authenticator
+            pub async fn request_absolute(
+                &self,
+                method: apisdk::Method,
+                url: impl apisdk::IntoUrl,
+                authenticate: bool,
+            ) -> apisdk::ApiResult<apisdk::RequestBuilder> {
+                self.core.build_request_absolute(method, url, authenticate).await
+            }
+
+            /// Build a new HTTP request, and split it into `Request` and `RequestSender`
+            /// - method: HTTP method
+            /// - path: relative path
+            pub async fn request_split(
+                &self,
+                method: apisdk::Method,
+                path: impl AsRef<str>,
+            ) -> apisdk::ApiResult<(apisdk::Request, apisdk::RequestSender)> {
+                self.core.build_request_split(method, path).await
+            }
         }
     };
 
+    if impl_clone {
+        api.extend(quote! {
+            impl Clone for #api_name {
+                fn clone(&self) -> Self {
+                    Self {
+                        core: self.core.clone(),
+                        #fields_clone
+                    }
+                }
+            }
+        });
+    }
+
+    if derive_clone || impl_clone {
+        api.extend(quote! {
+            impl #api_name {
+                /// Create a new api instance with a different base_url, and keep other fields
+                /// - base_url: the new base_url
+                pub fn with_base_url(
+                    &self,
+                    base_url: impl apisdk::IntoUrl,
+                ) -> apisdk::ApiResult<Self> {
+                    Ok(Self {
+                        core: std::sync::Arc::new(self.core.rebase(base_url)?),
+                        #fields_clone
+                    })
+                }
+            }
+        });
+    }
+
     if *default {
         api.extend(quote! {
             impl Default for #api_name {
@@ -179,6 +446,19 @@ pub(crate) fn build_api_impl(
     api
 }
 
+/// Check whether the attribute is `#[derive(Clone)]`
+fn is_derive_clone(attr: &Attribute) -> bool {
+    attr.path().is_ident("derive")
+        && attr
+            .parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)
+            .map(|paths| {
+                paths
+                    .iter()
+                    .any(|p| p.segments.last().is_some_and(|s| s.ident == "Clone"))
+            })
+            .unwrap_or(false)
+}
+
 /// Generate shortcut methods for api
 pub(crate) fn build_api_methods(_vis: Visibility) -> Vec<TokenStream> {
     [
@@ -208,9 +488,18 @@ pub(crate) fn build_macro_overrides(_fn_name: Ident) -> Vec<TokenStream> {
     [
         "send",
         "send_json",
+        "send_graphql",
         "send_xml",
         "send_form",
         "send_multipart",
+        "send_bytes",
+        "send_body_stream",
+        "send_range",
+        "send_sse",
+        "send_json_seq",
+        "send_with_trailers",
+        "download_to_file",
+        "resumable_download",
     ]
     .iter()
     .map(|name| {
@@ -234,6 +523,22 @@ pub(crate) fn build_macro_overrides(_fn_name: Ident) -> Vec<TokenStream> {
                         apisdk::#macro_with_name!($req, $arg1, $arg2, Self::__REQ_CONFIG.take()).await
                     }
                 };
+                ($req:expr, $arg1:expr, $arg2:expr, $arg3:tt) => {
+                    async {
+                        apisdk::#macro_with_name!($req, $arg1, $arg2, $arg3, Self::__REQ_CONFIG.take()).await
+                    }
+                };
+                // The arguments are not single token, e.g. `send_graphql!(req, query, json!({ "id": 1 }))`
+                ($req:expr, $arg1:expr) => {
+                    async {
+                        apisdk::#macro_with_name!($req, $arg1, Self::__REQ_CONFIG.take()).await
+                    }
+                };
+                ($req:expr, $arg1:expr, $arg2:expr) => {
+                    async {
+                        apisdk::#macro_with_name!($req, $arg1, $arg2, Self::__REQ_CONFIG.take()).await
+                    }
+                };
             }
         }
     })
@@ -54,17 +54,20 @@ pub fn http_api(
     let vis = ast.vis;
     let api_name = ast.ident;
     let api_attrs = ast.attrs;
-    let (fields_decl, fields_init, fields_clone) = parse_fields(ast.data);
+    let fields = parse_fields(ast.data);
 
-    let (builder_name, builder_impl) =
-        build_builder(&metadata, vis.clone(), api_name.clone(), fields_init);
+    let (builder_name, builder_impl) = build_builder(
+        &metadata,
+        vis.clone(),
+        api_name.clone(),
+        fields.init.clone(),
+    );
     let api_impl = build_api_impl(
         &metadata,
         vis.clone(),
         api_name.clone(),
         api_attrs,
-        fields_decl,
-        fields_clone,
+        fields,
         builder_name,
     );
     let methods = build_api_methods(vis.clone());
@@ -13,16 +13,20 @@ use syn::{
 pub(crate) struct Metadata {
     pub base_url: Literal,
     pub default: bool,
+    pub clone: bool,
 }
 
 impl From<proc_macro::TokenStream> for Metadata {
     fn from(value: proc_macro::TokenStream) -> Self {
         let mut iter = value.into_iter();
         let base_url = iter.next().unwrap().to_string();
-        let default = iter.all(|i| i.to_string() != "no_default");
+        let flags: Vec<String> = iter.map(|i| i.to_string()).collect();
+        let default = flags.iter().all(|f| f != "no_default");
+        let clone = flags.iter().any(|f| f == "clone");
         Self {
             base_url: Literal::from_str(base_url.as_str()).unwrap(),
             default,
+            clone,
         }
     }
 }
@@ -31,7 +35,17 @@ pub(crate) fn parse_meta(meta: proc_macro::TokenStream) -> Metadata {
     Metadata::from(meta)
 }
 
-pub(crate) fn parse_fields(data: Data) -> (TokenStream, TokenStream, TokenStream) {
+/// The tokens generated from fields of api struct
+pub(crate) struct ParsedFields {
+    /// The declarations of fields
+    pub decl: TokenStream,
+    /// The initializations of fields, by using `Default`
+    pub init: TokenStream,
+    /// The clones of fields
+    pub clone: TokenStream,
+}
+
+pub(crate) fn parse_fields(data: Data) -> ParsedFields {
     let empty = Punctuated::new();
     let fields = match data {
         Struct(DataStruct {
@@ -62,9 +76,9 @@ pub(crate) fn parse_fields(data: Data) -> (TokenStream, TokenStream, TokenStream
         }
     });
 
-    (
-        quote! {#(#fields_decl,)*},
-        quote! {#(#fields_init,)*},
-        quote! {#(#fields_clone,)*},
-    )
+    ParsedFields {
+        decl: quote! {#(#fields_decl,)*},
+        init: quote! {#(#fields_init,)*},
+        clone: quote! {#(#fields_clone,)*},
+    }
 }
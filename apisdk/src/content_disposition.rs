@@ -0,0 +1,150 @@
+//! Helpers to parse `Content-Disposition` header.
+
+use reqwest::header::{HeaderMap, CONTENT_DISPOSITION};
+
+use crate::server_timing::{split_unquoted, unquote};
+
+/// This struct represents the value of `Content-Disposition` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disposition {
+    /// The type of disposition, e.g. `attachment`, `inline` or `form-data`, in lowercase
+    pub kind: String,
+    /// `filename*` or `filename` param, and the former is preferred
+    pub filename: Option<String>,
+    /// `name` param, which is used by `form-data`
+    pub name: Option<String>,
+}
+
+impl Disposition {
+    /// Check whether the type is `attachment`
+    pub fn is_attachment(&self) -> bool {
+        self.kind == "attachment"
+    }
+
+    /// Parse the value of `Content-Disposition` header
+    /// - value: e.g. `attachment; filename="plain.txt"; filename*=UTF-8''%E2%82%AC%20rates.txt`
+    ///
+    /// Return `None` if the type is absent.
+    pub fn parse_value(value: &str) -> Option<Self> {
+        let mut parts = split_unquoted(value, ';').into_iter();
+        let kind = parts.next()?.trim().to_ascii_lowercase();
+        if kind.is_empty() {
+            return None;
+        }
+
+        let mut disposition = Self {
+            kind,
+            filename: None,
+            name: None,
+        };
+        let mut extended = None;
+        for param in parts {
+            let (key, value) = match param.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            if key.eq_ignore_ascii_case("filename*") {
+                extended = decode_ext_value(value);
+            } else if key.eq_ignore_ascii_case("filename") {
+                disposition.filename = Some(unquote(value));
+            } else if key.eq_ignore_ascii_case("name") {
+                disposition.name = Some(unquote(value));
+            }
+        }
+        if extended.is_some() {
+            disposition.filename = extended;
+        }
+        Some(disposition)
+    }
+}
+
+/// Parse `Content-Disposition` header
+/// - headers: HTTP headers
+///
+/// Return `None` if the header is absent or invalid.
+pub fn parse_content_disposition(headers: &HeaderMap) -> Option<Disposition> {
+    headers
+        .get(CONTENT_DISPOSITION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Disposition::parse_value)
+}
+
+/// Decode RFC 5987 ext-value, e.g. `UTF-8'en'%E2%82%AC%20rates.txt`
+///
+/// Only `UTF-8` and `ISO-8859-1` charsets are supported.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let bytes = percent_decode(parts.next()?)?;
+    if charset.eq_ignore_ascii_case("UTF-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("ISO-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+/// Decode `%XX` sequences
+fn percent_decode(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'%' => {
+                let hex = [iter.next()?, iter.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            b => bytes.push(b),
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue, CONTENT_DISPOSITION};
+
+    use super::{parse_content_disposition, Disposition};
+
+    #[test]
+    fn test_parse_plain() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_DISPOSITION,
+            HeaderValue::from_static(r#"Attachment; filename="report; 2024.pdf""#),
+        );
+        let disposition = parse_content_disposition(&headers).unwrap();
+        assert!(disposition.is_attachment());
+        assert_eq!(Some("report; 2024.pdf"), disposition.filename.as_deref());
+        assert_eq!(None, disposition.name);
+    }
+
+    #[test]
+    fn test_parse_rfc5987() {
+        let disposition = Disposition::parse_value(
+            r#"attachment; filename="EUR rates.txt"; filename*=UTF-8''%E2%82%AC%20rates.txt"#,
+        )
+        .unwrap();
+        assert_eq!(Some("€ rates.txt"), disposition.filename.as_deref());
+
+        let disposition =
+            Disposition::parse_value("inline; filename*=iso-8859-1'en'%A3%20rates.txt").unwrap();
+        assert_eq!("inline", disposition.kind);
+        assert_eq!(Some("£ rates.txt"), disposition.filename.as_deref());
+
+        // Fallback to `filename` if `filename*` is malformed
+        let disposition =
+            Disposition::parse_value(r#"attachment; filename="a.txt"; filename*=UTF-8''%E2%8"#)
+                .unwrap();
+        assert_eq!(Some("a.txt"), disposition.filename.as_deref());
+    }
+
+    #[test]
+    fn test_parse_absent() {
+        assert_eq!(None, parse_content_disposition(&HeaderMap::new()));
+        assert_eq!(None, Disposition::parse_value(" ; filename=a.txt"));
+    }
+}
@@ -1,26 +1,63 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
+use futures::future::BoxFuture;
+
+#[cfg(feature = "tracing")]
+use crate::SpanHeadersMiddleware;
 use crate::{
-    ApiAuthenticator, ApiError, ApiResult, AuthenticateMiddleware, Client, ClientBuilder,
-    DnsResolver, Initialiser, IntoUrl, LogConfig, LogMiddleware, Method, Middleware,
-    RequestBuilder, RequestTraceIdMiddleware, ReqwestDnsResolver, ReqwestUrlRewriter, Url, UrlOps,
-    UrlRewriter,
+    header::{HeaderMap, HeaderName, HeaderValue},
+    merge_headers, redirect, AddrPreference, ApiAuthenticator, ApiError, ApiName, ApiResult,
+    AuthenticateMiddleware, BodyChecksum, BodyChecksumMiddleware, Client, ClientBuilder,
+    ContentSniffing, DefaultAccept, DefaultAcceptMiddleware, DefaultHeaders,
+    DefaultHeadersMiddleware, DnsResolver, Extensions, Initialiser, IntoUrl, JsonOptions,
+    LogConfig, LogMiddleware, LogRecord, LogSink, Method, Middleware, MiddlewareError, MimeType,
+    Next, NoProxy, OverlayHeaderMap, PathRewriter, Proxy, Request, RequestBuilder, RequestSender,
+    RequestTraceIdMiddleware, ReqwestDnsResolver, ReqwestUrlRewriter, ResolvedUrl, Response,
+    ResponseCache, ResponseCacheMiddleware, TraceHeaderNames, Url, UrlOps, UrlRewriter,
 };
 
 /// This struct is used to build an instance of ApiCore
 pub struct ApiBuilder {
     /// Reqwest ClientBuilder
     client: ClientBuilder,
+    /// The name of api, which labels logs and spans
+    name: Option<String>,
     /// Base url for target api
     base_url: Url,
+    /// The proxies for Reqwest
+    proxies: Vec<Proxy>,
+    /// The hosts to bypass proxies
+    no_proxy: Vec<String>,
     /// The holder of UrlRewriter
     rewriter: Option<ReqwestUrlRewriter>,
+    /// The rewriter of relative path
+    path_rewriter: Option<PathRewriter>,
     /// The holder of DnsResolver
     resolver: Option<ReqwestDnsResolver>,
+    /// The preference of address family
+    addr_preference: AddrPreference,
     /// The holder of ApiAuthenticator
     authenticator: Option<Arc<dyn ApiAuthenticator>>,
     /// The holder of LogConfig
     logger: Option<Arc<LogConfig>>,
+    /// The holder of LogSink
+    log_sink: Option<LogSink>,
+    /// Indicate whether to register LogMiddleware
+    log_middleware: bool,
+    /// The names of trace headers
+    trace_headers: Option<TraceHeaderNames>,
+    /// The holder of JsonOptions
+    json_options: Option<Arc<JsonOptions>>,
+    /// The default headers, which will be validated when building
+    default_headers: Vec<(String, String)>,
+    /// The holder of ResponseCache
+    response_cache: Option<ResponseCache>,
+    /// Indicate whether to sniff payload when `Content-Type` is absent
+    content_sniffing: bool,
+    /// The holder of DefaultAccept
+    default_accept: Option<DefaultAccept>,
+    /// The holder of BodyChecksum
+    body_checksum: Option<BodyChecksum>,
     /// The initialisers for Reqwest
     initialisers: Vec<Arc<dyn Initialiser>>,
     /// The middlewares for Reqwest
@@ -31,24 +68,178 @@ impl ApiBuilder {
     /// Create an instance of ApiBuilder
     /// - base_url: base url for target api
     pub fn new(base_url: impl IntoUrl + std::fmt::Debug) -> ApiResult<Self> {
+        let display = format!("{:?}", base_url);
+        let base_url = base_url
+            .into_url()
+            .map_err(|e| ApiError::Config(format!("Invalid base url {}: {}", display, e)))?;
+        if base_url.cannot_be_a_base() {
+            return Err(ApiError::Config(format!(
+                "Invalid base url {}: cannot be a base",
+                display
+            )));
+        }
         Ok(Self {
             client: ClientBuilder::default(),
-            base_url: base_url.into_url().map_err(ApiError::InvalidUrl)?,
+            name: None,
+            base_url,
+            proxies: vec![],
+            no_proxy: vec![],
             rewriter: None,
+            path_rewriter: None,
             resolver: None,
+            addr_preference: AddrPreference::System,
             authenticator: None,
             logger: None,
+            log_sink: None,
+            log_middleware: true,
+            trace_headers: None,
+            json_options: None,
+            default_headers: vec![],
+            response_cache: None,
+            content_sniffing: false,
+            default_accept: None,
+            body_checksum: None,
             initialisers: vec![],
             middlewares: vec![],
         })
     }
 
+    /// Set the name of api, which prefixes the target of logs, and labels tracing spans as `api.name`
+    /// - name: the name of api, e.g. `payments-api`
+    pub fn with_name(self, name: impl ToString) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            ..self
+        }
+    }
+
     /// Set the ClientBuilder to create Client instance of Reqwest
     /// - client: Reqwest ClientBuilder
     pub fn with_client(self, client: ClientBuilder) -> Self {
         Self { client, ..self }
     }
 
+    /// Only use HTTP/1
+    ///
+    /// It's ignored on wasm32.
+    pub fn with_http1_only(self) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self {
+            client: self.client.http1_only(),
+            ..self
+        };
+        #[cfg(target_arch = "wasm32")]
+        self
+    }
+
+    /// Only use HTTP/2, without negotiation
+    ///
+    /// It's ignored on wasm32.
+    pub fn with_http2_prior_knowledge(self) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self {
+            client: self.client.http2_prior_knowledge(),
+            ..self
+        };
+        #[cfg(target_arch = "wasm32")]
+        self
+    }
+
+    /// Set the maximum idle connections per host in pool
+    /// - max: the maximum number of idle connections
+    ///
+    /// It's ignored on wasm32.
+    pub fn with_pool_max_idle_per_host(self, max: usize) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self {
+            client: self.client.pool_max_idle_per_host(max),
+            ..self
+        };
+        #[cfg(target_arch = "wasm32")]
+        self
+    }
+
+    /// Set the timeout for idle connections in pool
+    /// - timeout: the idle timeout
+    ///
+    /// It's ignored on wasm32.
+    pub fn with_pool_idle_timeout(self, timeout: Duration) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self {
+            client: self.client.pool_idle_timeout(timeout),
+            ..self
+        };
+        #[cfg(target_arch = "wasm32")]
+        self
+    }
+
+    /// Set the interval of TCP keepalive
+    /// - interval: the keepalive interval
+    ///
+    /// It's ignored on wasm32.
+    pub fn with_tcp_keepalive(self, interval: Duration) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self {
+            client: self.client.tcp_keepalive(interval),
+            ..self
+        };
+        #[cfg(target_arch = "wasm32")]
+        self
+    }
+
+    /// Set the policy of redirects
+    /// - policy: Reqwest redirect Policy
+    ///
+    /// It's ignored on wasm32.
+    pub fn with_redirect_policy(self, policy: redirect::Policy) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self {
+            client: self.client.redirect(policy),
+            ..self
+        };
+        #[cfg(target_arch = "wasm32")]
+        self
+    }
+
+    /// Don't follow redirects, and `ApiError::Redirect` will be returned for 3xx responses
+    ///
+    /// It's ignored on wasm32.
+    pub fn with_no_redirects(self) -> Self {
+        self.with_redirect_policy(redirect::Policy::none())
+    }
+
+    /// Add proxy
+    /// - proxy: Reqwest Proxy
+    ///
+    /// The proxy can't be used together with DnsResolver, and `try_build` will return `ApiError::Config`.
+    pub fn with_proxy(self, proxy: Proxy) -> Self {
+        let mut s = self;
+        s.proxies.push(proxy);
+        s
+    }
+
+    /// Add proxy for all traffic
+    /// - url: the url of proxy, e.g. `http://proxy.local:8080`
+    pub fn with_proxy_url(self, url: impl IntoUrl) -> ApiResult<Self> {
+        let proxy =
+            Proxy::all(url).map_err(|e| ApiError::Config(format!("Invalid proxy url: {}", e)))?;
+        Ok(self.with_proxy(proxy))
+    }
+
+    /// Bypass proxies for hosts, in `NO_PROXY` style
+    /// - hosts: comma-separated hosts, IPs, CIDRs or domain suffixes, e.g. `10.0.0.0/8,.svc.local`
+    ///
+    /// It applies to the system proxies from `HTTP_PROXY` / `HTTPS_PROXY` / `ALL_PROXY`, together with `NO_PROXY`.
+    /// The proxies added by `with_proxy` are kept as is, so use `Proxy::no_proxy` to bypass them.
+    /// If the endpoints are selected by UrlRewriter dynamically, the exclusion list should cover all possible hosts,
+    /// so prefer CIDRs or domain suffixes to list them one by one.
+    /// An invalid system proxy fails `try_build` with `ApiError::Config`, rather than being dropped.
+    pub fn with_no_proxy(self, hosts: impl ToString) -> Self {
+        let mut s = self;
+        s.no_proxy.push(hosts.to_string());
+        s
+    }
+
     /// Set the UrlRewriter
     /// - resolver: UrlRewriter
     pub fn with_rewriter<T>(self, rewriter: T) -> Self
@@ -61,8 +252,20 @@ impl ApiBuilder {
         }
     }
 
+    /// Set the PathRewriter, which rewrites the relative path of each request
+    /// - path_rewriter: PathRewriter
+    pub fn with_path_rewriter(self, path_rewriter: PathRewriter) -> Self {
+        Self {
+            path_rewriter: Some(path_rewriter),
+            ..self
+        }
+    }
+
     /// Set the DnsResolver
     /// - resolver: DnsResolver
+    ///
+    /// Pass `Arc<dyn DnsResolver>` to share one resolver (and its cache) across multiple builders.
+    /// It can't be used together with proxies added by `with_proxy`.
     pub fn with_resolver<T>(self, resolver: T) -> Self
     where
         T: DnsResolver,
@@ -73,6 +276,18 @@ impl ApiBuilder {
         }
     }
 
+    /// Set the preference of address family for dual-stack hosts (Happy Eyeballs)
+    /// - preference: AddrPreference
+    ///
+    /// The resolved addresses are ordered, so the preferred family is tried first, and the other one is raced after a short delay.
+    /// It applies to the addresses from DnsResolver too, and to the host of proxy, if any.
+    pub fn with_addr_preference(self, preference: AddrPreference) -> Self {
+        Self {
+            addr_preference: preference,
+            ..self
+        }
+    }
+
     /// Set the ApiAuthenticator
     /// - authenticator: ApiAuthenticator
     pub fn with_authenticator<T>(self, authenticator: T) -> Self
@@ -97,6 +312,102 @@ impl ApiBuilder {
         }
     }
 
+    /// Set the LogSink, which receives log records instead of `log` facade
+    /// - sink: the callback to receive log records
+    pub fn with_log_sink<F>(self, sink: F) -> Self
+    where
+        F: Fn(LogRecord) + Send + Sync + 'static,
+    {
+        Self {
+            log_sink: Some(LogSink::new(sink)),
+            ..self
+        }
+    }
+
+    /// Omit the middleware to log requests and responses, e.g. for maximum-throughput paths
+    ///
+    /// The request line, the status of response, `Server-Timing` and slow responses are logged by
+    /// the middleware, so they will be missing even if logs are enabled, while the payloads and
+    /// errors are still logged.
+    pub fn without_log_middleware(self) -> Self {
+        Self {
+            log_middleware: false,
+            ..self
+        }
+    }
+
+    /// Set the names of headers, which carry request id, trace id and span id
+    /// - names: TraceHeaderNames
+    pub fn with_trace_headers(self, names: TraceHeaderNames) -> Self {
+        Self {
+            trace_headers: Some(names),
+            ..self
+        }
+    }
+
+    /// Set the JsonOptions
+    /// - json_options: JsonOptions
+    pub fn with_json_options(self, json_options: JsonOptions) -> Self {
+        Self {
+            json_options: Some(Arc::new(json_options)),
+            ..self
+        }
+    }
+
+    /// Enable the in-memory cache of responses, which sends `If-None-Match` and reuses body on `304 Not Modified`
+    /// - capacity: the maximum number of cached responses
+    pub fn with_response_cache(self, capacity: usize) -> Self {
+        Self {
+            response_cache: Some(ResponseCache::new(capacity)),
+            ..self
+        }
+    }
+
+    /// Enable or disable sniffing payload to choose parser, when `Content-Type` header is absent
+    /// - enabled: true to sniff JSON (`{` / `[`) and XML (`<`) by the first non-whitespace byte
+    ///
+    /// It's off by default, and a present header (even `text/plain`) is always respected.
+    pub fn with_content_sniffing(self, enabled: bool) -> Self {
+        Self {
+            content_sniffing: enabled,
+            ..self
+        }
+    }
+
+    /// Set `Accept` header of all requests, if the request doesn't have one
+    /// - mime: MimeType, e.g. `MimeType::Json`
+    ///
+    /// It's the same as `with_initialiser(DefaultAccept(mime))`, and `DefaultAccept` extension overrides the mime for single request.
+    pub fn with_default_accept(self, mime: MimeType) -> Self {
+        Self {
+            default_accept: Some(DefaultAccept(mime)),
+            ..self
+        }
+    }
+
+    /// Set the checksum header of all requests, which is computed before `ApiAuthenticator` runs
+    /// - checksum: BodyChecksum, e.g. `BodyChecksum::amz_content_sha256()`
+    ///
+    /// It's the same as `with_initialiser(checksum)`, and `BodyChecksum` extension overrides the checksum for single request.
+    pub fn with_body_checksum(self, checksum: BodyChecksum) -> Self {
+        Self {
+            body_checksum: Some(checksum),
+            ..self
+        }
+    }
+
+    /// Add default header, which will be applied to all requests
+    /// - name: the name of header
+    /// - value: the value of header
+    ///
+    /// The header will be validated by `try_build`.
+    pub fn with_default_header(self, name: impl ToString, value: impl ToString) -> Self {
+        let mut s = self;
+        s.default_headers
+            .push((name.to_string(), value.to_string()));
+        s
+    }
+
     /// Add initialiser
     /// - initialiser: Reqwest Initialiser
     pub fn with_initialiser<T>(self, initialiser: T) -> Self
@@ -119,55 +430,234 @@ impl ApiBuilder {
         s
     }
 
+    /// Add middleware by using closure
+    /// - middleware: the closure to handle request, and call `next.run(req, extensions)` to continue
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let client = XxxApi::builder()
+    ///     .with_middleware_fn(|mut req, extensions, next| {
+    ///         req.headers_mut().insert("X-Key", HeaderValue::from_static("value"));
+    ///         Box::pin(next.run(req, extensions))
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn with_middleware_fn<F>(self, middleware: F) -> Self
+    where
+        F: Send + Sync + 'static,
+        F: for<'a> Fn(
+            Request,
+            &'a mut Extensions,
+            Next<'a>,
+        ) -> BoxFuture<'a, Result<Response, MiddlewareError>>,
+    {
+        self.with_middleware(middleware)
+    }
+
     /// Build an instance of ApiCore
+    ///
+    /// # Panics
+    ///
+    /// Panic if the builder is misconfigured. Use `try_build` to get the error instead.
     pub fn build(self) -> ApiCore {
-        let client = match self.resolver.clone() {
-            Some(r) => self.client.dns_resolver(Arc::new(r)),
-            None => self.client,
+        match self.try_build() {
+            Ok(core) => core,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Try to build an instance of ApiCore
+    ///
+    /// Return `ApiError::Config` if the builder is misconfigured.
+    pub fn try_build(mut self) -> ApiResult<ApiCore> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in std::mem::take(&mut self.default_headers) {
+            let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+                ApiError::Config(format!("Invalid default header name: {:?}", name))
+            })?;
+            let header_value = HeaderValue::from_str(&value).map_err(|_| {
+                ApiError::Config(format!(
+                    "Invalid default header value of {}: {:?}",
+                    name, value
+                ))
+            })?;
+            headers.append(header_name, header_value);
+        }
+
+        let mut client = self.client;
+        let mut proxies = self.proxies;
+        if proxies.is_empty() {
+            if !self.no_proxy.is_empty() {
+                // Take over the system proxies, which are disabled once any proxy is added
+                proxies = system_proxies(&self.no_proxy)?;
+            }
+        } else if self.resolver.is_some() {
+            return Err(ApiError::Config(
+                "DnsResolver can't be used with proxies, since it would resolve the host of proxy rather than the target api".to_string(),
+            ));
+        }
+        for proxy in proxies {
+            client = client.proxy(proxy);
+        }
+        let resolver = match (self.resolver, self.addr_preference) {
+            (resolver, AddrPreference::System) => resolver,
+            (Some(r), preference) => Some(r.with_preference(preference)),
+            (None, preference) => Some(ReqwestDnsResolver::system().with_preference(preference)),
         };
-        let mut client = reqwest_middleware::ClientBuilder::new(client.build().unwrap());
+        let client = match resolver.clone() {
+            Some(r) => client.dns_resolver(Arc::new(r)),
+            None => client,
+        };
+        let client = client
+            .build()
+            .map_err(|e| ApiError::Config(format!("Failed to build client: {}", e)))?;
+        let mut client = reqwest_middleware::ClientBuilder::new(client);
 
         // Apply middleware in correct order
         client = client.with(RequestTraceIdMiddleware);
         // client = client.with(RewriteHostMiddleware);
+        client = client.with(DefaultHeadersMiddleware);
         for middleware in self.middlewares {
             client = client.with_arc(middleware);
         }
+        client = client.with(DefaultAcceptMiddleware);
+        // The checksum of body should be set before authenticating, so signers could include it
+        client = client.with(BodyChecksumMiddleware);
         if self.authenticator.is_some() {
             client = client.with(AuthenticateMiddleware);
         }
-        client = client.with(LogMiddleware);
+        // The headers should be recorded after defaults and credentials are set, so the final ones are visible
+        #[cfg(feature = "tracing")]
+        {
+            client = client.with(SpanHeadersMiddleware);
+        }
+        // The credentials should be set before caching, so they could be included in the key
+        if self.response_cache.is_some() {
+            client = client.with(ResponseCacheMiddleware);
+        }
+        if self.log_middleware {
+            client = client.with(LogMiddleware);
+        }
 
-        // Apply initialisers
+        // Apply initialisers, which run before all middlewares (e.g. authenticator)
+        if let Some(name) = self.name.as_ref() {
+            client = client.with_init(ApiName(name.clone()));
+        }
         if let Some(logger) = self.logger {
             client = client.with_arc_init(logger);
         }
+        if let Some(log_sink) = self.log_sink {
+            client = client.with_init(log_sink);
+        }
+        if let Some(trace_headers) = self.trace_headers {
+            client = client.with_init(trace_headers);
+        }
+        if let Some(json_options) = self.json_options {
+            client = client.with_arc_init(json_options);
+        }
+        if let Some(response_cache) = self.response_cache {
+            client = client.with_init(response_cache);
+        }
+        if self.content_sniffing {
+            client = client.with_init(ContentSniffing);
+        }
+        if let Some(default_accept) = self.default_accept {
+            client = client.with_init(default_accept);
+        }
+        if let Some(body_checksum) = self.body_checksum {
+            client = client.with_init(body_checksum);
+        }
+        let default_headers = DefaultHeaders::default();
+        default_headers.merge(headers);
+        client = client.with_init(default_headers.clone());
         for initialiser in self.initialisers {
             client = client.with_arc_init(initialiser);
         }
 
-        ApiCore {
+        Ok(ApiCore {
             client: client.build(),
+            name: self.name,
             base_url: self.base_url,
             rewriter: self.rewriter,
-            resolver: self.resolver,
+            path_rewriter: self.path_rewriter,
+            resolver,
             authenticator: self.authenticator,
-        }
+            default_headers,
+            overlay_headers: Arc::default(),
+        })
+    }
+}
+
+/// Build proxies from `HTTP_PROXY` / `HTTPS_PROXY` / `ALL_PROXY` environment variables, as reqwest does
+/// - no_proxy: the hosts to bypass, which are merged with `NO_PROXY` environment variable
+///
+/// It's only used by `with_no_proxy`, since reqwest doesn't allow adding hosts to the system proxies.
+///
+/// `HTTP_PROXY` is ignored in CGI, since it could be set by the client via `Proxy` header.
+///
+/// Return `ApiError::Config` if any proxy url is invalid.
+fn system_proxies(no_proxy: &[String]) -> ApiResult<Vec<Proxy>> {
+    // The uppercase one takes precedence, even if it's empty
+    let get = |names: [&'static str; 2]| {
+        names
+            .iter()
+            .find_map(|name| std::env::var(name).ok().map(|v| (*name, v)))
+            .filter(|(_, v)| !v.is_empty())
+    };
+    let is_cgi = std::env::var_os("REQUEST_METHOD").is_some();
+
+    // `NoProxy` can't be merged, so merge the hosts before parsing
+    let mut hosts = no_proxy.to_vec();
+    hosts.extend(get(["NO_PROXY", "no_proxy"]).map(|(_, v)| v));
+    let no_proxy = NoProxy::from_string(&hosts.join(","));
+
+    let all = get(["ALL_PROXY", "all_proxy"]);
+    let http = (!is_cgi)
+        .then(|| get(["HTTP_PROXY", "http_proxy"]))
+        .flatten()
+        .or_else(|| all.clone());
+    let https = get(["HTTPS_PROXY", "https_proxy"]).or(all);
+
+    let invalid = |name: &str, url: &str, e: reqwest::Error| {
+        ApiError::Config(format!("Invalid proxy url in {} {:?}: {}", name, url, e))
+    };
+    let mut proxies = vec![];
+    if let Some((name, url)) = http {
+        proxies.push(Proxy::http(&url).map_err(|e| invalid(name, &url, e))?);
+    }
+    if let Some((name, url)) = https {
+        proxies.push(Proxy::https(&url).map_err(|e| invalid(name, &url, e))?);
     }
+    Ok(proxies
+        .into_iter()
+        .map(|p| p.no_proxy(no_proxy.clone()))
+        .collect())
 }
 
 /// This struct is used to create HTTP request
+///
+/// It's cheap to clone, since all fields are either `Arc` or backed by `Arc`.
+#[derive(Clone)]
 pub struct ApiCore {
     /// Reqwest Client
     client: Client,
+    /// The name of api
+    name: Option<String>,
     /// Base url for target api
     base_url: Url,
     /// The holder of ReqwestUrlRewriter
     rewriter: Option<ReqwestUrlRewriter>,
+    /// The holder of PathRewriter
+    path_rewriter: Option<PathRewriter>,
     /// The holder of ReqwestDnsResolver
     resolver: Option<ReqwestDnsResolver>,
     /// The holder of ApiAuthenticator
     authenticator: Option<Arc<dyn ApiAuthenticator>>,
+    /// The default headers, which could be updated after construction
+    default_headers: DefaultHeaders,
+    /// The headers layered onto default headers, which are owned by this instance
+    overlay_headers: Arc<HeaderMap>,
 }
 
 impl std::fmt::Debug for ApiCore {
@@ -176,9 +666,15 @@ impl std::fmt::Debug for ApiCore {
         let mut d = d
             .field("client", &self.client)
             .field("base_url", &self.base_url);
+        if let Some(name) = self.name.as_ref() {
+            d = d.field("name", name);
+        }
         if let Some(r) = self.rewriter.as_ref() {
             d = d.field("rewriter", &r.type_name());
         }
+        if let Some(r) = self.path_rewriter.as_ref() {
+            d = d.field("path_rewriter", r);
+        }
         if let Some(r) = self.resolver.as_ref() {
             d = d.field("resolver", &r.type_name());
         }
@@ -190,15 +686,17 @@ impl std::fmt::Debug for ApiCore {
 }
 
 impl ApiCore {
+    /// Get the name of api, which is set by `ApiBuilder::with_name`
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     /// Create a new ApiCore with a different base_url
     pub fn rebase(&self, base_url: impl IntoUrl) -> ApiResult<Self> {
         let base_url = base_url.into_url().map_err(ApiError::InvalidUrl)?;
         Ok(Self {
-            client: self.client.clone(),
             base_url,
-            rewriter: self.rewriter.clone(),
-            resolver: self.resolver.clone(),
-            authenticator: self.authenticator.clone(),
+            ..self.clone()
         })
     }
 
@@ -209,11 +707,17 @@ impl ApiCore {
         T: UrlRewriter,
     {
         Self {
-            client: self.client.clone(),
-            base_url: self.base_url.clone(),
             rewriter: Some(ReqwestUrlRewriter::new(rewriter)),
-            resolver: self.resolver.clone(),
-            authenticator: self.authenticator.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Set the PathRewriter, which rewrites the relative path of each request
+    /// - path_rewriter: PathRewriter
+    pub fn with_path_rewriter(&self, path_rewriter: PathRewriter) -> Self {
+        Self {
+            path_rewriter: Some(path_rewriter),
+            ..self.clone()
         }
     }
 
@@ -224,11 +728,8 @@ impl ApiCore {
         T: DnsResolver,
     {
         Self {
-            client: self.client.clone(),
-            base_url: self.base_url.clone(),
-            rewriter: self.rewriter.clone(),
             resolver: Some(ReqwestDnsResolver::new(resolver)),
-            authenticator: self.authenticator.clone(),
+            ..self.clone()
         }
     }
 
@@ -248,11 +749,38 @@ impl ApiCore {
         T: ApiAuthenticator,
     {
         Self {
-            client: self.client.clone(),
-            base_url: self.base_url.clone(),
-            rewriter: self.rewriter.clone(),
-            resolver: self.resolver.clone(),
             authenticator: Some(Arc::new(authenticator)),
+            ..self.clone()
+        }
+    }
+
+    /// Insert or update default headers, which will be applied to subsequent requests
+    /// - headers: the headers to merge
+    ///
+    /// The default headers are shared with all clones of this instance, and they are only applied
+    /// when the request doesn't have the headers with the same name.
+    pub fn set_default_headers(&self, headers: HeaderMap) {
+        self.default_headers.merge(headers);
+    }
+
+    /// Remove default header
+    /// - name: the name of header
+    pub fn remove_default_header(&self, name: &str) {
+        self.default_headers.remove(name);
+    }
+
+    /// Create a new ApiCore with headers layered onto default headers
+    /// - headers: the headers to layer
+    ///
+    /// Unlike `set_default_headers`, the headers are only applied to the new instance (and its clones),
+    /// e.g. per-tenant headers. They replace the default headers with the same name,
+    /// and the headers set on `RequestBuilder` later are kept.
+    pub fn with_default_headers(&self, headers: HeaderMap) -> Self {
+        let mut overlay_headers = self.overlay_headers.as_ref().clone();
+        merge_headers(&mut overlay_headers, headers);
+        Self {
+            overlay_headers: Arc::new(overlay_headers),
+            ..self.clone()
         }
     }
 
@@ -271,26 +799,76 @@ impl ApiCore {
     /// Build a new request url
     /// - path: relative path to base_url
     ///
+    /// The path is used as is, so use `path!` or `PathTemplate` to percent-encode the params in it.
+    ///
     /// Return error when failed to retrieve valid endpoint from ApiRouter
     pub async fn build_url(&self, path: impl AsRef<str>) -> ApiResult<Url> {
         let base = self.build_base_url().await?;
-        Ok(base.merge_path(path.as_ref()))
+        match self.path_rewriter.as_ref() {
+            Some(path_rewriter) => Ok(base.merge_path(&path_rewriter.rewrite(path.as_ref()))),
+            None => Ok(base.merge_path(path.as_ref())),
+        }
     }
 
     /// Build a new HTTP request
     /// - method: HTTP method
     /// - path: relative path to base_url
+    ///
+    /// The resolved url will be attached as `ResolvedUrl` extension.
     pub async fn build_request(
         &self,
         method: Method,
         path: impl AsRef<str>,
     ) -> ApiResult<RequestBuilder> {
         let url = self.build_url(path.as_ref()).await?;
-        let req = self.client.request(method, url);
+        Ok(self.build_request_to(method, url, true))
+    }
+
+    /// Build a new HTTP request to absolute url, e.g. the link of next page, or pre-signed url
+    /// - method: HTTP method
+    /// - url: absolute url, which is used as is
+    /// - authenticate: false to skip ApiAuthenticator, e.g. for pre-signed url
+    ///
+    /// The base_url, UrlRewriter and PathRewriter are not applied, while initialisers,
+    /// middlewares and default headers still work.
+    pub async fn build_request_absolute(
+        &self,
+        method: Method,
+        url: impl IntoUrl,
+        authenticate: bool,
+    ) -> ApiResult<RequestBuilder> {
+        let url = url.into_url().map_err(ApiError::InvalidUrl)?;
+        Ok(self.build_request_to(method, url, authenticate))
+    }
+
+    /// Build a new HTTP request to the url
+    fn build_request_to(&self, method: Method, url: Url, authenticate: bool) -> RequestBuilder {
+        let req = self
+            .client
+            .request(method, url.clone())
+            .with_extension(ResolvedUrl(url));
+        let req = match self.overlay_headers.is_empty() {
+            true => req,
+            false => req.with_extension(OverlayHeaderMap(self.overlay_headers.clone())),
+        };
 
         match self.authenticator.clone() {
-            Some(authenticator) => Ok(req.with_extension(authenticator)),
-            None => Ok(req),
+            Some(authenticator) if authenticate => req.with_extension(authenticator),
+            _ => req,
         }
     }
+
+    /// Build a new HTTP request, and split it into `Request` and `RequestSender`
+    /// - method: HTTP method
+    /// - path: relative path to base_url
+    ///
+    /// The `Request` could be modified, batched or scheduled, and then sent by `RequestSender`.
+    pub async fn build_request_split(
+        &self,
+        method: Method,
+        path: impl AsRef<str>,
+    ) -> ApiResult<(Request, RequestSender)> {
+        let req = self.build_request(method, path).await?;
+        RequestSender::split(req)
+    }
 }
@@ -1,4 +1,5 @@
 use base64::{engine::general_purpose, DecodeError, Engine};
+use hmac::{digest::KeyInit, Hmac, Mac};
 use md5::{Digest, Md5};
 use sha1::Sha1;
 use sha2::Sha256;
@@ -72,6 +73,66 @@ pub fn sha256_base64(input: impl AsRef<[u8]>) -> String {
     encode_base64(sha256_raw(input))
 }
 
+/// Calc HMAC-MD5, and encode via hex
+pub fn hmac_md5(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> String {
+    hex::encode(hmac_md5_raw(key, input))
+}
+
+/// Calc HMAC-MD5
+pub fn hmac_md5_raw(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> Vec<u8> {
+    hmac_raw::<Hmac<Md5>>(key.as_ref(), input.as_ref())
+}
+
+/// Calc HMAC-MD5, and encode via base64
+pub fn hmac_md5_base64(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> String {
+    encode_base64(hmac_md5_raw(key, input))
+}
+
+/// Calc HMAC-SHA1, and encode via hex
+pub fn hmac_sha1(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> String {
+    hex::encode(hmac_sha1_raw(key, input))
+}
+
+/// Calc HMAC-SHA1
+pub fn hmac_sha1_raw(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> Vec<u8> {
+    hmac_raw::<Hmac<Sha1>>(key.as_ref(), input.as_ref())
+}
+
+/// Calc HMAC-SHA1, and encode via base64
+pub fn hmac_sha1_base64(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> String {
+    encode_base64(hmac_sha1_raw(key, input))
+}
+
+/// Calc HMAC-SHA256, and encode via hex
+pub fn hmac_sha256(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> String {
+    hex::encode(hmac_sha256_raw(key, input))
+}
+
+/// Calc HMAC-SHA256
+pub fn hmac_sha256_raw(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> Vec<u8> {
+    hmac_raw::<Hmac<Sha256>>(key.as_ref(), input.as_ref())
+}
+
+/// Calc HMAC-SHA256, and encode via base64
+pub fn hmac_sha256_base64(key: impl AsRef<[u8]>, input: impl AsRef<[u8]>) -> String {
+    encode_base64(hmac_sha256_raw(key, input))
+}
+
+/// Calc HMAC by using the MAC, e.g. `Hmac<Sha256>`
+fn hmac_raw<M: Mac + KeyInit>(key: &[u8], input: &[u8]) -> Vec<u8> {
+    let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(input);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Compare bytes in constant time, to avoid timing side channels when verifying signatures
+///
+/// Only the length of inputs may be leaked.
+pub fn constant_time_eq(a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 /// Encode base64
 pub fn encode_base64(input: impl AsRef<[u8]>) -> String {
     general_purpose::STANDARD.encode(input)
@@ -123,4 +184,64 @@ mod tests {
             output
         );
     }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231, test case 2 & 6
+        let output = hmac_sha256("Jefe", "what do ya want for nothing?");
+        assert_eq!(
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            output
+        );
+
+        let key = [0xaa; 131];
+        let output = hmac_sha256(
+            key,
+            "Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!(
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            output
+        );
+    }
+
+    #[test]
+    fn test_hmac_md5() {
+        // RFC 2202, test case 2 & 6
+        let output = hmac_md5("Jefe", "what do ya want for nothing?");
+        assert_eq!("750c783e6ab0b503eaa86e310a5db738", output);
+
+        let key = [0xaa; 80];
+        let output = hmac_md5(
+            key,
+            "Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!("6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd", output);
+    }
+
+    #[test]
+    fn test_hmac_sha1() {
+        // RFC 2202, test case 2 & 6
+        let output = hmac_sha1("Jefe", "what do ya want for nothing?");
+        assert_eq!("effcdf6ae5eb2fa2d27416d5f184df9c259a7c79", output);
+
+        let key = [0xaa; 80];
+        let output = hmac_sha1(
+            key,
+            "Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        assert_eq!("aa4ae5e15272d00e95705637ce8a3b55ed402112", output);
+        assert_eq!(
+            "7/zfauXrL6LSdBbV8YTfnCWafHk=",
+            hmac_sha1_base64("Jefe", "what do ya want for nothing?")
+        );
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("signature", "signature"));
+        assert!(!constant_time_eq("signature", "signaturE"));
+        assert!(!constant_time_eq("signature", "sign"));
+        assert!(constant_time_eq([], []));
+    }
 }
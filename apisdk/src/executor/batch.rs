@@ -0,0 +1,240 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::{channel::oneshot, future::BoxFuture};
+
+use crate::{ApiError, ApiResult};
+
+/// The closure to fetch values of many keys by one request
+type FetchFn<K, V> = dyn Fn(Vec<K>) -> BoxFuture<'static, ApiResult<HashMap<K, V>>> + Send + Sync;
+
+/// The sender to reply a single fetch
+type Reply<V> = oneshot::Sender<ApiResult<Option<V>>>;
+
+/// The action after a key is enqueued
+enum Action<K, V> {
+    /// Wait for others in the same window, since the batch is started by this call
+    Lead(u64),
+    /// Join the batch started by others
+    Join,
+    /// Send the full batch immediately
+    Flush(Batch<K, V>),
+}
+
+/// The batch under collection
+struct Batch<K, V> {
+    /// The id of batch
+    id: u64,
+    /// The keys and their waiters
+    entries: Vec<(K, Reply<V>)>,
+}
+
+/// The state of batcher
+struct State<K, V> {
+    /// The id of last batch
+    last_id: u64,
+    /// The batch under collection
+    pending: Option<Batch<K, V>>,
+}
+
+/// This struct is used to coalesce many single-key fetches into one multi-key request.
+///
+/// The keys requested within `window` are collected, and `fetch` is invoked once with the distinct ones.
+/// The result of `fetch` is fanned out to all callers by key, and a missing key will be `None`.
+/// If `fetch` fails, all callers will receive `ApiError::Batch` with the shared error,
+/// so the status and `is_retryable` are kept. `ApiError::BatchCancelled` is returned if the batch is dropped before replying.
+///
+/// The combined request is sent by a spawned task, so it's not cancelled when any caller is dropped.
+/// It requires the `tokio` runtime.
+///
+/// It's not available on `wasm32`.
+///
+/// # Examples
+///
+/// ```
+/// let api = Arc::new(api);
+/// let batcher = RequestBatcher::new(move |ids: Vec<u64>| {
+///     let api = api.clone();
+///     async move {
+///         let req = api.get("/items").await?;
+///         let ids = ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
+///         let items: Vec<Item> = send!(req.query(&[("ids", ids)]), CodeDataMessage).await?;
+///         Ok(items.into_iter().map(|item| (item.id, item)).collect())
+///     }
+/// })
+/// .with_window(Duration::from_millis(5));
+///
+/// let item: Option<Item> = batcher.get(1).await?;
+/// ```
+pub struct RequestBatcher<K, V> {
+    /// Fetch values of many keys
+    fetch: Arc<FetchFn<K, V>>,
+    /// The time window to collect keys
+    window: Duration,
+    /// The max number of keys in one batch
+    max_batch_size: usize,
+    /// The state of batcher
+    state: Arc<Mutex<State<K, V>>>,
+}
+
+impl<K, V> Clone for RequestBatcher<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            fetch: self.fetch.clone(),
+            window: self.window,
+            max_batch_size: self.max_batch_size,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<K, V> std::fmt::Debug for RequestBatcher<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestBatcher")
+            .field("window", &self.window)
+            .field("max_batch_size", &self.max_batch_size)
+            .finish()
+    }
+}
+
+impl<K, V> RequestBatcher<K, V>
+where
+    K: Eq + Hash + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    /// Create a new instance
+    /// - fetch: build and send the combined request, and split the response by key
+    ///
+    /// The default window is 10ms, and the default max batch size is 100.
+    pub fn new<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn(Vec<K>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ApiResult<HashMap<K, V>>> + Send + 'static,
+    {
+        Self {
+            fetch: Arc::new(move |keys| -> BoxFuture<'static, _> { Box::pin(fetch(keys)) }),
+            window: Duration::from_millis(10),
+            max_batch_size: 100,
+            state: Arc::new(Mutex::new(State {
+                last_id: 0,
+                pending: None,
+            })),
+        }
+    }
+
+    /// Set the time window to collect keys
+    /// - window: the time window
+    pub fn with_window(self, window: Duration) -> Self {
+        Self { window, ..self }
+    }
+
+    /// Set the max number of keys in one batch, and a full batch will be sent immediately
+    /// - max_batch_size: the max number of keys, at least 1
+    pub fn with_max_batch_size(self, max_batch_size: usize) -> Self {
+        Self {
+            max_batch_size: max_batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Fetch the value of single key, along with others in the same window
+    /// - key: the key
+    ///
+    /// Return `None` if the key is absent in the response.
+    pub async fn get(&self, key: K) -> ApiResult<Option<V>> {
+        let (tx, rx) = oneshot::channel();
+        match self.enqueue(key, tx)? {
+            Action::Lead(id) => {
+                let batcher = self.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(batcher.window).await;
+                    if let Ok(Some(batch)) = batcher.take(id) {
+                        batcher.dispatch(batch).await;
+                    }
+                });
+            }
+            Action::Join => {}
+            Action::Flush(batch) => {
+                let batcher = self.clone();
+                tokio::spawn(async move { batcher.dispatch(batch).await });
+            }
+        }
+
+        match rx.await {
+            Ok(result) => result,
+            Err(_) => Err(ApiError::BatchCancelled),
+        }
+    }
+
+    /// Add the key to pending batch, or start a new one
+    fn enqueue(&self, key: K, tx: Reply<V>) -> ApiResult<Action<K, V>> {
+        let mut state = self.lock()?;
+        let action = match state.pending.as_mut() {
+            Some(batch) => {
+                batch.entries.push((key, tx));
+                Action::Join
+            }
+            None => {
+                state.last_id += 1;
+                state.pending = Some(Batch {
+                    id: state.last_id,
+                    entries: vec![(key, tx)],
+                });
+                Action::Lead(state.last_id)
+            }
+        };
+        match state.pending.take() {
+            Some(batch) if batch.entries.len() >= self.max_batch_size => Ok(Action::Flush(batch)),
+            pending => {
+                state.pending = pending;
+                Ok(action)
+            }
+        }
+    }
+
+    /// Take the pending batch, if it's still the one started by this call
+    fn take(&self, id: u64) -> ApiResult<Option<Batch<K, V>>> {
+        let mut state = self.lock()?;
+        match state.pending.as_ref() {
+            Some(batch) if batch.id == id => Ok(state.pending.take()),
+            _ => Ok(None),
+        }
+    }
+
+    /// Lock the state
+    fn lock(&self) -> ApiResult<std::sync::MutexGuard<'_, State<K, V>>> {
+        self.state
+            .lock()
+            .map_err(|e| ApiError::Other(format!("The batcher is poisoned: {}", e)))
+    }
+
+    /// Fetch the batch, and fan out the result
+    async fn dispatch(&self, batch: Batch<K, V>) {
+        let mut seen = HashSet::new();
+        let keys: Vec<K> = batch
+            .entries
+            .iter()
+            .filter(|(key, _)| seen.insert((*key).clone()))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        match (self.fetch)(keys).await {
+            Ok(values) => {
+                for (key, tx) in batch.entries {
+                    let _ = tx.send(Ok(values.get(&key).cloned()));
+                }
+            }
+            Err(e) => {
+                let e = Arc::new(e);
+                for (_, tx) in batch.entries {
+                    let _ = tx.send(Err(ApiError::Batch(e.clone())));
+                }
+            }
+        }
+    }
+}
@@ -1,17 +1,61 @@
 use std::collections::HashMap;
 
+use bytes::Bytes;
 use http::StatusCode;
-use reqwest::{header::CONTENT_TYPE, Response, ResponseBuilderExt};
-use serde::Serialize;
+#[cfg(any(feature = "sse", not(target_arch = "wasm32")))]
+use reqwest::header::ACCEPT;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::header::TE;
+use reqwest::{
+    header::{HeaderMap, CONNECTION, CONTENT_TYPE, ETAG, IF_RANGE, LOCATION, RANGE},
+    Response, ResponseBuilderExt, Version,
+};
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 #[cfg(feature = "tracing")]
 use tracing::Instrument;
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{extractor::decode_json_seq, BodyWithTrailers};
+#[cfg(feature = "sse")]
+use crate::{extractor::decode_sse, SseEvent};
 use crate::{
-    get_default_log_level, ApiError, ApiResult, FormLike, IntoFilter, LogConfig, Logger, MimeType,
-    MockServer, RequestBuilder, RequestId, RequestTraceIdMiddleware, Responder, ResponseBody,
+    get_default_log_level, ApiError, ApiName, ApiResponse, ApiResult, CacheKey, CachedResponse,
+    CloseConnection, ConditionalRequest, ContentRange, ContentSniffing, FormLike, IntoFilter,
+    JsonOptions, JsonValidator, LenientJson, LogConfig, LogSink, Logger, MimeType, MockServer,
+    PartialContent, ProblemDetails, RequestBuilder, RequestId, RequestTraceIdMiddleware,
+    RequestValidator, Responder, ResponseBody, ResponseCache, ResumedDownload, RetainRawBytes,
+    SignatureVerifier, TraceHeaderNames,
 };
 
+/// Create the tracing span of API call
+/// - name: the name of span, e.g. `API call / send`
+/// - config: the RequestConfigurator
+/// - req_type: the type of request payload, e.g. `json`
+#[cfg(feature = "tracing")]
+macro_rules! api_span {
+    ($name:literal, $config:expr) => {
+        api_span!($name, $config, tracing::field::Empty)
+    };
+    ($name:literal, $config:expr, $req_type:expr) => {
+        tracing::info_span!(
+            $name,
+            otel.name = format!("[API] {}", $config.get_caller()),
+            "api.func" = $config.log_target,
+            "api.name" = tracing::field::Empty,
+            "req.headers" = tracing::field::Empty,
+            "http.method" = tracing::field::Empty,
+            "http.host" = tracing::field::Empty,
+            "http.status_code" = tracing::field::Empty,
+            "http.version" = tracing::field::Empty,
+            "req.type" = $req_type,
+            "resp.type" = tracing::field::Empty,
+            "error" = tracing::field::Empty,
+            "exception" = tracing::field::Empty,
+        )
+    };
+}
+
 /// This struct is used to build RequestConfig internally by macros.
 #[derive(Debug, Default)]
 pub struct RequestConfigurator {
@@ -58,19 +102,35 @@ impl RequestConfigurator {
     fn build(self, req: &mut RequestBuilder) -> (Logger, bool) {
         let extensions = req.extensions();
 
-        let log_filter = extensions
-            .get::<LogConfig>()
+        let log_config = extensions.get::<LogConfig>();
+        let log_filter = log_config
             .map(|config| config.level)
             .or(self.log_filter)
             .unwrap_or(get_default_log_level());
+        let log_format = log_config.map(|config| config.format).unwrap_or_default();
+        let pretty_json = log_config.is_some_and(|config| config.pretty_json);
+        let slow_threshold = log_config.and_then(|config| config.slow_threshold);
 
         let request_id = extensions
             .get::<RequestId>()
             .map(|id| id.request_id.clone())
             .unwrap_or_default();
 
+        let sink = extensions.get::<LogSink>().cloned();
+
+        let name = extensions.get::<ApiName>();
+        #[cfg(feature = "tracing")]
+        if let Some(name) = name {
+            tracing::Span::current().record("api.name", name.0.as_str());
+        }
+
         (
-            Logger::new(self.log_target, log_filter, request_id),
+            Logger::new(self.log_target, log_filter, request_id)
+                .with_name(name)
+                .with_sink(sink)
+                .with_format(log_format)
+                .with_pretty_json(pretty_json)
+                .with_slow_threshold(slow_threshold),
             self.require_headers,
         )
     }
@@ -82,14 +142,7 @@ impl RequestConfigurator {
 pub async fn send(req: RequestBuilder, config: RequestConfigurator) -> ApiResult<ResponseBody> {
     #[cfg(feature = "tracing")]
     {
-        let span = tracing::info_span!(
-            "API call / send",
-            otel.name = format!("[API] {}", config.get_caller()),
-            "api.func" = config.log_target,
-            "resp.type" = tracing::field::Empty,
-            "error" = tracing::field::Empty,
-            "exception" = tracing::field::Empty,
-        );
+        let span = api_span!("API call / send", config);
         with_span(do_send(req, config), span, || {}).await
     }
     #[cfg(not(feature = "tracing"))]
@@ -106,37 +159,86 @@ async fn do_send(mut req: RequestBuilder, config: RequestConfigurator) -> ApiRes
     send_and_parse(req, logger, require_headers).await
 }
 
+/// Send request, and keep status and headers of response
+/// - req: used to build request
+/// - config: control the send process
+pub async fn send_full(
+    req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<ApiResponse<ResponseBody>> {
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / send_full", config);
+        let mut meta = None;
+        let body = with_span(
+            async {
+                let res = do_send_full(req, config).await?;
+                meta = Some((res.status, res.version, res.headers));
+                Ok(res.body)
+            },
+            span,
+            || {},
+        )
+        .await?;
+        let (status, version, headers) = meta.ok_or(ApiError::Impossible)?;
+        Ok(ApiResponse {
+            status,
+            version,
+            headers,
+            body,
+        })
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_send_full(req, config).await
+}
+async fn do_send_full(
+    mut req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<ApiResponse<ResponseBody>> {
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    let (logger, require_headers) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    send_and_parse_full(req, logger, require_headers).await
+}
+
 /// Send request with JSON payload
 /// - req: used to build request
 /// - json: request payload
 /// - config: control the send process
 pub async fn send_json<I>(
-    req: RequestBuilder,
+    mut req: RequestBuilder,
     json: &I,
     config: RequestConfigurator,
 ) -> ApiResult<ResponseBody>
 where
     I: Serialize + ?Sized,
 {
-    let req = req.json(json);
+    let mut json = serde_json::to_value(json).map_err(|e| ApiError::SerializeRequest {
+        mime: MimeType::Json,
+        detail: e.to_string(),
+    })?;
+    if let Some(options) = req.extensions().get::<JsonOptions>() {
+        options.apply(&mut json);
+    }
+    if let Some(validator) = req.extensions().get::<RequestValidator>() {
+        validator
+            .validate(&json)
+            .map_err(|detail| ApiError::SerializeRequest {
+                mime: MimeType::Json,
+                detail,
+            })?;
+    }
+    let req = req.json(&json);
 
     #[cfg(feature = "tracing")]
     {
-        let span = tracing::info_span!(
-            "API call / send_json",
-            otel.name = format!("[API] {}", config.get_caller()),
-            "api.func" = config.log_target,
-            "req.type" = "json",
-            "resp.type" = tracing::field::Empty,
-            "error" = tracing::field::Empty,
-            "exception" = tracing::field::Empty,
-        );
-        with_span(do_send_json(req, json, config), span, || {
-            tracing::info!(
-                name = "request",
-                json = serde_json::to_string(json).unwrap_or_default(),
-                "request.json",
-            );
+        let span = api_span!("API call / send_json", config, "json");
+        with_span(do_send_json(req, json.clone(), config), span, || {
+            tracing::info!(name = "request", json = json.to_string(), "request.json",);
         })
         .await
     }
@@ -144,23 +246,16 @@ where
     do_send_json(req, json, config).await
 }
 
-async fn do_send_json<I>(
+async fn do_send_json(
     mut req: RequestBuilder,
-    json: &I,
+    json: Value,
     config: RequestConfigurator,
-) -> ApiResult<ResponseBody>
-where
-    I: Serialize + ?Sized,
-{
+) -> ApiResult<ResponseBody> {
     // Inject extensions
     req = RequestTraceIdMiddleware::inject_extension(req);
     let (logger, require_headers) = config.build(&mut req);
     if logger.is_enabled() {
-        req = req.with_extension(
-            logger
-                .clone()
-                .with_json(serde_json::to_value(json).unwrap_or_default()),
-        );
+        req = req.with_extension(logger.clone().with_json(json));
     }
 
     send_and_parse(req, logger, require_headers).await
@@ -178,20 +273,15 @@ pub async fn send_xml<I>(
 where
     I: Serialize + ?Sized,
 {
-    let xml = quick_xml::se::to_string(xml)?;
+    let xml = quick_xml::se::to_string(xml).map_err(|e| ApiError::SerializeRequest {
+        mime: MimeType::Xml,
+        detail: e.to_string(),
+    })?;
     let req = req.header(CONTENT_TYPE, MimeType::Xml).body(xml.clone());
 
     #[cfg(feature = "tracing")]
     {
-        let span = tracing::info_span!(
-            "API call / send_xml",
-            otel.name = format!("[API] {}", config.get_caller()),
-            "api.func" = config.log_target,
-            "req.type" = "xml",
-            "resp.type" = tracing::field::Empty,
-            "error" = tracing::field::Empty,
-            "exception" = tracing::field::Empty,
-        );
+        let span = api_span!("API call / send_xml", config, "xml");
         with_span(do_send_xml(req, xml.clone(), config), span, || {
             tracing::info!(name = "request", xml = xml, "request.xml",);
         })
@@ -235,22 +325,14 @@ where
         if let Some(multipart) = form.get_multipart() {
             req = req.multipart(multipart)
         }
-    } else if let Some(form) = form.get_form() {
-        req = req.form(&form);
-    };
+    } else {
+        req = req.form(&form.get_pairs()?);
+    }
 
     #[cfg(feature = "tracing")]
     {
         let type_name = if is_multipart { "multipart" } else { "form" };
-        let span = tracing::info_span!(
-            "API call / send_form",
-            otel.name = format!("[API] {}", config.get_caller()),
-            "api.func" = config.log_target,
-            "req.type" = type_name,
-            "resp.type" = tracing::field::Empty,
-            "error" = tracing::field::Empty,
-            "exception" = tracing::field::Empty,
-        );
+        let span = api_span!("API call / send_form", config, type_name);
         with_span(
             do_send_form(req, is_multipart, meta.clone(), config),
             span,
@@ -309,15 +391,7 @@ where
 
     #[cfg(feature = "tracing")]
     {
-        let span = tracing::info_span!(
-            "API call / send_multipart",
-            otel.name = format!("[API] {}", config.get_caller()),
-            "api.func" = config.log_target,
-            "req.type" = "multipart",
-            "resp.type" = tracing::field::Empty,
-            "error" = tracing::field::Empty,
-            "exception" = tracing::field::Empty,
-        );
+        let span = api_span!("API call / send_multipart", config, "multipart");
         with_span(do_send_multipart(req, meta.clone(), config), span, || {
             tracing::info!(
                 name = "request",
@@ -346,21 +420,73 @@ async fn do_send_multipart(
     send_and_parse(req, logger, require_headers).await
 }
 
+/// Send request with streamed payload
+/// - req: used to build request
+/// - stream: request payload, which will not be buffered
+/// - content_type: the value of `Content-Type` header
+/// - config: control the send process
+///
+/// The payload could not be logged or retried, and `MockServer` receives it as a streaming body.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn send_body_stream<S>(
+    req: RequestBuilder,
+    stream: S,
+    content_type: impl AsRef<str>,
+    config: RequestConfigurator,
+) -> ApiResult<ResponseBody>
+where
+    S: futures::TryStream + Send + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    Bytes: From<S::Ok>,
+{
+    let content_type = content_type.as_ref().to_string();
+    let req = req
+        .header(CONTENT_TYPE, content_type.as_str())
+        .body(reqwest::Body::wrap_stream(stream));
+
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / send_body_stream", config, "stream");
+        with_span(
+            do_send_body_stream(req, content_type.clone(), config),
+            span,
+            || {
+                tracing::info!(
+                    name = "request",
+                    content_type = content_type,
+                    "request.stream"
+                );
+            },
+        )
+        .await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_send_body_stream(req, content_type, config).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn do_send_body_stream(
+    mut req: RequestBuilder,
+    content_type: String,
+    config: RequestConfigurator,
+) -> ApiResult<ResponseBody> {
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    let (logger, require_headers) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone().with_stream(content_type));
+    }
+
+    send_and_parse(req, logger, require_headers).await
+}
+
 /// Send request, and get raw response
 /// - req: used to build request
 /// - config: control the send process
 pub async fn send_raw(req: RequestBuilder, config: RequestConfigurator) -> ApiResult<Response> {
     #[cfg(feature = "tracing")]
     {
-        let span = tracing::info_span!(
-            "API call / send_raw",
-            otel.name = format!("[API] {}", config.get_caller()),
-            "api.func" = config.log_target,
-            "req.type" = "raw",
-            "resp.type" = tracing::field::Empty,
-            "error" = tracing::field::Empty,
-            "exception" = tracing::field::Empty,
-        );
+        let span = api_span!("API call / send_raw", config, "raw");
         with_span_raw(do_send_raw(req, config), span).await
     }
     #[cfg(not(feature = "tracing"))]
@@ -378,6 +504,440 @@ async fn do_send_raw(mut req: RequestBuilder, config: RequestConfigurator) -> Ap
     send_and_unparse(req, logger).await
 }
 
+/// Send request, and read response body as bytes
+/// - req: used to build request
+/// - config: control the send process
+pub async fn send_bytes(req: RequestBuilder, config: RequestConfigurator) -> ApiResult<Vec<u8>> {
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / send_bytes", config);
+        with_span_of(do_send_bytes(req, config), span, "raw").await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_send_bytes(req, config).await
+}
+
+async fn do_send_bytes(mut req: RequestBuilder, config: RequestConfigurator) -> ApiResult<Vec<u8>> {
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    let (logger, _) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    let res = send_and_unparse(req, logger.clone()).await?;
+    let res = check_status(res, &logger).await?;
+
+    match res.bytes().await {
+        Ok(bytes) => {
+            logger.log_response_bytes(&bytes);
+            Ok(bytes.to_vec())
+        }
+        Err(e) => {
+            let e = ApiError::DecodeResponse(MimeType::Empty, e.to_string());
+            logger.log_error(&e);
+            Err(e)
+        }
+    }
+}
+
+/// Send request with `Range` header, and read partial content
+/// - req: used to build request
+/// - start: the first byte, inclusive
+/// - end: the last byte, inclusive, or `None` to read until the end
+/// - config: control the send process
+///
+/// The response should be `206 Partial Content`, and its `Content-Range` should match the request.
+pub async fn send_range(
+    req: RequestBuilder,
+    start: u64,
+    end: Option<u64>,
+    config: RequestConfigurator,
+) -> ApiResult<PartialContent> {
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / send_range", config);
+        with_span_of(do_send_range(req, start, end, config), span, "raw").await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_send_range(req, start, end, config).await
+}
+
+async fn do_send_range(
+    mut req: RequestBuilder,
+    start: u64,
+    end: Option<u64>,
+    config: RequestConfigurator,
+) -> ApiResult<PartialContent> {
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    req = req.header(RANGE, PartialContent::range_header(start, end));
+    let (logger, _) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    let res = send_and_unparse(req, logger.clone()).await?;
+    let res = check_status(res, &logger).await?;
+
+    // The server may ignore `Range`
+    let status = res.status();
+    if status != StatusCode::PARTIAL_CONTENT {
+        let e = ApiError::InvalidRange(format!("Expect 206 Partial Content, actual {}", status));
+        logger.log_error(&e);
+        return Err(e);
+    }
+
+    let range = ContentRange::parse(res.headers());
+    let bytes = match res.bytes().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let e = ApiError::DecodeResponse(MimeType::Empty, e.to_string());
+            logger.log_error(&e);
+            return Err(e);
+        }
+    };
+    logger.log_response_bytes(&bytes);
+    match PartialContent::validate(range, start, end, bytes.to_vec()) {
+        Ok(partial) => Ok(partial),
+        Err(e) => {
+            logger.log_error(&e);
+            Err(e)
+        }
+    }
+}
+
+/// Send request, and stream response body into file
+/// - req: used to build request
+/// - path: the path of file, which will be created or truncated
+/// - config: control the send process
+///
+/// Return the number of bytes written. The file will be removed if it fails to write.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn download_to_file(
+    req: RequestBuilder,
+    path: impl AsRef<std::path::Path>,
+    config: RequestConfigurator,
+) -> ApiResult<u64> {
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / download_to_file", config);
+        with_span_of(
+            do_download_to_file(req, path.as_ref(), config),
+            span,
+            "file",
+        )
+        .await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_download_to_file(req, path.as_ref(), config).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn do_download_to_file(
+    mut req: RequestBuilder,
+    path: &std::path::Path,
+    config: RequestConfigurator,
+) -> ApiResult<u64> {
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    let (logger, _) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    let res = send_and_unparse(req, logger.clone()).await?;
+    let res = check_status(res, &logger).await?;
+
+    match write_to_file(res, path, false).await {
+        Ok(written) => {
+            logger.log_response_file(path, written);
+            Ok(written)
+        }
+        Err(e) => {
+            let _ = tokio::fs::remove_file(path).await;
+            logger.log_error(&e);
+            Err(e)
+        }
+    }
+}
+
+/// Send request, and resume the download into a partial file
+/// - req: used to build request
+/// - path: the path of file, which may hold the downloaded part
+/// - if_range: the `ETag` of the downloaded part, which is sent as `If-Range`
+/// - config: control the send process
+///
+/// `Range: bytes=N-` is sent if the file has `N` bytes, and the response body will be appended.
+/// If the resource has changed, the server responds with the whole body, and the file will be truncated.
+/// The file is kept if it fails to write, so the download could be resumed again.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn resumable_download(
+    req: RequestBuilder,
+    path: impl AsRef<std::path::Path>,
+    if_range: Option<impl AsRef<str>>,
+    config: RequestConfigurator,
+) -> ApiResult<ResumedDownload> {
+    let if_range = if_range.map(|etag| etag.as_ref().to_string());
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / resumable_download", config);
+        with_span_of(
+            do_resumable_download(req, path.as_ref(), if_range, config),
+            span,
+            "file",
+        )
+        .await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_resumable_download(req, path.as_ref(), if_range, config).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn do_resumable_download(
+    mut req: RequestBuilder,
+    path: &std::path::Path,
+    if_range: Option<String>,
+    config: RequestConfigurator,
+) -> ApiResult<ResumedDownload> {
+    let offset = match tokio::fs::metadata(path).await {
+        Ok(meta) if meta.is_file() => meta.len(),
+        _ => 0,
+    };
+
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    if offset > 0 {
+        req = req.header(RANGE, PartialContent::range_header(offset, None));
+        if let Some(etag) = if_range {
+            req = req.header(IF_RANGE, etag);
+        }
+    }
+    let (logger, _) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    let res = send_and_unparse(req, logger.clone()).await?;
+    let etag = res
+        .headers()
+        .get(ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
+    // Check status code
+    let status = res.status();
+    if status == StatusCode::RANGE_NOT_SATISFIABLE
+        && offset > 0
+        && ContentRange::parse_unsatisfied(res.headers()) == Some(offset)
+    {
+        // The file has been downloaded completely
+        logger.log_response_file(path, 0);
+        return Ok(ResumedDownload {
+            written: 0,
+            length: offset,
+            resumed: true,
+            etag,
+        });
+    }
+    let res = check_status(res, &logger).await?;
+
+    let resumed = status == StatusCode::PARTIAL_CONTENT;
+    let range = match resumed {
+        true => match ContentRange::parse(res.headers()) {
+            Some(range) if range.start == offset => Some(range),
+            range => {
+                let e = ApiError::InvalidRange(format!(
+                    "Expect {}, actual {:?}",
+                    PartialContent::range_header(offset, None),
+                    range
+                ));
+                logger.log_error(&e);
+                return Err(e);
+            }
+        },
+        false => None,
+    };
+
+    let written = match write_to_file(res, path, resumed).await {
+        Ok(written) => written,
+        Err(e) => {
+            logger.log_error(&e);
+            return Err(e);
+        }
+    };
+    logger.log_response_file(path, written);
+    if let Some(range) = range.filter(|range| range.size() != written) {
+        let e = ApiError::InvalidRange(format!(
+            "Expect {} bytes, actual {} bytes",
+            range.size(),
+            written
+        ));
+        logger.log_error(&e);
+        return Err(e);
+    }
+
+    Ok(ResumedDownload {
+        written,
+        length: if resumed { offset + written } else { written },
+        resumed,
+        etag,
+    })
+}
+
+/// Write response body into file chunk by chunk
+/// - res: the response
+/// - path: the path of file
+/// - append: append to the file, or truncate it
+#[cfg(not(target_arch = "wasm32"))]
+async fn write_to_file(res: Response, path: &std::path::Path, append: bool) -> ApiResult<u64> {
+    use futures::StreamExt;
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .await?;
+    let mut stream = res.bytes_stream();
+    let mut written = 0u64;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(ApiError::from)?;
+        file.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+    }
+    file.flush().await?;
+    Ok(written)
+}
+
+/// Send request, and read response body with trailers
+/// - req: used to build request
+/// - config: control the send process
+///
+/// `TE: trailers` will be sent to indicate that trailers are acceptable.
+/// See `BodyWithTrailers` for the limitations.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn send_with_trailers(
+    req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<BodyWithTrailers> {
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / send_with_trailers", config);
+        with_span_of(do_send_with_trailers(req, config), span, "raw").await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_send_with_trailers(req, config).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn do_send_with_trailers(
+    mut req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<BodyWithTrailers> {
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    req = req.header(TE, "trailers");
+    let (logger, _) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    let res = send_and_unparse(req, logger.clone()).await?;
+    let res = check_status(res, &logger).await?;
+
+    match BodyWithTrailers::read(res).await {
+        Ok(body) => {
+            logger.log_response_bytes(&body.body);
+            Ok(body)
+        }
+        Err(e) => {
+            logger.log_error(&e);
+            Err(e)
+        }
+    }
+}
+
+/// Send request, and decode response as Server-Sent Events
+/// - req: used to build request
+/// - config: control the send process
+#[cfg(feature = "sse")]
+pub async fn send_sse(
+    req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<impl futures::Stream<Item = ApiResult<SseEvent>>> {
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / send_sse", config);
+        with_span_of(do_send_sse(req, config), span, "sse").await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_send_sse(req, config).await
+}
+
+#[cfg(feature = "sse")]
+async fn do_send_sse(
+    mut req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<impl futures::Stream<Item = ApiResult<SseEvent>>> {
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    req = req.header(ACCEPT, MimeType::EventStream);
+    let (logger, _) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    let res = send_and_unparse(req, logger.clone()).await?;
+    let res = check_status(res, &logger).await?;
+
+    Ok(decode_sse(res.bytes_stream()))
+}
+
+/// Send request, and decode response as JSON text sequences
+/// - req: used to build request
+/// - config: control the send process
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn send_json_seq<T>(
+    req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<impl futures::Stream<Item = ApiResult<T>>>
+where
+    T: DeserializeOwned,
+{
+    #[cfg(feature = "tracing")]
+    {
+        let span = api_span!("API call / send_json_seq", config);
+        with_span_of(do_send_json_seq::<T>(req, config), span, "json-seq").await
+    }
+    #[cfg(not(feature = "tracing"))]
+    do_send_json_seq::<T>(req, config).await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn do_send_json_seq<T>(
+    mut req: RequestBuilder,
+    config: RequestConfigurator,
+) -> ApiResult<impl futures::Stream<Item = ApiResult<T>>>
+where
+    T: DeserializeOwned,
+{
+    // Inject extensions
+    req = RequestTraceIdMiddleware::inject_extension(req);
+    req = req.header(ACCEPT, MimeType::JsonSeq);
+    let (logger, _) = config.build(&mut req);
+    if logger.is_enabled() {
+        req = req.with_extension(logger.clone());
+    }
+
+    let res = send_and_unparse(req, logger.clone()).await?;
+    let res = check_status(res, &logger).await?;
+
+    Ok(decode_json_seq(res.bytes_stream()))
+}
+
 /// Send request with a tracing span
 #[cfg(feature = "tracing")]
 async fn with_span<F, I>(f: F, span: tracing::Span, init: I) -> Result<ResponseBody, ApiError>
@@ -410,6 +970,14 @@ where
                     span.record("resp.type", "text");
                     tracing::info!(name = "response", text = text, "response.text",);
                 }
+                ResponseBody::Html(html) => {
+                    span.record("resp.type", "html");
+                    tracing::info!(name = "response", html = html, "response.html",);
+                }
+                ResponseBody::Raw(bytes) => {
+                    span.record("resp.type", "raw");
+                    tracing::info!(name = "response", size = bytes.len(), "response.raw",);
+                }
             },
             Err(e) => {
                 span.record("error", true);
@@ -453,10 +1021,38 @@ where
     future.instrument(span.clone()).await
 }
 
+/// Send request with a tracing span, and record the type of response if it succeeds
+#[cfg(feature = "tracing")]
+async fn with_span_of<F, T>(f: F, span: tracing::Span, resp_type: &'static str) -> ApiResult<T>
+where
+    F: std::future::Future<Output = ApiResult<T>>,
+{
+    let future = async {
+        let outcome = f.await;
+        match outcome.as_ref() {
+            Ok(_) => {
+                span.record("resp.type", resp_type);
+            }
+            Err(e) => {
+                span.record("error", true);
+                span.record("exception", e.to_string());
+                tracing::warn!(
+                    name = "exception",
+                    exception = e.to_string(),
+                    "response.error",
+                );
+            }
+        }
+        outcome
+    };
+    future.instrument(span.clone()).await
+}
+
 /// Send request, and return unparsed response
 /// - req: the request to send
 /// - logger: helper to log messages
 async fn send_and_unparse(mut req: RequestBuilder, logger: Logger) -> ApiResult<Response> {
+    req = apply_close_connection(req);
     let extensions = req.extensions();
 
     // Mock
@@ -467,16 +1063,12 @@ async fn send_and_unparse(mut req: RequestBuilder, logger: Logger) -> ApiResult<
         match mock.handle(req).await {
             Ok(body) => {
                 logger.log_mock_response_body(&body);
-                let (content_type, text) = match body {
-                    ResponseBody::Empty => (MimeType::Empty, "".to_string()),
-                    ResponseBody::Json(json) => (MimeType::Json, json.to_string()),
-                    ResponseBody::Xml(xml) => (MimeType::Xml, xml),
-                    ResponseBody::Text(text) => (MimeType::Text, text),
-                };
+                let content_type = body.mime_type();
+                let bytes = body.into_bytes();
                 let res = hyper::Response::builder()
                     .url(url)
                     .header(CONTENT_TYPE, content_type.to_string())
-                    .body(text)
+                    .body(bytes)
                     .map_err(|_| {
                         ApiError::Middleware(anyhow::format_err!("Failed to build response"))
                     })?;
@@ -493,17 +1085,72 @@ async fn send_and_unparse(mut req: RequestBuilder, logger: Logger) -> ApiResult<
     Ok(res)
 }
 
+/// Check status code, and turn error statuses and unfollowed redirects into ApiError
+/// - res: the response
+/// - logger: helper to log messages
+async fn check_status(res: Response, logger: &Logger) -> ApiResult<Response> {
+    let status = res.status();
+    if status.is_client_error() || status.is_server_error() {
+        let e = parse_status_error(res).await;
+        logger.log_error(&e);
+        Err(e)
+    } else if status.is_redirection() {
+        // The redirect is not followed (e.g. disabled by policy), or `304` is not served from cache
+        let location = res
+            .headers()
+            .get(LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let e = ApiError::Redirect(status.as_u16(), location);
+        logger.log_error(&e);
+        Err(e)
+    } else {
+        Ok(res)
+    }
+}
+
+/// Set `Connection: close` if `CloseConnection` is present
+fn apply_close_connection(mut req: RequestBuilder) -> RequestBuilder {
+    match req.extensions().get::<CloseConnection>() {
+        Some(_) => req.header(CONNECTION, "close"),
+        None => req,
+    }
+}
+
 /// Send request, and parse response as desired type
 /// - req: the request to send
 /// - logger: helper to log messages
 /// - require_headers: should zip headers into response body
 async fn send_and_parse(
-    mut req: RequestBuilder,
+    req: RequestBuilder,
     logger: Logger,
     require_headers: bool,
 ) -> ApiResult<ResponseBody> {
+    send_and_parse_full(req, logger, require_headers)
+        .await
+        .map(|res| res.body)
+}
+
+/// Send request, and parse response as desired type, along with status and headers
+/// - req: the request to send
+/// - logger: helper to log messages
+/// - require_headers: should zip headers into response body
+async fn send_and_parse_full(
+    mut req: RequestBuilder,
+    logger: Logger,
+    require_headers: bool,
+) -> ApiResult<ApiResponse<ResponseBody>> {
+    req = apply_close_connection(req);
     let extensions = req.extensions();
 
+    let retain_raw = extensions.get::<RetainRawBytes>().is_some();
+    let lenient = extensions.get::<LenientJson>().is_some();
+    let sniffing = extensions.get::<ContentSniffing>().is_some();
+    let trace_headers = extensions
+        .get::<TraceHeaderNames>()
+        .cloned()
+        .unwrap_or_default();
+
     // Mock
     if let Some(mock) = extensions.get::<MockServer>().cloned() {
         let req = req.build().map_err(ApiError::BuildRequest)?;
@@ -511,7 +1158,16 @@ async fn send_and_parse(
         match mock.handle(req).await {
             Ok(body) => {
                 logger.log_mock_response_body(&body);
-                return Ok(body);
+                let body = match retain_raw {
+                    true => ResponseBody::Raw(body.into_bytes()),
+                    false => body,
+                };
+                return Ok(ApiResponse {
+                    status: StatusCode::OK,
+                    version: Version::default(),
+                    headers: HeaderMap::new(),
+                    body,
+                });
             }
             Err(e) => {
                 logger.log_error(&e);
@@ -520,41 +1176,193 @@ async fn send_and_parse(
         }
     }
 
-    // Send the request
+    // Send the request, which could be served from cache unless raw bytes are required
+    let verifier = extensions.get::<SignatureVerifier>().cloned();
+    let cache = extensions.get::<ResponseCache>().cloned();
+    if cache.is_some() && !retain_raw {
+        req = req.with_extension(ConditionalRequest);
+    }
     let res = req.send().await?;
+    let cache_key = res.extensions().get::<CacheKey>().cloned();
+
+    // Return cached body for 304 Not Modified, and leave others to `check_status`
+    if res.status() == StatusCode::NOT_MODIFIED {
+        if let Some(cached) = res.extensions().get::<CachedResponse>().cloned() {
+            let CachedResponse {
+                mut headers, body, ..
+            } = cached;
+            // The headers of `304` replace the cached ones with the same name
+            for name in res.headers().keys() {
+                headers.remove(name);
+            }
+            for (name, value) in res.headers() {
+                headers.append(name, value.clone());
+            }
+            let zipped = collect_headers(&headers, require_headers, &trace_headers);
+            let body = inject_body_headers(body, zipped);
+            logger.log_response_cached(&body);
+            return Ok(ApiResponse {
+                status: res.status(),
+                version: res.version(),
+                headers,
+                body,
+            });
+        }
+    }
 
     // Check status code
     let status = res.status();
-    let res = if status.is_client_error() || status.is_server_error() {
-        let e = if status.is_client_error() {
-            ApiError::HttpClientStatus(status.as_u16(), status.to_string())
-        } else {
-            ApiError::HttpServerStatus(status.as_u16(), status.to_string())
-        };
-        logger.log_error(&e);
-        return Err(e);
-    } else {
-        res
+    let res = check_status(res, &logger).await?;
+
+    // Verify signature over raw payload
+    let res = match verifier {
+        Some(verifier) => verify_signature(res, &verifier, &logger).await?,
+        None => res,
     };
 
+    let version = res.version();
+    let headers = res.headers().clone();
+
     // Ignore all payload for 204 No Content
     if res.status() == StatusCode::NO_CONTENT {
-        return Ok(ResponseBody::Empty);
+        logger.log_response_empty();
+        return Ok(ApiResponse {
+            status,
+            version,
+            headers,
+            body: ResponseBody::Empty,
+        });
     }
 
-    // Check content-type, and parse payload
-    let content_type = res
+    // Keep raw bytes if required
+    if retain_raw {
+        let bytes = match res.bytes().await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let e = ApiError::DecodeResponse(MimeType::Empty, e.to_string());
+                logger.log_error(&e);
+                return Err(e);
+            }
+        };
+        logger.log_response_bytes(&bytes);
+        return Ok(ApiResponse {
+            status,
+            version,
+            headers,
+            body: ResponseBody::Raw(bytes),
+        });
+    }
+
+    // Treat zero-length payload as empty, regardless of status and content-type
+    if res.content_length() == Some(0) {
+        logger.log_response_empty();
+        return Ok(ApiResponse {
+            status,
+            version,
+            headers,
+            body: ResponseBody::Empty,
+        });
+    }
+
+    // Check content-type, and sniff payload only if the header is absent
+    let (res, content_type) = match res.headers().get(CONTENT_TYPE) {
+        Some(v) => {
+            let content_type = v.to_str().map(MimeType::from).unwrap_or(MimeType::Text);
+            (res, content_type)
+        }
+        None if sniffing => {
+            let (res, bytes) = buffer_response(res, &logger).await?;
+            (res, MimeType::sniff(&bytes))
+        }
+        None => (res, MimeType::Text),
+    };
+    let etag = res
         .headers()
-        .get(CONTENT_TYPE)
+        .get(ETAG)
         .and_then(|v| v.to_str().ok())
-        .map(MimeType::from)
-        .unwrap_or(MimeType::Text);
-    match content_type {
-        MimeType::Json => parse_as_json(res, content_type, logger, require_headers).await,
+        .map(|v| v.to_string());
+    let zipped = collect_headers(res.headers(), require_headers, &trace_headers);
+    let body = match content_type {
+        MimeType::Json => parse_as_json(res, content_type, logger).await,
         MimeType::Xml => parse_as_xml(res, content_type, logger).await,
-        MimeType::Text => parse_as_text(res, content_type, logger).await,
+        MimeType::Html | MimeType::Text if lenient => {
+            parse_as_lenient_json(res, content_type, logger).await
+        }
+        MimeType::Html | MimeType::Text | MimeType::EventStream | MimeType::JsonSeq => {
+            parse_as_text(res, content_type, logger).await
+        }
         _ => Err(ApiError::UnsupportedContentType(content_type)),
+    }?;
+
+    // Cache body by ETag, before headers are injected
+    if let (Some(cache), Some(key), Some(etag)) = (cache, cache_key, etag) {
+        cache.put(key, etag, headers.clone(), body.clone());
+    }
+
+    Ok(ApiResponse {
+        status,
+        version,
+        headers,
+        body: inject_body_headers(body, zipped),
+    })
+}
+
+/// Build error for failed response
+///
+/// The `application/problem+json` (RFC 7807) payload will be used to build the message if possible.
+async fn parse_status_error(res: Response) -> ApiError {
+    let status = res.status();
+    let is_problem = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(ProblemDetails::is_problem_json)
+        .unwrap_or_default();
+    if is_problem {
+        if let Ok(problem) = res.json::<ProblemDetails>().await {
+            return problem.into_api_error(status.as_u16());
+        }
+    }
+    ApiError::from_status(status.as_u16(), status)
+}
+
+/// Verify signature over raw payload, and rebuild response for parsing
+async fn verify_signature(
+    res: Response,
+    verifier: &SignatureVerifier,
+    logger: &Logger,
+) -> ApiResult<Response> {
+    let (res, bytes) = buffer_response(res, logger).await?;
+    if let Err(e) = verifier.verify(res.headers(), &bytes) {
+        logger.log_error(&e);
+        return Err(e);
     }
+    Ok(res)
+}
+
+/// Read the whole payload, and rebuild response from it, so it could be parsed later
+async fn buffer_response(res: Response, logger: &Logger) -> ApiResult<(Response, Bytes)> {
+    let status = res.status();
+    let version = res.version();
+    let url = res.url().clone();
+    let headers = res.headers().clone();
+    let bytes = match res.bytes().await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let e = ApiError::DecodeResponse(MimeType::Empty, e.to_string());
+            logger.log_error(&e);
+            return Err(e);
+        }
+    };
+
+    let mut res = hyper::Response::builder()
+        .status(status)
+        .version(version)
+        .url(url)
+        .body(bytes.clone())
+        .map_err(|_| ApiError::Middleware(anyhow::format_err!("Failed to build response")))?;
+    *res.headers_mut() = headers;
+    Ok((Response::from(res), bytes))
 }
 
 /// Parse response body to json
@@ -562,23 +1370,21 @@ async fn parse_as_json(
     res: Response,
     content_type: MimeType,
     logger: Logger,
-    require_headers: bool,
 ) -> ApiResult<ResponseBody> {
-    // Extract HTTP headers from response
-    let headers = if require_headers {
-        let mut headers = HashMap::new();
-        for (name, value) in res.headers() {
-            if let Ok(value) = value.to_str() {
-                headers.insert(name.to_string(), value.to_string());
-            }
+    // Decode response
+    let text = match res.text().await {
+        Ok(text) if text.is_empty() => {
+            logger.log_response_empty();
+            return Ok(ResponseBody::Empty);
+        }
+        Ok(text) => text,
+        Err(e) => {
+            let e = ApiError::DecodeResponse(content_type, e.to_string());
+            logger.log_error(&e);
+            return Err(e);
         }
-        Some(headers)
-    } else {
-        None
     };
-
-    // Decode response
-    let mut json = match res.json::<Value>().await {
+    let json = match serde_json::from_str::<Value>(&text) {
         Ok(json) => {
             logger.log_response_json(&json);
             json
@@ -590,17 +1396,89 @@ async fn parse_as_json(
         }
     };
 
-    // Inject headers as `__headers__` field into payload
-    // Extractor could parse the `__headers__` field if required
-    if let Some(headers) = headers {
-        if let Value::Object(m) = &mut json {
-            if let Ok(headers) = serde_json::to_value(headers) {
-                m.insert("__headers__".to_string(), headers);
-            }
+    Ok(ResponseBody::Json(json))
+}
+
+/// Parse response body to json, and fall back to text if failed
+async fn parse_as_lenient_json(
+    res: Response,
+    content_type: MimeType,
+    logger: Logger,
+) -> ApiResult<ResponseBody> {
+    // Decode response
+    let text = match res.text().await {
+        Ok(text) if text.is_empty() => {
+            logger.log_response_empty();
+            return Ok(ResponseBody::Empty);
+        }
+        Ok(text) => text,
+        Err(e) => {
+            let e = ApiError::DecodeResponse(content_type, e.to_string());
+            logger.log_error(&e);
+            return Err(e);
         }
+    };
+    if let Ok(json) = serde_json::from_str::<Value>(&text) {
+        logger.log_response_json(&json);
+        return Ok(ResponseBody::Json(json));
     }
 
-    Ok(ResponseBody::Json(json))
+    logger.log_response_text(&text);
+    match content_type {
+        MimeType::Html => Ok(ResponseBody::Html(text)),
+        _ => Ok(ResponseBody::Text(text)),
+    }
+}
+
+/// Extract HTTP headers of response if required
+fn collect_headers(
+    res_headers: &HeaderMap,
+    require_headers: bool,
+    trace_headers: &TraceHeaderNames,
+) -> Option<HashMap<String, String>> {
+    if !require_headers {
+        return None;
+    }
+    let mut headers = HashMap::new();
+    for (name, value) in res_headers {
+        if let Ok(value) = value.to_str() {
+            headers.insert(name.to_string(), value.to_string());
+        }
+    }
+    trace_headers.alias_defaults(&mut headers);
+    Some(headers)
+}
+
+/// Inject headers as `__headers__` field into json payload, and keep other payloads as is
+fn inject_body_headers(
+    body: ResponseBody,
+    headers: Option<HashMap<String, String>>,
+) -> ResponseBody {
+    match body {
+        ResponseBody::Json(json) => ResponseBody::Json(inject_headers(json, headers)),
+        body => body,
+    }
+}
+
+/// Inject headers as `__headers__` field into payload
+///
+/// Extractor could parse the `__headers__` field if required.
+/// Non-object payload is wrapped as `{"__body__": payload}` to carry the headers.
+fn inject_headers(json: Value, headers: Option<HashMap<String, String>>) -> Value {
+    let headers = match headers.map(serde_json::to_value) {
+        Some(Ok(headers)) => headers,
+        _ => return json,
+    };
+    match json {
+        Value::Object(mut m) => {
+            m.insert("__headers__".to_string(), headers);
+            Value::Object(m)
+        }
+        json => serde_json::json!({
+            "__body__": json,
+            "__headers__": headers,
+        }),
+    }
 }
 
 /// Parse response body to xml
@@ -611,6 +1489,10 @@ async fn parse_as_xml(
 ) -> ApiResult<ResponseBody> {
     // Decode response as text
     let text = match res.text().await {
+        Ok(text) if text.is_empty() => {
+            logger.log_response_empty();
+            return Ok(ResponseBody::Empty);
+        }
         Ok(text) => {
             logger.log_response_xml(&text);
             text
@@ -633,6 +1515,10 @@ async fn parse_as_text(
 ) -> ApiResult<ResponseBody> {
     // Decode response
     let text = match res.text().await {
+        Ok(text) if text.is_empty() => {
+            logger.log_response_empty();
+            return Ok(ResponseBody::Empty);
+        }
         Ok(text) => {
             logger.log_response_text(&text);
             text
@@ -644,5 +1530,8 @@ async fn parse_as_text(
         }
     };
 
-    Ok(ResponseBody::Text(text))
+    match content_type {
+        MimeType::Html => Ok(ResponseBody::Html(text)),
+        _ => Ok(ResponseBody::Text(text)),
+    }
 }
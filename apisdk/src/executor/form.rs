@@ -1,8 +1,11 @@
 use std::{borrow::Cow, collections::HashMap};
 
 use reqwest::multipart::{Form, Part};
+use serde::Serialize;
 use serde_json::Value;
 
+use crate::{ApiError, ApiResult, MimeType};
+
 /// This trait provides form related functions
 pub trait FormLike {
     /// Check whether the form is a multipart form
@@ -11,6 +14,17 @@ pub trait FormLike {
     fn get_meta(&self) -> HashMap<String, String>;
     /// Treat the form as an urlencoded form
     fn get_form(self) -> Option<HashMap<String, String>>;
+    /// Treat the form as an urlencoded form, which could carry repeated keys
+    ///
+    /// Return `ApiError::SerializeRequest` with the reason, if it's not an urlencoded form.
+    fn get_pairs(self) -> ApiResult<Vec<(String, String)>>
+    where
+        Self: Sized,
+    {
+        self.get_form()
+            .map(|form| form.into_iter().collect())
+            .ok_or_else(|| invalid_form("the payload is not an urlencoded form"))
+    }
     /// Treat the form as a multipart form
     fn get_multipart(self) -> Option<Form>;
 }
@@ -40,11 +54,95 @@ where
         Some(form)
     }
 
+    fn get_pairs(self) -> ApiResult<Vec<(String, String)>> {
+        Ok(self
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect())
+    }
+
     fn get_multipart(self) -> Option<Form> {
         None
     }
 }
 
+/// Build ApiError for the payload, which could not be sent as urlencoded form
+fn invalid_form(detail: impl ToString) -> ApiError {
+    ApiError::SerializeRequest {
+        mime: MimeType::Other("application/x-www-form-urlencoded".to_string()),
+        detail: detail.to_string(),
+    }
+}
+
+/// Describe the kind of json value in errors
+fn value_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Build ApiError for the json value, which is not an object
+fn not_object(value: &Value) -> ApiError {
+    invalid_form(format!(
+        "expected a json object to send as form, but got {}",
+        value_kind(value)
+    ))
+}
+
+/// Build the meta of form, and join the values of repeated keys by `,`
+fn join_meta(pairs: impl IntoIterator<Item = (String, String)>) -> HashMap<String, String> {
+    let mut meta: HashMap<String, String> = HashMap::new();
+    for (k, v) in pairs {
+        meta.entry(k)
+            .and_modify(|current| {
+                current.push(',');
+                current.push_str(&v);
+            })
+            .or_insert(v);
+    }
+    meta
+}
+
+/// This enum decides how to serialize `null` fields of json `Value` into form
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullPolicy {
+    /// Skip `null` fields
+    #[default]
+    Skip,
+    /// Send `null` fields as empty string
+    Empty,
+    /// Send `null` fields as literal `null`
+    Literal,
+}
+
+impl NullPolicy {
+    /// Convert the fields of json object to form fields
+    /// - value: json object
+    ///
+    /// Strings are sent without quotes, and nested arrays / objects are sent as json text.
+    /// Return `None` if the value is not an object.
+    pub fn to_form(&self, value: &Value) -> Option<HashMap<String, String>> {
+        match value {
+            Value::Object(map) => Some(
+                map.iter()
+                    .filter_map(|(k, v)| match (v, self) {
+                        (Value::Null, Self::Skip) => None,
+                        (Value::Null, Self::Empty) => Some((k.to_string(), String::new())),
+                        (Value::String(s), _) => Some((k.to_string(), s.clone())),
+                        (v, _) => Some((k.to_string(), v.to_string())),
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+}
+
 impl FormLike for Value {
     fn is_multipart(&self) -> bool {
         false
@@ -58,6 +156,10 @@ impl FormLike for Value {
         (&self).get_form()
     }
 
+    fn get_pairs(self) -> ApiResult<Vec<(String, String)>> {
+        (&self).get_pairs()
+    }
+
     fn get_multipart(self) -> Option<Form> {
         None
     }
@@ -69,28 +171,258 @@ impl FormLike for &Value {
     }
 
     fn get_meta(&self) -> HashMap<String, String> {
-        match self {
-            Value::Object(map) => {
-                let mut meta = HashMap::new();
-                for (k, v) in map {
-                    meta.insert(k.to_string(), v.to_string());
-                }
-                meta
-            }
-            _ => HashMap::new(),
+        NullPolicy::default().to_form(self).unwrap_or_default()
+    }
+
+    fn get_form(self) -> Option<HashMap<String, String>> {
+        NullPolicy::default().to_form(self)
+    }
+
+    fn get_pairs(self) -> ApiResult<Vec<(String, String)>> {
+        NullPolicy::default()
+            .to_form(self)
+            .map(|form| form.into_iter().collect())
+            .ok_or_else(|| not_object(self))
+    }
+
+    fn get_multipart(self) -> Option<Form> {
+        None
+    }
+}
+
+/// The JsonForm is json `Value` with customized `NullPolicy`
+///
+/// # Examples
+///
+/// ```
+/// let form = JsonForm::new(json!({"a": null, "b": 1})).with_null_policy(NullPolicy::Empty);
+/// let res: TypeOfResponse = send_form!(req, form).await?;
+///
+/// // user[name]=x&tags[]=a
+/// let form = JsonForm::new(json!({"user": {"name": "x"}, "tags": ["a"]})).with_brackets(ArrayStyle::Brackets);
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonForm {
+    /// The json object
+    value: Value,
+    /// The policy of `null` fields
+    null_policy: NullPolicy,
+    /// Flatten nested arrays / objects with bracketed keys, in the style of array items
+    brackets: Option<ArrayStyle>,
+}
+
+impl JsonForm {
+    /// Create a new JsonForm, which skips `null` fields
+    /// - value: json object
+    pub fn new(value: Value) -> Self {
+        Self {
+            value,
+            null_policy: NullPolicy::default(),
+            brackets: None,
+        }
+    }
+
+    /// Set the policy of `null` fields
+    /// - null_policy: NullPolicy
+    pub fn with_null_policy(self, null_policy: NullPolicy) -> Self {
+        Self {
+            null_policy,
+            ..self
+        }
+    }
+
+    /// Flatten nested arrays / objects with bracketed keys, e.g. `user[name]=x`, instead of json text
+    /// - array_style: the style of array items
+    ///
+    /// See `BracketForm` for more information.
+    pub fn with_brackets(self, array_style: ArrayStyle) -> Self {
+        Self {
+            brackets: Some(array_style),
+            ..self
+        }
+    }
+
+    /// Build the BracketForm, if bracketed keys are enabled
+    fn to_bracket_form(&self) -> Option<BracketForm> {
+        self.brackets.map(|array_style| {
+            BracketForm::new(self.value.clone())
+                .with_null_policy(self.null_policy)
+                .with_array_style(array_style)
+        })
+    }
+}
+
+impl FormLike for JsonForm {
+    fn is_multipart(&self) -> bool {
+        false
+    }
+
+    fn get_meta(&self) -> HashMap<String, String> {
+        match self.to_bracket_form() {
+            Some(form) => form.get_meta(),
+            None => self.null_policy.to_form(&self.value).unwrap_or_default(),
         }
     }
 
     fn get_form(self) -> Option<HashMap<String, String>> {
-        match self {
+        self.get_pairs()
+            .ok()
+            .map(|pairs| pairs.into_iter().collect())
+    }
+
+    fn get_pairs(self) -> ApiResult<Vec<(String, String)>> {
+        match self.to_bracket_form() {
+            Some(form) => form.get_pairs(),
+            None => self
+                .null_policy
+                .to_form(&self.value)
+                .map(|form| form.into_iter().collect())
+                .ok_or_else(|| not_object(&self.value)),
+        }
+    }
+
+    fn get_multipart(self) -> Option<Form> {
+        None
+    }
+}
+
+/// This enum decides how to encode array items into bracketed form keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayStyle {
+    /// Repeat empty brackets, e.g. `items[]=1&items[]=2`, which is used by Rails and PHP
+    #[default]
+    Brackets,
+    /// Put index into brackets, e.g. `items[0]=1&items[1]=2`
+    Indexed,
+}
+
+/// The BracketForm encodes nested json `Value` with Rails/PHP-style bracketed keys
+///
+/// - `{"items": [1, 2]}` -> `items[]=1&items[]=2`
+/// - `{"user": {"name": "x"}}` -> `user[name]=x`
+///
+/// # Examples
+///
+/// ```
+/// let form = BracketForm::from_serialize(&payload)?.with_array_style(ArrayStyle::Indexed);
+/// let res: TypeOfResponse = send_form!(req, form).await?;
+///
+/// // It could be used as query params as well
+/// let req = req.query(&BracketForm::new(json!({"filter": {"ids": [1, 2]}})).to_pairs());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BracketForm {
+    /// The json object
+    value: Value,
+    /// The policy of `null` fields
+    null_policy: NullPolicy,
+    /// The style of array items
+    array_style: ArrayStyle,
+}
+
+impl BracketForm {
+    /// Create a new BracketForm, which skips `null` fields
+    /// - value: json object
+    pub fn new(value: Value) -> Self {
+        Self {
+            value,
+            null_policy: NullPolicy::default(),
+            array_style: ArrayStyle::default(),
+        }
+    }
+
+    /// Create a new BracketForm from any serializable data, e.g. struct
+    /// - data: the data to serialize
+    pub fn from_serialize<T>(data: &T) -> ApiResult<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        serde_json::to_value(data)
+            .map(Self::new)
+            .map_err(invalid_form)
+    }
+
+    /// Set the policy of `null` fields
+    /// - null_policy: NullPolicy
+    pub fn with_null_policy(self, null_policy: NullPolicy) -> Self {
+        Self {
+            null_policy,
+            ..self
+        }
+    }
+
+    /// Set the style of array items
+    /// - array_style: ArrayStyle
+    pub fn with_array_style(self, array_style: ArrayStyle) -> Self {
+        Self {
+            array_style,
+            ..self
+        }
+    }
+
+    /// Encode as key-value pairs, in order
+    ///
+    /// Return empty pairs if the value is not an object.
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = vec![];
+        if let Value::Object(map) = &self.value {
+            for (k, v) in map {
+                self.encode(k.clone(), v, &mut pairs);
+            }
+        }
+        pairs
+    }
+
+    /// Encode value under the key recursively
+    fn encode(&self, key: String, value: &Value, pairs: &mut Vec<(String, String)>) {
+        match value {
+            Value::Null => match self.null_policy {
+                NullPolicy::Skip => {}
+                NullPolicy::Empty => pairs.push((key, String::new())),
+                NullPolicy::Literal => pairs.push((key, "null".to_string())),
+            },
+            Value::Bool(b) => pairs.push((key, b.to_string())),
+            Value::Number(n) => pairs.push((key, n.to_string())),
+            Value::String(s) => pairs.push((key, s.clone())),
+            Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    let key = match self.array_style {
+                        ArrayStyle::Brackets => format!("{}[]", key),
+                        ArrayStyle::Indexed => format!("{}[{}]", key, i),
+                    };
+                    self.encode(key, item, pairs);
+                }
+            }
             Value::Object(map) => {
-                let mut form = HashMap::new();
                 for (k, v) in map {
-                    form.insert(k.to_string(), v.to_string());
+                    self.encode(format!("{}[{}]", key, k), v, pairs);
                 }
-                Some(form)
             }
-            _ => Some(HashMap::new()),
+        }
+    }
+}
+
+impl FormLike for BracketForm {
+    fn is_multipart(&self) -> bool {
+        false
+    }
+
+    /// Repeated keys are joined by `,`
+    fn get_meta(&self) -> HashMap<String, String> {
+        join_meta(self.to_pairs())
+    }
+
+    /// Only the last one of repeated keys is kept, so use `get_pairs` instead
+    fn get_form(self) -> Option<HashMap<String, String>> {
+        self.get_pairs()
+            .ok()
+            .map(|pairs| pairs.into_iter().collect())
+    }
+
+    fn get_pairs(self) -> ApiResult<Vec<(String, String)>> {
+        match self.value {
+            Value::Object(_) => Ok(self.to_pairs()),
+            _ => Err(not_object(&self.value)),
         }
     }
 
@@ -99,6 +431,100 @@ impl FormLike for &Value {
     }
 }
 
+/// The OrderedForm is an urlencoded form, which keeps the order of fields and allows repeated keys
+///
+/// # Examples
+///
+/// ```
+/// let form = OrderedForm::new().append("tags", "a").append("tags", "b");
+/// // tags=a&tags=b
+/// let res: TypeOfResponse = send_form!(req, form).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OrderedForm {
+    /// The fields in order
+    pairs: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl OrderedForm {
+    /// Create a new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a field, and keep the existing ones with the same key
+    /// - name: the name of field
+    /// - value: the value of field
+    pub fn append<T, U>(self, name: T, value: U) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+        U: Into<Cow<'static, str>>,
+    {
+        let mut pairs = self.pairs;
+        pairs.push((name.into(), value.into()));
+        Self { pairs }
+    }
+
+    /// Append fields with the same key
+    /// - name: the name of fields
+    /// - values: the values of fields
+    pub fn append_all<T, I, U>(self, name: T, values: I) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = U>,
+        U: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+        values
+            .into_iter()
+            .fold(self, |form, value| form.append(name.clone(), value))
+    }
+
+    /// Get all values of the key, in order
+    /// - name: the name of field
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.pairs
+            .iter()
+            .filter(|(k, _)| k == name)
+            .map(|(_, v)| v.as_ref())
+            .collect()
+    }
+}
+
+impl FormLike for OrderedForm {
+    fn is_multipart(&self) -> bool {
+        false
+    }
+
+    /// Repeated keys are joined by `,`
+    fn get_meta(&self) -> HashMap<String, String> {
+        join_meta(
+            self.pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string())),
+        )
+    }
+
+    /// Only the last one of repeated keys is kept, so use `get_pairs` instead
+    fn get_form(self) -> Option<HashMap<String, String>> {
+        self.get_pairs()
+            .ok()
+            .map(|pairs| pairs.into_iter().collect())
+    }
+
+    fn get_pairs(self) -> ApiResult<Vec<(String, String)>> {
+        Ok(self
+            .pairs
+            .into_iter()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect())
+    }
+
+    fn get_multipart(self) -> Option<Form> {
+        None
+    }
+}
+
 impl<K, V> FormLike for HashMap<K, V>
 where
     K: ToString,
@@ -189,6 +615,67 @@ pub trait MultipartFormOps {
     fn part<T>(self, name: T, part: Part) -> Self
     where
         T: Into<Cow<'static, str>>;
+
+    /// Add a data field with supplied name, value and content-type.
+    ///
+    /// The field will be sent as `text/plain` if the content-type is invalid.
+    fn text_with_mime<T, U>(self, name: T, value: U, mime: MimeType) -> Self
+    where
+        Self: Sized,
+        T: Into<Cow<'static, str>>,
+        U: Into<Cow<'static, str>>,
+    {
+        self.part(name, build_text_part(value.into(), &mime))
+    }
+
+    /// Adds a customized Part with content-type.
+    ///
+    /// The content-type of part will be kept if the content-type is invalid.
+    fn part_with_mime<T>(self, name: T, part: Part, mime: MimeType) -> Self
+    where
+        Self: Sized,
+        T: Into<Cow<'static, str>>,
+    {
+        self.part(name, apply_mime(part, &mime))
+    }
+}
+
+/// Apply content-type to part
+/// - part: the part to update
+/// - mime: the content-type of part
+fn apply_mime(part: Part, mime: &MimeType) -> Part {
+    let mime = mime.to_string();
+    match mime.parse::<mime::Mime>() {
+        Ok(_) => part.mime_str(&mime).expect("mime type has been validated"),
+        Err(_) => part,
+    }
+}
+
+/// Guess content-type by the extension of filename
+///
+/// It requires `mime_guess` feature, otherwise `application/octet-stream` will be used.
+#[cfg(not(target_arch = "wasm32"))]
+fn guess_mime(filename: &str) -> MimeType {
+    #[cfg(feature = "mime_guess")]
+    return MimeType::Other(
+        mime_guess::from_path(filename)
+            .first_or_octet_stream()
+            .to_string(),
+    );
+    #[cfg(not(feature = "mime_guess"))]
+    {
+        let _ = filename;
+        MimeType::Other(mime::APPLICATION_OCTET_STREAM.to_string())
+    }
+}
+
+/// Build a text part with content-type
+/// - value: the value of part
+/// - mime: the content-type of part
+fn build_text_part(value: Cow<'static, str>, mime: &MimeType) -> Part {
+    Part::text(value.clone())
+        .mime_str(&mime.to_string())
+        .unwrap_or_else(|_| Part::text(value))
 }
 
 impl MultipartFormOps for Form {
@@ -219,6 +706,63 @@ impl MultipartForm {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Add a file field, which will be read as stream when sending.
+    /// - name: the name of field
+    /// - path: the path of file
+    ///
+    /// The filename and the content-type (guessed by extension, requires `mime_guess` feature) will be set.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn file<T>(self, name: T, path: impl AsRef<std::path::Path>) -> ApiResult<Self>
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        let length = file.metadata()?.len();
+        let filename = path
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let body = reqwest::Body::from(tokio::fs::File::from_std(file));
+        Ok(self.stream_part(name, filename, Part::stream_with_length(body, length)))
+    }
+
+    /// Add a field, which reads the content from stream.
+    /// - name: the name of field
+    /// - filename: the filename of field
+    /// - stream: the stream of bytes
+    ///
+    /// The content-type will be guessed by the extension of filename (requires `mime_guess` feature).
+    /// Since the length is unknown, the request will be sent with chunked transfer encoding.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn stream<T, F, S>(self, name: T, filename: F, stream: S) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+        F: Into<Cow<'static, str>>,
+        S: futures::TryStream + Send + 'static,
+        S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+        bytes::Bytes: From<S::Ok>,
+    {
+        let body = reqwest::Body::wrap_stream(stream);
+        self.stream_part(name, filename, Part::stream(body))
+    }
+
+    /// Add a streaming part with filename and guessed content-type
+    #[cfg(not(target_arch = "wasm32"))]
+    fn stream_part<T, F>(self, name: T, filename: F, part: Part) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+        F: Into<Cow<'static, str>>,
+    {
+        let Self { mut meta, mut form } = self;
+        let name = name.into();
+        let filename = filename.into();
+        let mime = guess_mime(filename.as_ref());
+        meta.insert(name.to_string(), format!("({}) @{}", mime, filename));
+        form = form.part(name, apply_mime(part.file_name(filename), &mime));
+        Self { meta, form }
+    }
 }
 
 impl FormLike for MultipartForm {
@@ -263,6 +807,30 @@ impl MultipartFormOps for MultipartForm {
         form = form.part(name, part);
         Self { meta, form }
     }
+
+    fn text_with_mime<T, U>(self, name: T, value: U, mime: MimeType) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+        U: Into<Cow<'static, str>>,
+    {
+        let Self { mut meta, mut form } = self;
+        let name = name.into();
+        let value = value.into();
+        meta.insert(name.to_string(), format!("({}) {}", mime, value));
+        form = form.part(name, build_text_part(value, &mime));
+        Self { meta, form }
+    }
+
+    fn part_with_mime<T>(self, name: T, part: Part, mime: MimeType) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let Self { mut meta, mut form } = self;
+        let name = name.into();
+        meta.insert(name.to_string(), format!("({}) {:?}", mime, part));
+        form = form.part(name, apply_mime(part, &mime));
+        Self { meta, form }
+    }
 }
 
 /// The DynamicForm is mixin of urlencoded form and multipart form
@@ -303,6 +871,31 @@ impl MultipartFormOps for DynamicForm {
             form: Some(form),
         }
     }
+
+    fn text_with_mime<T, U>(self, name: T, value: U, mime: MimeType) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+        U: Into<Cow<'static, str>>,
+    {
+        let Self { map, form } = self;
+        let form = form.unwrap_or_default().text_with_mime(name, value, mime);
+        Self {
+            map,
+            form: Some(form),
+        }
+    }
+
+    fn part_with_mime<T>(self, name: T, part: Part, mime: MimeType) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let Self { map, form } = self;
+        let form = form.unwrap_or_default().part_with_mime(name, part, mime);
+        Self {
+            map,
+            form: Some(form),
+        }
+    }
 }
 
 impl FormLike for DynamicForm {
@@ -343,3 +936,105 @@ impl FormLike for DynamicForm {
         Some(form)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ArrayStyle, BracketForm, FormLike, JsonForm, NullPolicy, OrderedForm};
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_bracket_array() {
+        let form = BracketForm::new(json!({ "items": [1, "two", true], "empty": [] }));
+        assert_eq!(
+            pairs(&[("items[]", "1"), ("items[]", "two"), ("items[]", "true")]),
+            form.to_pairs()
+        );
+
+        let form = form.with_array_style(ArrayStyle::Indexed);
+        assert_eq!(
+            pairs(&[("items[0]", "1"), ("items[1]", "two"), ("items[2]", "true")]),
+            form.to_pairs()
+        );
+    }
+
+    #[test]
+    fn test_bracket_nested_object() {
+        let form = BracketForm::new(json!({
+            "user": {
+                "name": "x",
+                "roles": [{ "id": 1 }, { "id": 2 }],
+                "nick": null,
+            },
+        }))
+        .with_null_policy(NullPolicy::Empty);
+        assert_eq!(
+            pairs(&[
+                ("user[name]", "x"),
+                ("user[nick]", ""),
+                ("user[roles][][id]", "1"),
+                ("user[roles][][id]", "2"),
+            ]),
+            form.to_pairs()
+        );
+    }
+
+    #[test]
+    fn test_ordered_form() {
+        let form = OrderedForm::new()
+            .append("tags", "a")
+            .append("q", "x")
+            .append_all("tags", ["b", "c"]);
+        assert_eq!(vec!["a", "b", "c"], form.get_all("tags"));
+        assert_eq!("a,b,c", form.get_meta()["tags"]);
+        assert_eq!(
+            pairs(&[("tags", "a"), ("q", "x"), ("tags", "b"), ("tags", "c")]),
+            form.get_pairs().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_form_scalars() {
+        let form = JsonForm::new(json!({
+            "s": "x y",
+            "n": 1.5,
+            "b": false,
+            "o": { "a": 1 },
+        }))
+        .get_form()
+        .unwrap();
+        assert_eq!("x y", form["s"]);
+        assert_eq!("1.5", form["n"]);
+        assert_eq!("false", form["b"]);
+        assert_eq!(r#"{"a":1}"#, form["o"]);
+    }
+
+    #[test]
+    fn test_json_form_brackets() {
+        let form = JsonForm::new(json!({ "o": { "a": "x" }, "items": [1, 2] }))
+            .with_brackets(ArrayStyle::Indexed);
+        assert_eq!("x", form.get_meta()["o[a]"]);
+        assert_eq!(
+            pairs(&[("items[0]", "1"), ("items[1]", "2"), ("o[a]", "x")]),
+            form.get_pairs().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_form_not_object() {
+        let e = JsonForm::new(json!([1, 2])).get_pairs().unwrap_err();
+        assert_eq!(
+            "Serialize request error: application/x-www-form-urlencoded => expected a json object to send as form, but got an array",
+            e.to_string()
+        );
+        let e = BracketForm::new(json!("x")).get_pairs().unwrap_err();
+        assert!(e.to_string().ends_with("but got a string"));
+    }
+}
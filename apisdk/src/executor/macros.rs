@@ -23,16 +23,33 @@ macro_rules! _function_path {
 ///     - send the request, verify response status, then discard response
 /// - `send!(req, Body)` -> `impl Future<Output = ApiResult<apisdk::ResponseBody>>`
 ///     - send the request, verify response status, and decode response body
+/// - `send!(req, Raw)` -> `impl Future<Output = ApiResult<apisdk::ResponseBody>>`
+///     - send the request, verify response status, and keep raw bytes as `ResponseBody::Raw`
 /// - `send!(req, Json)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as json, then use serde_json to deserialize it
+///     - the payload is kept verbatim, if `T` is `Box<serde_json::value::RawValue>`
+/// - `send!(req, RawJson)` -> `impl Future<Output = ApiResult<Box<serde_json::value::RawValue>>>`
+///     - send the request, and keep the json payload verbatim, so it's not reordered
 /// - `send!(req, Xml)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as xml, then use quick_xml to deserialize it
 /// - `send!(req, Text)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as text, then use FromStr to deserialize it
+/// - `send!(req, Html)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as html, then use FromStr to deserialize it
 /// - `send!(req, OtherType)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as json, and use `OtherType` as JsonExtractor
 /// - `send!(req, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as json, and use `OtherType` as JsonExtractor
+/// - `send!(req, Full<T>)` -> `impl Future<Output = ApiResult<apisdk::ApiResponse<T>>>`
+///     - send the request, parse response as json or xml based on response, and keep status and headers
+///     - no JsonExtractor is applied, and `__headers__` is not injected, so it works for arrays and scalar payloads
+///     - with `MockServer`, the status is always `200 OK` and the headers are empty
+/// - `send!(req, Allow)` -> `impl Future<Output = ApiResult<Vec<apisdk::Method>>>`
+///     - send the request (usually `OPTIONS`), verify response status, then parse `Allow` and `Access-Control-Allow-Methods` headers
+///     - with `MockServer`, it's always empty, since the mocked response has no header
+/// - `send!(req, ResourceInfo)` -> `impl Future<Output = ApiResult<apisdk::ResourceInfo>>`
+///     - send the request (usually `HEAD`), verify response status, then parse `Content-Length`, `Accept-Ranges`, `Content-Type` and `ETag` headers
+///     - with `MockServer`, all fields are unset, since the mocked response has no header
 ///
 /// ### Built-in JsonExtractors
 ///
@@ -44,6 +61,8 @@ macro_rules! _function_path {
 ///     - an alias of serde_json::Value
 /// - apisdk::CodeDataMessage
 ///     - parse `{code, data, message}` json payload, verify `code`, and return `data` field
+/// - apisdk::Either<L, R>
+///     - parse json payload as `L`, or `R` if failed
 ///
 /// # Examples
 ///
@@ -89,8 +108,41 @@ macro_rules! send {
             .and_then(|c| c.try_into())
         }
     };
+    ($req:expr, Raw) => {
+        $crate::__internal::send(
+            $req.with_extension($crate::RetainRawBytes),
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
     ($req:expr, Json) => {
-        $crate::send!($req, $crate::Json, ())
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send(
+                $crate::Json::retain_raw_for($req, &parse),
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, RawJson) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::send!(
+                $req.with_extension($crate::RetainRawBytes),
+                $crate::Json,
+                ()
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, Xml) => {
         $crate::send!($req, $crate::Xml, ())
@@ -98,6 +150,9 @@ macro_rules! send {
     ($req:expr, Text) => {
         $crate::send!($req, $crate::Text, ())
     };
+    ($req:expr, Html) => {
+        $crate::send!($req, $crate::Html, ())
+    };
     ($req:expr, $parser:ty, ()) => {
         async {
             let result = $crate::__internal::send(
@@ -112,6 +167,48 @@ macro_rules! send {
             <$parser>::try_parse(result)
         }
     };
+    ($req:expr, Full<$t:ty>) => {
+        async {
+            $crate::__internal::send_full(
+                $req,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?
+            .try_map($crate::Auto::try_parse::<$t>)
+        }
+    };
+    ($req:expr, Allow) => {
+        async {
+            $crate::__internal::send_full(
+                $req.with_extension($crate::RetainRawBytes),
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await
+            .map(|res| $crate::Allow::parse(&res.headers))
+        }
+    };
+    ($req:expr, ResourceInfo) => {
+        async {
+            $crate::__internal::send_full(
+                $req.with_extension($crate::RetainRawBytes),
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await
+            .map(|res| $crate::ResourceInfo::parse(&res.headers))
+        }
+    };
     ($req:expr, Json<$ve:ty>) => {
         $crate::send!($req, $crate::Json, $crate::JsonExtractor, $ve)
     };
@@ -130,6 +227,9 @@ macro_rules! send {
                 ),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
@@ -157,8 +257,34 @@ macro_rules! _send_with {
                 .and_then(|c| c.try_into())
         }
     };
+    ($req:expr, Raw, $config:expr) => {
+        $crate::__internal::send(
+            $req.with_extension($crate::RetainRawBytes),
+            $config.merge($crate::_function_path!(), false),
+        )
+    };
     ($req:expr, Json, $config:expr) => {
-        $crate::_send_with!($req, $crate::Json, (), $config)
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send(
+                $crate::Json::retain_raw_for($req, &parse),
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, RawJson, $config:expr) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::_send_with!(
+                $req.with_extension($crate::RetainRawBytes),
+                $crate::Json,
+                (),
+                $config
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, Xml, $config:expr) => {
         $crate::_send_with!($req, $crate::Xml, (), $config)
@@ -166,6 +292,9 @@ macro_rules! _send_with {
     ($req:expr, Text, $config:expr) => {
         $crate::_send_with!($req, $crate::Text, (), $config)
     };
+    ($req:expr, Html, $config:expr) => {
+        $crate::_send_with!($req, $crate::Html, (), $config)
+    };
     ($req:expr, $parser:ty, (), $config:expr) => {
         async {
             let result =
@@ -174,6 +303,33 @@ macro_rules! _send_with {
             <$parser>::try_parse(result)
         }
     };
+    ($req:expr, Full<$t:ty>, $config:expr) => {
+        async {
+            $crate::__internal::send_full($req, $config.merge($crate::_function_path!(), false))
+                .await?
+                .try_map($crate::Auto::try_parse::<$t>)
+        }
+    };
+    ($req:expr, Allow, $config:expr) => {
+        async {
+            $crate::__internal::send_full(
+                $req.with_extension($crate::RetainRawBytes),
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await
+            .map(|res| $crate::Allow::parse(&res.headers))
+        }
+    };
+    ($req:expr, ResourceInfo, $config:expr) => {
+        async {
+            $crate::__internal::send_full(
+                $req.with_extension($crate::RetainRawBytes),
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await
+            .map(|res| $crate::ResourceInfo::parse(&res.headers))
+        }
+    };
     ($req:expr, Json<$ve:ty>, $config:expr) => {
         $crate::_send_with!($req, $crate::Json, $crate::JsonExtractor, $ve, $config)
     };
@@ -188,6 +344,9 @@ macro_rules! _send_with {
                 $config.merge($crate::_function_path!(), <$ve>::require_headers()),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
@@ -206,10 +365,15 @@ macro_rules! _send_with {
 ///     - send json, verify response status, and decode response body
 /// - `send_json!(req, json, Json)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as json, then use serde_json to deserialize it
+///     - the payload is kept verbatim, if `T` is `Box<serde_json::value::RawValue>`
+/// - `send_json!(req, json, RawJson)` -> `impl Future<Output = ApiResult<Box<serde_json::value::RawValue>>>`
+///     - send the request, and keep the json payload verbatim, so it's not reordered
 /// - `send_json!(req, json, Xml)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as xml, then use quick_xml to deserialize it
 /// - `send_json!(req, json, Text)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as text, then use FromStr to deserialize it
+/// - `send_json!(req, json, Html)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as html, then use FromStr to deserialize it
 /// - `send_json!(req, json, OtherType)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send json, parse response as json, and use `OtherType` as JsonExtractor
 /// - `send_json!(req, json, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
@@ -262,7 +426,32 @@ macro_rules! send_json {
         }
     };
     ($req:expr, $json:expr, Json) => {
-        $crate::send_json!($req, $json, $crate::Json, ())
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_json(
+                $crate::Json::retain_raw_for($req, &parse),
+                &($json),
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $json:expr, RawJson) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::send_json!(
+                $req.with_extension($crate::RetainRawBytes),
+                $json,
+                $crate::Json,
+                ()
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, $json:expr, Xml) => {
         $crate::send_json!($req, $json, $crate::Xml, ())
@@ -270,6 +459,9 @@ macro_rules! send_json {
     ($req:expr, $json:expr, Text) => {
         $crate::send_json!($req, $json, $crate::Text, ())
     };
+    ($req:expr, $json:expr, Html) => {
+        $crate::send_json!($req, $json, $crate::Html, ())
+    };
     ($req:expr, $json:expr, $parser:ty, ()) => {
         async {
             let result = $crate::__internal::send_json(
@@ -304,6 +496,9 @@ macro_rules! send_json {
                 ),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
@@ -340,7 +535,29 @@ macro_rules! _send_json_with {
         }
     };
     ($req:expr, $json:expr, Json, $config:expr) => {
-        $crate::_send_json_with!($req, $json, $crate::Json, (), $config)
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_json(
+                $crate::Json::retain_raw_for($req, &parse),
+                &($json),
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $json:expr, RawJson, $config:expr) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::_send_json_with!(
+                $req.with_extension($crate::RetainRawBytes),
+                $json,
+                $crate::Json,
+                (),
+                $config
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, $json:expr, Xml, $config:expr) => {
         $crate::_send_json_with!($req, $json, $crate::Xml, (), $config)
@@ -348,6 +565,9 @@ macro_rules! _send_json_with {
     ($req:expr, $json:expr, Text, $config:expr) => {
         $crate::_send_json_with!($req, $json, $crate::Text, (), $config)
     };
+    ($req:expr, $json:expr, Html, $config:expr) => {
+        $crate::_send_json_with!($req, $json, $crate::Html, (), $config)
+    };
     ($req:expr, $json:expr, $parser:ty, (), $config:expr) => {
         async {
             let result = $crate::__internal::send_json(
@@ -388,6 +608,9 @@ macro_rules! _send_json_with {
                 $config.merge($crate::_function_path!(), <$ve>::require_headers()),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
@@ -406,10 +629,14 @@ macro_rules! _send_json_with {
 ///     - send xml, verify response status, and decode response body
 /// - `send_xml!(req, xml, Json)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as json, then use serde_json to deserialize it
+/// - `send_xml!(req, xml, RawJson)` -> `impl Future<Output = ApiResult<Box<serde_json::value::RawValue>>>`
+///     - send the request, and keep the json payload verbatim, so it's not reordered
 /// - `send_xml!(req, xml, Xml)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as xml, then use quick_xml to deserialize it
 /// - `send_xml!(req, xml, Text)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as text, then use FromStr to deserialize it
+/// - `send_xml!(req, xml, Html)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as html, then use FromStr to deserialize it
 /// - `send_xml!(req, xml, OtherType)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send xml, parse response as json, and use `OtherType` as JsonExtractor
 /// - `send_xml!(req, xml, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
@@ -465,7 +692,32 @@ macro_rules! send_xml {
         }
     };
     ($req:expr, $xml:expr, Json) => {
-        $crate::send_xml!($req, $xml, $crate::Json, ())
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_xml(
+                $crate::Json::retain_raw_for($req, &parse),
+                &($xml),
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $xml:expr, RawJson) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::send_xml!(
+                $req.with_extension($crate::RetainRawBytes),
+                $xml,
+                $crate::Json,
+                ()
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, $xml:expr, Xml) => {
         $crate::send_xml!($req, $xml, $crate::Xml, ())
@@ -473,6 +725,9 @@ macro_rules! send_xml {
     ($req:expr, $xml:expr, Text) => {
         $crate::send_xml!($req, $xml, $crate::Text, ())
     };
+    ($req:expr, $xml:expr, Html) => {
+        $crate::send_xml!($req, $xml, $crate::Html, ())
+    };
     ($req:expr, $xml:expr, $parser:ty, ()) => {
         async {
             let result = $crate::__internal::send_xml(
@@ -507,6 +762,9 @@ macro_rules! send_xml {
                 ),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
@@ -543,7 +801,29 @@ macro_rules! _send_xml_with {
         }
     };
     ($req:expr, $xml:expr, Json, $config:expr) => {
-        $crate::_send_xml_with!($req, $xml, $crate::Json, (), $config)
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_xml(
+                $crate::Json::retain_raw_for($req, &parse),
+                &($xml),
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $xml:expr, RawJson, $config:expr) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::_send_xml_with!(
+                $req.with_extension($crate::RetainRawBytes),
+                $xml,
+                $crate::Json,
+                (),
+                $config
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, $xml:expr, Xml, $config:expr) => {
         $crate::_send_xml_with!($req, $xml, $crate::Xml, (), $config)
@@ -551,6 +831,9 @@ macro_rules! _send_xml_with {
     ($req:expr, $xml:expr, Text, $config:expr) => {
         $crate::_send_xml_with!($req, $xml, $crate::Text, (), $config)
     };
+    ($req:expr, $xml:expr, Html, $config:expr) => {
+        $crate::_send_xml_with!($req, $xml, $crate::Html, (), $config)
+    };
     ($req:expr, $xml:expr, $parser:ty, (), $config:expr) => {
         async {
             let result = $crate::__internal::send_xml(
@@ -591,67 +874,63 @@ macro_rules! _send_xml_with {
                 $config.merge($crate::_function_path!(), <$ve>::require_headers()),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
     };
 }
 
-/// Send the payload as form
+/// Send the payload from a stream, which will not be buffered (not available on wasm32)
 ///
 /// # Forms
 ///
-/// - `send_form!(req, form)` -> `impl Future<Output = ApiResult<T>>`
-///     - send form, and parse response as json or xml based on response
-/// - `send_form!(req, form, ())` -> `impl Future<Output = ApiResult<()>>`
-///     - send form, verify response status, then discard response
-/// - `send_form!(req, form, Body)` -> `impl Future<Output = ApiResult<apisdk::ResponseBody>>`
-///     - send form, verify response status, and decode response body
-/// - `send_form!(req, form, Json)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_body_stream!(req, stream, content_type)` -> `impl Future<Output = ApiResult<T>>`
+///     - send stream with `Content-Type`, and parse response as json or xml based on response
+/// - `send_body_stream!(req, stream, content_type, ())` -> `impl Future<Output = ApiResult<()>>`
+///     - send stream, verify response status, then discard response
+/// - `send_body_stream!(req, stream, content_type, Body)` -> `impl Future<Output = ApiResult<apisdk::ResponseBody>>`
+///     - send stream, verify response status, and decode response body
+/// - `send_body_stream!(req, stream, content_type, Json)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as json, then use serde_json to deserialize it
-/// - `send_form!(req, form, Xml)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_body_stream!(req, stream, content_type, RawJson)` -> `impl Future<Output = ApiResult<Box<serde_json::value::RawValue>>>`
+///     - send the request, and keep the json payload verbatim, so it's not reordered
+/// - `send_body_stream!(req, stream, content_type, Xml)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as xml, then use quick_xml to deserialize it
-/// - `send_form!(req, form, Text)`-> `impl Future<Output = ApiResult<T>>`
+/// - `send_body_stream!(req, stream, content_type, Text)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as text, then use FromStr to deserialize it
-/// - `send_form!(req, form, OtherType)` -> `impl Future<Output = ApiResult<T>>`
-///     - send form, parse response as json, and use `OtherType` as JsonExtractor
-/// - `send_form!(req, form, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
-///     - send form, parse response as json, and use `OtherType` as JsonExtractor
-///
-/// # Examples
-///
-/// ### Use HashMap to build form
+/// - `send_body_stream!(req, stream, content_type, Html)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as html, then use FromStr to deserialize it
+/// - `send_body_stream!(req, stream, content_type, OtherType)` -> `impl Future<Output = ApiResult<T>>`
+///     - send stream, parse response as json, and use `OtherType` as JsonExtractor
+/// - `send_body_stream!(req, stream, content_type, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
+///     - send stream, parse response as json, and use `OtherType` as JsonExtractor
 ///
-/// ```
-/// let mut form = HashMap::new();
-/// form.insert("key", "value");
-/// let req = client.post("/path/api").await?;
-/// let res: TypeOfResponse = send_form!(req, form).await?;
-/// ```
+/// The stream should yield `Result<Bytes, E>`. Since the payload is not buffered, it is logged as a placeholder,
+/// and the request could not be retried.
 ///
-/// ### Use DynamicForm to build form
+/// # Examples
 ///
 /// ```
-/// use apisdk::DynamicForm;
-///
-/// let mut form = DynamicForm::new();
-/// form.text("key", "value");
-/// form.pair("part", Part::text("part-value"));
+/// let chunks = futures::stream::iter(lines.into_iter().map(|line| Ok::<_, std::io::Error>(Bytes::from(line))));
 /// let req = client.post("/path/api").await?;
-/// let res: TypeOfResponse = send_form!(req, form).await?;
+/// let res: TypeOfResponse = send_body_stream!(req, chunks, "application/x-ndjson").await?;
 /// ```
 ///
 /// Please reference `send` for more information
 #[macro_export]
-macro_rules! send_form {
-    ($req:expr, $form:expr) => {
-        $crate::send_form!($req, $form, $crate::Auto, ())
+macro_rules! send_body_stream {
+    ($req:expr, $stream:expr, $content_type:expr) => {
+        $crate::send_body_stream!($req, $stream, $content_type, $crate::Auto, ())
     };
-    ($req:expr, $form:expr, ()) => {
+    ($req:expr, $stream:expr, $content_type:expr, ()) => {
         async {
-            let _ = $crate::__internal::send_form(
+            let _ = $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $crate::__internal::RequestConfigurator::new(
                     $crate::_function_path!(),
                     None::<bool>,
@@ -662,11 +941,12 @@ macro_rules! send_form {
             Ok(())
         }
     };
-    ($req:expr, $form:expr, Body) => {
+    ($req:expr, $stream:expr, $content_type:expr, Body) => {
         async {
-            $crate::__internal::send_form(
+            $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $crate::__internal::RequestConfigurator::new(
                     $crate::_function_path!(),
                     None::<bool>,
@@ -677,20 +957,51 @@ macro_rules! send_form {
             .and_then(|c| c.try_into())
         }
     };
-    ($req:expr, $form:expr, Json) => {
-        $crate::send_form!($req, $form, $crate::Json, ())
+    ($req:expr, $stream:expr, $content_type:expr, Json) => {
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_body_stream(
+                $crate::Json::retain_raw_for($req, &parse),
+                $stream,
+                $content_type,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            parse(result)
+        }
     };
-    ($req:expr, $form:expr, Xml) => {
-        $crate::send_form!($req, $form, $crate::Xml, ())
+    ($req:expr, $stream:expr, $content_type:expr, RawJson) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::send_body_stream!(
+                $req.with_extension($crate::RetainRawBytes),
+                $stream,
+                $content_type,
+                $crate::Json,
+                ()
+            )
+            .await?;
+            Ok(raw)
+        }
     };
-    ($req:expr, $form:expr, Text) => {
-        $crate::send_form!($req, $form, $crate::Text, ())
+    ($req:expr, $stream:expr, $content_type:expr, Xml) => {
+        $crate::send_body_stream!($req, $stream, $content_type, $crate::Xml, ())
     };
-    ($req:expr, $form:expr, $parser:ty, ()) => {
+    ($req:expr, $stream:expr, $content_type:expr, Text) => {
+        $crate::send_body_stream!($req, $stream, $content_type, $crate::Text, ())
+    };
+    ($req:expr, $stream:expr, $content_type:expr, Html) => {
+        $crate::send_body_stream!($req, $stream, $content_type, $crate::Html, ())
+    };
+    ($req:expr, $stream:expr, $content_type:expr, $parser:ty, ()) => {
         async {
-            let result = $crate::__internal::send_form(
+            let result = $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $crate::__internal::RequestConfigurator::new(
                     $crate::_function_path!(),
                     None::<bool>,
@@ -701,18 +1012,33 @@ macro_rules! send_form {
             <$parser>::try_parse(result)
         }
     };
-    ($req:expr, $form:expr, Json<$ve:ty>) => {
-        $crate::send_form!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
+    ($req:expr, $stream:expr, $content_type:expr, Json<$ve:ty>) => {
+        $crate::send_body_stream!(
+            $req,
+            $stream,
+            $content_type,
+            $crate::Json,
+            $crate::JsonExtractor,
+            $ve
+        )
     };
-    ($req:expr, $form:expr, $ve:ty) => {
-        $crate::send_form!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
+    ($req:expr, $stream:expr, $content_type:expr, $ve:ty) => {
+        $crate::send_body_stream!(
+            $req,
+            $stream,
+            $content_type,
+            $crate::Json,
+            $crate::JsonExtractor,
+            $ve
+        )
     };
-    ($req:expr, $form:expr, $parser:ty, $vet:ty, $ve:ty) => {
+    ($req:expr, $stream:expr, $content_type:expr, $parser:ty, $vet:ty, $ve:ty) => {
         async {
             use $vet;
-            let result = $crate::__internal::send_form(
+            let result = $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $crate::__internal::RequestConfigurator::new(
                     $crate::_function_path!(),
                     None::<bool>,
@@ -720,6 +1046,9 @@ macro_rules! send_form {
                 ),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
@@ -729,131 +1058,180 @@ macro_rules! send_form {
 /// Internal macro
 #[macro_export]
 #[doc(hidden)]
-macro_rules! _send_form_with {
-    ($req:expr, $form:expr, $config:expr) => {
-        $crate::_send_form_with!($req, $form, $crate::Auto, (), $config)
+macro_rules! _send_body_stream_with {
+    ($req:expr, $stream:expr, $content_type:expr, $config:expr) => {
+        $crate::_send_body_stream_with!($req, $stream, $content_type, $crate::Auto, (), $config)
     };
-    ($req:expr, $form:expr, (), $config:expr) => {
+    ($req:expr, $stream:expr, $content_type:expr, (), $config:expr) => {
         async {
-            let _ = $crate::__internal::send_form(
+            let _ = $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $config.merge($crate::_function_path!(), false),
             )
             .await?;
             Ok(())
         }
     };
-    ($req:expr, $form:expr, Body, $config:expr) => {
+    ($req:expr, $stream:expr, $content_type:expr, Body, $config:expr) => {
         async {
-            $crate::__internal::send_form(
+            $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $config.merge($crate::_function_path!(), true),
             )
             .await
             .and_then(|c| c.try_into())
         }
     };
-    ($req:expr, $form:expr, Json, $config:expr) => {
-        $crate::_send_form_with!($req, $form, $crate::Json, (), $config)
+    ($req:expr, $stream:expr, $content_type:expr, Json, $config:expr) => {
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_body_stream(
+                $crate::Json::retain_raw_for($req, &parse),
+                $stream,
+                $content_type,
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            parse(result)
+        }
     };
-    ($req:expr, $form:expr, Xml, $config:expr) => {
-        $crate::_send_form_with!($req, $form, $crate::Xml, (), $config)
+    ($req:expr, $stream:expr, $content_type:expr, RawJson, $config:expr) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::_send_body_stream_with!(
+                $req.with_extension($crate::RetainRawBytes),
+                $stream,
+                $content_type,
+                $crate::Json,
+                (),
+                $config
+            )
+            .await?;
+            Ok(raw)
+        }
     };
-    ($req:expr, $form:expr, Text, $config:expr) => {
-        $crate::_send_form_with!($req, $form, $crate::Text, (), $config)
+    ($req:expr, $stream:expr, $content_type:expr, Xml, $config:expr) => {
+        $crate::_send_body_stream_with!($req, $stream, $content_type, $crate::Xml, (), $config)
     };
-    ($req:expr, $form:expr, $parser:ty, (), $config:expr) => {
+    ($req:expr, $stream:expr, $content_type:expr, Text, $config:expr) => {
+        $crate::_send_body_stream_with!($req, $stream, $content_type, $crate::Text, (), $config)
+    };
+    ($req:expr, $stream:expr, $content_type:expr, Html, $config:expr) => {
+        $crate::_send_body_stream_with!($req, $stream, $content_type, $crate::Html, (), $config)
+    };
+    ($req:expr, $stream:expr, $content_type:expr, $parser:ty, (), $config:expr) => {
         async {
-            let result = $crate::__internal::send_form(
+            let result = $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $config.merge($crate::_function_path!(), false),
             )
             .await?;
             <$parser>::try_parse(result)
         }
     };
-    ($req:expr, $form:expr, Json<$ve:ty>, $config:expr) => {
-        $crate::_send_form_with!(
+    ($req:expr, $stream:expr, $content_type:expr, Json<$ve:ty>, $config:expr) => {
+        $crate::_send_body_stream_with!(
             $req,
-            $form,
+            $stream,
+            $content_type,
             $crate::Json,
             $crate::JsonExtractor,
             $ve,
             $config
         )
     };
-    ($req:expr, $form:expr, $ve:ty, $config:expr) => {
-        $crate::_send_form_with!(
+    ($req:expr, $stream:expr, $content_type:expr, $ve:ty, $config:expr) => {
+        $crate::_send_body_stream_with!(
             $req,
-            $form,
+            $stream,
+            $content_type,
             $crate::Json,
             $crate::JsonExtractor,
             $ve,
             $config
         )
     };
-    ($req:expr, $form:expr, $parser:ty, $vet:ty, $ve:ty, $config:expr) => {
+    ($req:expr, $stream:expr, $content_type:expr, $parser:ty, $vet:ty, $ve:ty, $config:expr) => {
         async {
             use $vet;
-            let result = $crate::__internal::send_form(
+            let result = $crate::__internal::send_body_stream(
                 $req,
-                $form,
+                $stream,
+                $content_type,
                 $config.merge($crate::_function_path!(), <$ve>::require_headers()),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
     };
 }
 
-/// Send the payload as multipart form
+/// Send the payload as form
 ///
 /// # Forms
 ///
-/// - `send_multipart!(req, form)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_form!(req, form)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send form, and parse response as json or xml based on response
-/// - `send_multipart!(req, form, ())` -> `impl Future<Output = ApiResult<()>>`
+/// - `send_form!(req, form, ())` -> `impl Future<Output = ApiResult<()>>`
 ///     - send form, verify response status, then discard response
-/// - `send_multipart!(req, form, Body)` -> `impl Future<Output = ApiResult<apisdk::ResponseBody>>`
+/// - `send_form!(req, form, Body)` -> `impl Future<Output = ApiResult<apisdk::ResponseBody>>`
 ///     - send form, verify response status, and decode response body
-/// - `send_multipart!(req, form, Json)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_form!(req, form, Json)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as json, then use serde_json to deserialize it
-/// - `send_multipart!(req, form, Xml)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_form!(req, form, RawJson)` -> `impl Future<Output = ApiResult<Box<serde_json::value::RawValue>>>`
+///     - send the request, and keep the json payload verbatim, so it's not reordered
+/// - `send_form!(req, form, Xml)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as xml, then use quick_xml to deserialize it
-/// - `send_multipart!(req, form, Text)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_form!(req, form, Text)`-> `impl Future<Output = ApiResult<T>>`
 ///     - send the request, parse response as text, then use FromStr to deserialize it
-/// - `send_multipart!(req, form, OtherType)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_form!(req, form, Html)`-> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as html, then use FromStr to deserialize it
+/// - `send_form!(req, form, OtherType)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send form, parse response as json, and use `OtherType` as JsonExtractor
-/// - `send_multipart!(req, form, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
+/// - `send_form!(req, form, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
 ///     - send form, parse response as json, and use `OtherType` as JsonExtractor
 ///
 /// # Examples
 ///
-/// ### Use MultipartForm to build form
+/// ### Use HashMap to build form
 ///
 /// ```
-/// use apisdk::MultipartForm;
+/// let mut form = HashMap::new();
+/// form.insert("key", "value");
+/// let req = client.post("/path/api").await?;
+/// let res: TypeOfResponse = send_form!(req, form).await?;
+/// ```
 ///
-/// let mut form = MultipartForm::new();
+/// ### Use DynamicForm to build form
+///
+/// ```
+/// use apisdk::DynamicForm;
+///
+/// let mut form = DynamicForm::new();
 /// form.text("key", "value");
 /// form.pair("part", Part::text("part-value"));
 /// let req = client.post("/path/api").await?;
-/// let res: TypeOfResponse = send_multipart!(req, form).await?;
+/// let res: TypeOfResponse = send_form!(req, form).await?;
 /// ```
 ///
 /// Please reference `send` for more information
 #[macro_export]
-macro_rules! send_multipart {
+macro_rules! send_form {
     ($req:expr, $form:expr) => {
-        $crate::send_multipart!($req, $form, $crate::Auto, ())
+        $crate::send_form!($req, $form, $crate::Auto, ())
     };
     ($req:expr, $form:expr, ()) => {
         async {
-            let _ = $crate::__internal::send_multipart(
+            let _ = $crate::__internal::send_form(
                 $req,
                 $form,
                 $crate::__internal::RequestConfigurator::new(
@@ -868,7 +1246,7 @@ macro_rules! send_multipart {
     };
     ($req:expr, $form:expr, Body) => {
         async {
-            $crate::__internal::send_multipart(
+            $crate::__internal::send_form(
                 $req,
                 $form,
                 $crate::__internal::RequestConfigurator::new(
@@ -882,17 +1260,45 @@ macro_rules! send_multipart {
         }
     };
     ($req:expr, $form:expr, Json) => {
-        $crate::send_multipart!($req, $form, $crate::Json, ())
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_form(
+                $crate::Json::retain_raw_for($req, &parse),
+                $form,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $form:expr, RawJson) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::send_form!(
+                $req.with_extension($crate::RetainRawBytes),
+                $form,
+                $crate::Json,
+                ()
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, $form:expr, Xml) => {
-        $crate::send_multipart!($req, $form, $crate::Xml, ())
+        $crate::send_form!($req, $form, $crate::Xml, ())
     };
     ($req:expr, $form:expr, Text) => {
-        $crate::send_multipart!($req, $form, $crate::Text, ())
+        $crate::send_form!($req, $form, $crate::Text, ())
+    };
+    ($req:expr, $form:expr, Html) => {
+        $crate::send_form!($req, $form, $crate::Html, ())
     };
     ($req:expr, $form:expr, $parser:ty, ()) => {
         async {
-            let result = $crate::__internal::send_multipart(
+            let result = $crate::__internal::send_form(
                 $req,
                 $form,
                 $crate::__internal::RequestConfigurator::new(
@@ -906,15 +1312,15 @@ macro_rules! send_multipart {
         }
     };
     ($req:expr, $form:expr, Json<$ve:ty>) => {
-        $crate::send_multipart!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
+        $crate::send_form!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
     };
     ($req:expr, $form:expr, $ve:ty) => {
-        $crate::send_multipart!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
+        $crate::send_form!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
     };
     ($req:expr, $form:expr, $parser:ty, $vet:ty, $ve:ty) => {
         async {
             use $vet;
-            let result = $crate::__internal::send_multipart(
+            let result = $crate::__internal::send_form(
                 $req,
                 $form,
                 $crate::__internal::RequestConfigurator::new(
@@ -924,6 +1330,9 @@ macro_rules! send_multipart {
                 ),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
@@ -933,13 +1342,13 @@ macro_rules! send_multipart {
 /// Internal macro
 #[macro_export]
 #[doc(hidden)]
-macro_rules! _send_multipart_with {
+macro_rules! _send_form_with {
     ($req:expr, $form:expr, $config:expr) => {
-        $crate::_send_multipart_with!($req, $form, $crate::Auto, (), $config)
+        $crate::_send_form_with!($req, $form, $crate::Auto, (), $config)
     };
     ($req:expr, $form:expr, (), $config:expr) => {
         async {
-            let _ = $crate::__internal::send_multipart(
+            let _ = $crate::__internal::send_form(
                 $req,
                 $form,
                 $config.merge($crate::_function_path!(), false),
@@ -950,7 +1359,7 @@ macro_rules! _send_multipart_with {
     };
     ($req:expr, $form:expr, Body, $config:expr) => {
         async {
-            $crate::__internal::send_multipart(
+            $crate::__internal::send_form(
                 $req,
                 $form,
                 $config.merge($crate::_function_path!(), true),
@@ -960,17 +1369,42 @@ macro_rules! _send_multipart_with {
         }
     };
     ($req:expr, $form:expr, Json, $config:expr) => {
-        $crate::_send_multipart_with!($req, $form, $crate::Json, (), $config)
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_form(
+                $crate::Json::retain_raw_for($req, &parse),
+                $form,
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $form:expr, RawJson, $config:expr) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::_send_form_with!(
+                $req.with_extension($crate::RetainRawBytes),
+                $form,
+                $crate::Json,
+                (),
+                $config
+            )
+            .await?;
+            Ok(raw)
+        }
     };
     ($req:expr, $form:expr, Xml, $config:expr) => {
-        $crate::_send_multipart_with!($req, $form, $crate::Xml, (), $config)
+        $crate::_send_form_with!($req, $form, $crate::Xml, (), $config)
     };
     ($req:expr, $form:expr, Text, $config:expr) => {
-        $crate::_send_multipart_with!($req, $form, $crate::Text, (), $config)
+        $crate::_send_form_with!($req, $form, $crate::Text, (), $config)
+    };
+    ($req:expr, $form:expr, Html, $config:expr) => {
+        $crate::_send_form_with!($req, $form, $crate::Html, (), $config)
     };
     ($req:expr, $form:expr, $parser:ty, (), $config:expr) => {
         async {
-            let result = $crate::__internal::send_multipart(
+            let result = $crate::__internal::send_form(
                 $req,
                 $form,
                 $config.merge($crate::_function_path!(), false),
@@ -980,7 +1414,7 @@ macro_rules! _send_multipart_with {
         }
     };
     ($req:expr, $form:expr, Json<$ve:ty>, $config:expr) => {
-        $crate::_send_multipart_with!(
+        $crate::_send_form_with!(
             $req,
             $form,
             $crate::Json,
@@ -990,7 +1424,7 @@ macro_rules! _send_multipart_with {
         )
     };
     ($req:expr, $form:expr, $ve:ty, $config:expr) => {
-        $crate::_send_multipart_with!(
+        $crate::_send_form_with!(
             $req,
             $form,
             $crate::Json,
@@ -1002,35 +1436,706 @@ macro_rules! _send_multipart_with {
     ($req:expr, $form:expr, $parser:ty, $vet:ty, $ve:ty, $config:expr) => {
         async {
             use $vet;
-            let result = $crate::__internal::send_multipart(
+            let result = $crate::__internal::send_form(
                 $req,
                 $form,
                 $config.merge($crate::_function_path!(), <$ve>::require_headers()),
             )
             .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
             let result = <$parser>::try_parse::<$ve>(result)?;
             <$ve>::try_extract(result)
         }
     };
 }
 
-/// Send and get raw response
+/// Send the payload as multipart form
 ///
 /// # Forms
 ///
-/// - `send_raw!(req)`
-///     - send request, and return raw response
+/// - `send_multipart!(req, form)` -> `impl Future<Output = ApiResult<T>>`
+///     - send form, and parse response as json or xml based on response
+/// - `send_multipart!(req, form, ())` -> `impl Future<Output = ApiResult<()>>`
+///     - send form, verify response status, then discard response
+/// - `send_multipart!(req, form, Body)` -> `impl Future<Output = ApiResult<apisdk::ResponseBody>>`
+///     - send form, verify response status, and decode response body
+/// - `send_multipart!(req, form, Json)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as json, then use serde_json to deserialize it
+/// - `send_multipart!(req, form, RawJson)` -> `impl Future<Output = ApiResult<Box<serde_json::value::RawValue>>>`
+///     - send the request, and keep the json payload verbatim, so it's not reordered
+/// - `send_multipart!(req, form, Xml)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as xml, then use quick_xml to deserialize it
+/// - `send_multipart!(req, form, Text)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as text, then use FromStr to deserialize it
+/// - `send_multipart!(req, form, Html)` -> `impl Future<Output = ApiResult<T>>`
+///     - send the request, parse response as html, then use FromStr to deserialize it
+/// - `send_multipart!(req, form, OtherType)` -> `impl Future<Output = ApiResult<T>>`
+///     - send form, parse response as json, and use `OtherType` as JsonExtractor
+/// - `send_multipart!(req, form, Json<OtherType>)` -> `impl Future<Output = ApiResult<T>>`
+///     - send form, parse response as json, and use `OtherType` as JsonExtractor
+///
+/// # Examples
+///
+/// ### Use MultipartForm to build form
+///
+/// ```
+/// use apisdk::MultipartForm;
+///
+/// let mut form = MultipartForm::new();
+/// form.text("key", "value");
+/// form.pair("part", Part::text("part-value"));
+/// let req = client.post("/path/api").await?;
+/// let res: TypeOfResponse = send_multipart!(req, form).await?;
+/// ```
+///
+/// Please reference `send` for more information
 #[macro_export]
-macro_rules! send_raw {
-    ($req:expr) => {
-        $crate::__internal::send_raw(
-            $req,
-            $crate::__internal::RequestConfigurator::new(
-                $crate::_function_path!(),
-                None::<bool>,
-                false,
-            ),
-        )
+macro_rules! send_multipart {
+    ($req:expr, $form:expr) => {
+        $crate::send_multipart!($req, $form, $crate::Auto, ())
+    };
+    ($req:expr, $form:expr, ()) => {
+        async {
+            let _ = $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            Ok(())
+        }
+    };
+    ($req:expr, $form:expr, Body) => {
+        async {
+            $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    true,
+                ),
+            )
+            .await
+            .and_then(|c| c.try_into())
+        }
+    };
+    ($req:expr, $form:expr, Json) => {
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_multipart(
+                $crate::Json::retain_raw_for($req, &parse),
+                $form,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $form:expr, RawJson) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::send_multipart!(
+                $req.with_extension($crate::RetainRawBytes),
+                $form,
+                $crate::Json,
+                ()
+            )
+            .await?;
+            Ok(raw)
+        }
+    };
+    ($req:expr, $form:expr, Xml) => {
+        $crate::send_multipart!($req, $form, $crate::Xml, ())
+    };
+    ($req:expr, $form:expr, Text) => {
+        $crate::send_multipart!($req, $form, $crate::Text, ())
+    };
+    ($req:expr, $form:expr, Html) => {
+        $crate::send_multipart!($req, $form, $crate::Html, ())
+    };
+    ($req:expr, $form:expr, $parser:ty, ()) => {
+        async {
+            let result = $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    false,
+                ),
+            )
+            .await?;
+            <$parser>::try_parse(result)
+        }
+    };
+    ($req:expr, $form:expr, Json<$ve:ty>) => {
+        $crate::send_multipart!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
+    };
+    ($req:expr, $form:expr, $ve:ty) => {
+        $crate::send_multipart!($req, $form, $crate::Json, $crate::JsonExtractor, $ve)
+    };
+    ($req:expr, $form:expr, $parser:ty, $vet:ty, $ve:ty) => {
+        async {
+            use $vet;
+            let result = $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $crate::__internal::RequestConfigurator::new(
+                    $crate::_function_path!(),
+                    None::<bool>,
+                    <$ve>::require_headers(),
+                ),
+            )
+            .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
+            let result = <$parser>::try_parse::<$ve>(result)?;
+            <$ve>::try_extract(result)
+        }
+    };
+}
+
+/// Internal macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _send_multipart_with {
+    ($req:expr, $form:expr, $config:expr) => {
+        $crate::_send_multipart_with!($req, $form, $crate::Auto, (), $config)
+    };
+    ($req:expr, $form:expr, (), $config:expr) => {
+        async {
+            let _ = $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            Ok(())
+        }
+    };
+    ($req:expr, $form:expr, Body, $config:expr) => {
+        async {
+            $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $config.merge($crate::_function_path!(), true),
+            )
+            .await
+            .and_then(|c| c.try_into())
+        }
+    };
+    ($req:expr, $form:expr, Json, $config:expr) => {
+        async {
+            let parse = $crate::Json::try_parse;
+            let result = $crate::__internal::send_multipart(
+                $crate::Json::retain_raw_for($req, &parse),
+                $form,
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            parse(result)
+        }
+    };
+    ($req:expr, $form:expr, RawJson, $config:expr) => {
+        async {
+            let raw: Box<$crate::serde_json::value::RawValue> = $crate::_send_multipart_with!(
+                $req.with_extension($crate::RetainRawBytes),
+                $form,
+                $crate::Json,
+                (),
+                $config
+            )
+            .await?;
+            Ok(raw)
+        }
+    };
+    ($req:expr, $form:expr, Xml, $config:expr) => {
+        $crate::_send_multipart_with!($req, $form, $crate::Xml, (), $config)
+    };
+    ($req:expr, $form:expr, Text, $config:expr) => {
+        $crate::_send_multipart_with!($req, $form, $crate::Text, (), $config)
+    };
+    ($req:expr, $form:expr, Html, $config:expr) => {
+        $crate::_send_multipart_with!($req, $form, $crate::Html, (), $config)
+    };
+    ($req:expr, $form:expr, $parser:ty, (), $config:expr) => {
+        async {
+            let result = $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $config.merge($crate::_function_path!(), false),
+            )
+            .await?;
+            <$parser>::try_parse(result)
+        }
+    };
+    ($req:expr, $form:expr, Json<$ve:ty>, $config:expr) => {
+        $crate::_send_multipart_with!(
+            $req,
+            $form,
+            $crate::Json,
+            $crate::JsonExtractor,
+            $ve,
+            $config
+        )
+    };
+    ($req:expr, $form:expr, $ve:ty, $config:expr) => {
+        $crate::_send_multipart_with!(
+            $req,
+            $form,
+            $crate::Json,
+            $crate::JsonExtractor,
+            $ve,
+            $config
+        )
+    };
+    ($req:expr, $form:expr, $parser:ty, $vet:ty, $ve:ty, $config:expr) => {
+        async {
+            use $vet;
+            let result = $crate::__internal::send_multipart(
+                $req,
+                $form,
+                $config.merge($crate::_function_path!(), <$ve>::require_headers()),
+            )
+            .await?;
+            if result.is_empty() {
+                return result.parse_json();
+            }
+            let result = <$parser>::try_parse::<$ve>(result)?;
+            <$ve>::try_extract(result)
+        }
+    };
+}
+
+/// Send GraphQL query as JSON, and extract `data` field from response
+///
+/// # Forms
+///
+/// - `send_graphql!(req, query)` -> `impl Future<Output = ApiResult<T>>`
+///     - send query, and deserialize `data` field of response
+/// - `send_graphql!(req, query, variables)` -> `impl Future<Output = ApiResult<T>>`
+///     - send query with variables, and deserialize `data` field of response
+///
+/// The `query` could be `&str`, `String` or `apisdk::GraphQLQuery`.
+/// A non-empty `errors` field will be returned as `ApiError::GraphQL`.
+///
+/// # Examples
+///
+/// ```
+/// #[derive(serde::Deserialize)]
+/// struct Data {
+///     user: User,
+/// }
+///
+/// let req = client.post("/graphql").await?;
+/// let query = "query ($id: ID!) { user(id: $id) { name } }";
+/// let res: Data = send_graphql!(req, query, json!({ "id": 1 })).await?;
+/// ```
+///
+/// Please reference `send_json` for more information
+#[macro_export]
+macro_rules! send_graphql {
+    ($req:expr, $query:expr) => {
+        async {
+            let query = $crate::GraphQLQuery::from($query);
+            $crate::send_json!($req, query, $crate::GraphQLResponse).await
+        }
+    };
+    ($req:expr, $query:expr, $variables:expr) => {
+        async {
+            let query = $crate::GraphQLQuery::from($query).with_variables(&($variables))?;
+            $crate::send_json!($req, query, $crate::GraphQLResponse).await
+        }
+    };
+}
+
+/// Internal macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _send_graphql_with {
+    ($req:expr, $query:expr, $config:expr) => {
+        async {
+            let query = $crate::GraphQLQuery::from($query);
+            $crate::_send_json_with!($req, query, $crate::GraphQLResponse, $config).await
+        }
+    };
+    ($req:expr, $query:expr, $variables:expr, $config:expr) => {
+        async {
+            let query = $crate::GraphQLQuery::from($query).with_variables(&($variables))?;
+            $crate::_send_json_with!($req, query, $crate::GraphQLResponse, $config).await
+        }
+    };
+}
+
+/// Send and get raw response
+///
+/// # Forms
+///
+/// - `send_raw!(req)`
+///     - send request, and return raw response
+#[macro_export]
+macro_rules! send_raw {
+    ($req:expr) => {
+        $crate::__internal::send_raw(
+            $req,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Send and read response body as bytes
+///
+/// # Forms
+///
+/// - `send_bytes!(req)` -> `impl Future<Output = ApiResult<Vec<u8>>>`
+///     - send request, verify response status, and return response body as bytes
+///
+/// # Examples
+///
+/// ```
+/// let req = client.get("/path/image.png").await?;
+/// let bytes = send_bytes!(req).await?;
+/// ```
+#[macro_export]
+macro_rules! send_bytes {
+    ($req:expr) => {
+        $crate::__internal::send_bytes(
+            $req,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Internal macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _send_bytes_with {
+    ($req:expr, $config:expr) => {
+        $crate::__internal::send_bytes($req, $config.merge($crate::_function_path!(), false))
+    };
+}
+
+/// Send with `Range` header, and read partial content
+///
+/// # Forms
+///
+/// - `send_range!(req, start, end)` -> `impl Future<Output = ApiResult<apisdk::PartialContent>>`
+///     - send request with `Range: bytes=start-end`, verify `206 Partial Content` and `Content-Range`, and return partial body
+/// - `send_range!(req, start)` -> `impl Future<Output = ApiResult<apisdk::PartialContent>>`
+///     - send request with `Range: bytes=start-`, which reads until the end
+///
+/// # Examples
+///
+/// ```
+/// let req = client.get("/path/archive.zip").await?;
+/// let part = send_range!(req, 0, 1023).await?;
+/// let total = part.range.total;
+/// ```
+#[macro_export]
+macro_rules! send_range {
+    ($req:expr, $start:expr) => {
+        $crate::__internal::send_range(
+            $req,
+            $start,
+            None,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+    ($req:expr, $start:expr, $end:expr) => {
+        $crate::__internal::send_range(
+            $req,
+            $start,
+            Some($end),
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Internal macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _send_range_with {
+    ($req:expr, $start:expr, $config:expr) => {
+        $crate::__internal::send_range(
+            $req,
+            $start,
+            None,
+            $config.merge($crate::_function_path!(), false),
+        )
+    };
+    ($req:expr, $start:expr, $end:expr, $config:expr) => {
+        $crate::__internal::send_range(
+            $req,
+            $start,
+            Some($end),
+            $config.merge($crate::_function_path!(), false),
+        )
+    };
+}
+
+/// Send and read response body with trailers (not available on wasm32)
+///
+/// # Forms
+///
+/// - `send_with_trailers!(req)` -> `impl Future<Output = ApiResult<apisdk::BodyWithTrailers>>`
+///     - send request, verify response status, and return response body with trailers
+///
+/// # Examples
+///
+/// ```
+/// let req = client.post("/grpc.Service/Method").await?;
+/// let res = send_with_trailers!(req).await?;
+/// let status = res.trailer("grpc-status");
+/// ```
+#[macro_export]
+macro_rules! send_with_trailers {
+    ($req:expr) => {
+        $crate::__internal::send_with_trailers(
+            $req,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Internal macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _send_with_trailers_with {
+    ($req:expr, $config:expr) => {
+        $crate::__internal::send_with_trailers(
+            $req,
+            $config.merge($crate::_function_path!(), false),
+        )
+    };
+}
+
+/// Send and stream response body into file (not available on wasm32)
+///
+/// # Forms
+///
+/// - `download_to_file!(req, path)` -> `impl Future<Output = ApiResult<u64>>`
+///     - send request, verify response status, write response body into `path`, and return the number of bytes written
+///
+/// # Examples
+///
+/// ```
+/// let req = client.get("/path/archive.zip").await?;
+/// let written = download_to_file!(req, "/tmp/archive.zip").await?;
+/// ```
+#[macro_export]
+macro_rules! download_to_file {
+    ($req:expr, $path:expr) => {
+        $crate::__internal::download_to_file(
+            $req,
+            $path,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Internal macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _download_to_file_with {
+    ($req:expr, $path:expr, $config:expr) => {
+        $crate::__internal::download_to_file(
+            $req,
+            $path,
+            $config.merge($crate::_function_path!(), false),
+        )
+    };
+}
+
+/// Send and resume the download into a partial file (not available on wasm32)
+///
+/// # Forms
+///
+/// - `resumable_download!(req, path)` -> `impl Future<Output = ApiResult<ResumedDownload>>`
+///     - send `Range: bytes=N-` for the partial file of `N` bytes, and append response body into `path`
+/// - `resumable_download!(req, path, etag)` -> `impl Future<Output = ApiResult<ResumedDownload>>`
+///     - `etag` is `Option<impl AsRef<str>>`, which is sent as `If-Range`, so the whole body will be downloaded again if the resource has changed
+///
+/// # Examples
+///
+/// ```
+/// let req = client.get("/path/archive.zip").await?;
+/// let res = resumable_download!(req, "/tmp/archive.zip", etag).await?;
+/// // keep res.etag to resume later
+/// ```
+#[macro_export]
+macro_rules! resumable_download {
+    ($req:expr, $path:expr) => {
+        $crate::resumable_download!($req, $path, None::<&str>)
+    };
+    ($req:expr, $path:expr, $etag:expr) => {
+        $crate::__internal::resumable_download(
+            $req,
+            $path,
+            $etag,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Internal macro
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _resumable_download_with {
+    ($req:expr, $path:expr, $config:expr) => {
+        $crate::_resumable_download_with!($req, $path, None::<&str>, $config)
+    };
+    ($req:expr, $path:expr, $etag:expr, $config:expr) => {
+        $crate::__internal::resumable_download(
+            $req,
+            $path,
+            $etag,
+            $config.merge($crate::_function_path!(), false),
+        )
+    };
+}
+
+/// Send and decode response as JSON text sequences (`application/json-seq`, RFC 7464)
+///
+/// # Forms
+///
+/// - `send_json_seq!(req)` -> `impl Future<Output = ApiResult<impl Stream<Item = ApiResult<T>>>>`
+///     - send request, and decode each record as `T`, which is inferred from usage
+/// - `send_json_seq!(req, T)` -> `impl Future<Output = ApiResult<impl Stream<Item = ApiResult<T>>>>`
+///     - send request, and decode each record as `T`
+///
+/// A malformed record will be returned as `ApiError::DecodeJson`, and the following records could still be read.
+///
+/// # Examples
+///
+/// ```
+/// use futures::TryStreamExt;
+///
+/// let req = client.get("/path/api").await?;
+/// let mut records = Box::pin(send_json_seq!(req, Record).await?);
+/// while let Some(record) = records.try_next().await? {
+///     println!("{:?}", record);
+/// }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+#[macro_export]
+macro_rules! send_json_seq {
+    ($req:expr) => {
+        $crate::__internal::send_json_seq(
+            $req,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+    ($req:expr, $t:ty) => {
+        $crate::__internal::send_json_seq::<$t>(
+            $req,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Internal macro
+#[cfg(not(target_arch = "wasm32"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _send_json_seq_with {
+    ($req:expr, $config:expr) => {
+        $crate::__internal::send_json_seq($req, $config.merge($crate::_function_path!(), false))
+    };
+    ($req:expr, $t:ty, $config:expr) => {
+        $crate::__internal::send_json_seq::<$t>(
+            $req,
+            $config.merge($crate::_function_path!(), false),
+        )
+    };
+}
+
+/// Send and decode response as Server-Sent Events (requires `sse` feature)
+///
+/// # Forms
+///
+/// - `send_sse!(req)` -> `impl Future<Output = ApiResult<impl Stream<Item = ApiResult<apisdk::SseEvent>>>>`
+///     - send request, and decode `text/event-stream` response as stream of events
+///
+/// # Examples
+///
+/// ```
+/// use futures::TryStreamExt;
+///
+/// let req = client.post("/path/api").await?;
+/// let mut events = Box::pin(send_sse!(req).await?);
+/// while let Some(event) = events.try_next().await? {
+///     println!("{}", event.data);
+/// }
+/// ```
+#[cfg(feature = "sse")]
+#[macro_export]
+macro_rules! send_sse {
+    ($req:expr) => {
+        $crate::__internal::send_sse(
+            $req,
+            $crate::__internal::RequestConfigurator::new(
+                $crate::_function_path!(),
+                None::<bool>,
+                false,
+            ),
+        )
+    };
+}
+
+/// Internal macro
+#[cfg(feature = "sse")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! _send_sse_with {
+    ($req:expr, $config:expr) => {
+        $crate::__internal::send_sse($req, $config.merge($crate::_function_path!(), false))
     };
 }
 
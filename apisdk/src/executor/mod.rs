@@ -1,18 +1,51 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod batch;
 mod execute;
 mod form;
 mod macros;
+mod paginate;
+mod query;
+mod range;
+mod response;
+mod sender;
+#[cfg(not(target_arch = "wasm32"))]
+mod trailers;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use batch::*;
 pub use form::*;
+pub use paginate::*;
+pub use query::*;
+pub use range::*;
+pub use response::*;
+pub use sender::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use trailers::*;
 // pub use macros::*;
 
 /// Internal struct & functions
 #[doc(hidden)]
 pub mod __internal {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::execute::download_to_file;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::execute::resumable_download;
     pub use super::execute::send;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::execute::send_body_stream;
+    pub use super::execute::send_bytes;
     pub use super::execute::send_form;
+    pub use super::execute::send_full;
     pub use super::execute::send_json;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::execute::send_json_seq;
     pub use super::execute::send_multipart;
+    pub use super::execute::send_range;
     pub use super::execute::send_raw;
+    #[cfg(feature = "sse")]
+    pub use super::execute::send_sse;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use super::execute::send_with_trailers;
     pub use super::execute::send_xml;
     pub use super::execute::RequestConfigurator;
 }
@@ -0,0 +1,297 @@
+use std::{collections::HashSet, sync::Arc};
+
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{ApiError, ApiResult, IntoUrl, Json, RequestBuilder};
+
+use super::execute::{send, RequestConfigurator};
+
+/// The log target of paginators
+const LOG_TARGET: &str = "apisdk::paginate";
+
+/// The function to extract the next cursor from payload
+type CursorGetter = Box<dyn Fn(&Value) -> Option<String> + Send + Sync>;
+
+/// The function to apply the cursor to request
+type CursorSetter = Box<dyn Fn(RequestBuilder, &str) -> ApiResult<RequestBuilder> + Send + Sync>;
+
+/// How to apply the cursor to request
+enum CursorTarget {
+    /// Update the request by function
+    Setter(CursorSetter),
+    /// Send the request to the cursor, which is an absolute URL
+    NextUrl,
+}
+
+/// This struct is used to fetch all items from cursor-based APIs.
+///
+/// The paginator will send the request again and again, until the next cursor is absent,
+/// or it has been seen before, or `max_pages` is reached.
+///
+/// # Examples
+///
+/// ### Cursor in query param
+///
+/// ```
+/// let req = api.get("/items").await?;
+/// let items: Vec<Item> = CursorPaginator::new(|page| {
+///     page.pointer("/paging/cursors/after")
+///         .and_then(|v| v.as_str())
+///         .map(|v| v.to_string())
+/// })
+/// .with_query_param("after")
+/// .collect(req)
+/// .await?;
+/// ```
+///
+/// ### Follow `paging.next` URL
+///
+/// ```
+/// let req = api.get("/me/media").await?;
+/// let items: Vec<Media> = CursorPaginator::paging_next().collect(req).await?;
+/// ```
+pub struct CursorPaginator {
+    /// Extract the next cursor from payload
+    next_cursor: CursorGetter,
+    /// Apply the cursor to request
+    set_cursor: CursorTarget,
+    /// The JSON pointer to locate items in payload
+    items_pointer: String,
+    /// The max number of pages to fetch
+    max_pages: Option<usize>,
+    /// Whether to follow the URL of next page on another origin
+    cross_origin: bool,
+}
+
+impl std::fmt::Debug for CursorPaginator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorPaginator")
+            .field("items_pointer", &self.items_pointer)
+            .field("max_pages", &self.max_pages)
+            .field("cross_origin", &self.cross_origin)
+            .finish()
+    }
+}
+
+impl CursorPaginator {
+    /// Create a new instance
+    /// - next_cursor: extract the next cursor from payload, and return `None` when exhausted
+    ///
+    /// The cursor will be carried by `cursor` query param, and the items will be read from `data` field.
+    pub fn new<F>(next_cursor: F) -> Self
+    where
+        F: Fn(&Value) -> Option<String> + Send + Sync + 'static,
+    {
+        Self {
+            next_cursor: Box::new(next_cursor),
+            set_cursor: CursorTarget::Setter(Box::new(|req, cursor| {
+                Ok(req.query(&[("cursor", cursor)]))
+            })),
+            items_pointer: "/data".to_string(),
+            max_pages: None,
+            cross_origin: false,
+        }
+    }
+
+    /// Create a new instance, which follows the absolute URL of next page
+    /// - pointer: JSON pointer to locate the URL of next page, e.g. `/paging/next`
+    ///
+    /// The request will be sent to the URL of next page as is, and the items will be read from `data` field.
+    /// The URL must have the same scheme, host and port as the request, unless `with_cross_origin(true)` is set.
+    pub fn follow_next_url(pointer: impl ToString) -> Self {
+        let pointer = pointer.to_string();
+        Self {
+            next_cursor: Box::new(move |page| {
+                page.pointer(&pointer)
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+            }),
+            set_cursor: CursorTarget::NextUrl,
+            items_pointer: "/data".to_string(),
+            max_pages: None,
+            cross_origin: false,
+        }
+    }
+
+    /// Create a new instance for Graph-style APIs
+    ///
+    /// The payload should be `{ data: [...], paging: { next: "<url>" } }`,
+    /// and the `paging.next` URL will be followed until exhausted.
+    pub fn paging_next() -> Self {
+        Self::follow_next_url("/paging/next")
+    }
+
+    /// Carry the cursor by query param
+    /// - name: the name of query param
+    pub fn with_query_param(self, name: impl ToString) -> Self {
+        let name = name.to_string();
+        Self {
+            set_cursor: CursorTarget::Setter(Box::new(move |req, cursor| {
+                Ok(req.query(&[(name.as_str(), cursor)]))
+            })),
+            ..self
+        }
+    }
+
+    /// Apply the cursor to request by custom function
+    /// - set_cursor: update the request with cursor
+    pub fn with_cursor_setter<F>(self, set_cursor: F) -> Self
+    where
+        F: Fn(RequestBuilder, &str) -> RequestBuilder + Send + Sync + 'static,
+    {
+        Self {
+            set_cursor: CursorTarget::Setter(Box::new(move |req, cursor| {
+                Ok(set_cursor(req, cursor))
+            })),
+            ..self
+        }
+    }
+
+    /// Locate items in payload by JSON pointer
+    /// - pointer: JSON pointer, e.g. `/data`, or `""` for whole payload
+    pub fn with_items_pointer(self, pointer: impl ToString) -> Self {
+        Self {
+            items_pointer: pointer.to_string(),
+            ..self
+        }
+    }
+
+    /// Limit the number of pages to fetch
+    /// - max_pages: the max number of pages, and the paginator stops silently when it's reached
+    pub fn with_max_pages(self, max_pages: usize) -> Self {
+        Self {
+            max_pages: Some(max_pages),
+            ..self
+        }
+    }
+
+    /// Allow to follow the URL of next page on another origin
+    /// - cross_origin: `true` to follow URL with different scheme, host or port
+    ///
+    /// It's disabled by default, because the request carries credentials, e.g. `Authorization` header.
+    pub fn with_cross_origin(self, cross_origin: bool) -> Self {
+        Self {
+            cross_origin,
+            ..self
+        }
+    }
+
+    /// Fetch one page
+    async fn fetch_page<T>(
+        &self,
+        req: &RequestBuilder,
+        cursor: Option<&str>,
+    ) -> ApiResult<(Vec<T>, Option<String>)>
+    where
+        T: DeserializeOwned,
+    {
+        let req = req.try_clone().ok_or(ApiError::UncloneableRequest)?;
+        let req = match cursor {
+            Some(cursor) => match &self.set_cursor {
+                CursorTarget::Setter(set_cursor) => set_cursor(req, cursor)?,
+                CursorTarget::NextUrl => retarget(req, cursor, self.cross_origin)?,
+            },
+            None => req,
+        };
+        let body = send(
+            req,
+            RequestConfigurator::new(LOG_TARGET, None::<bool>, false),
+        )
+        .await?;
+        let page: Value = Json::try_parse(body)?;
+
+        let next = (self.next_cursor)(&page).filter(|next| !next.is_empty());
+        let items = match page.pointer(&self.items_pointer) {
+            Some(items) => serde_json::from_value(items.clone())?,
+            None => vec![],
+        };
+        Ok((items, next))
+    }
+
+    /// Fetch all items as a stream
+    /// - req: the request of first page, it will be cloned for each page
+    pub fn stream<T>(self, req: RequestBuilder) -> impl Stream<Item = ApiResult<T>> + Send
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let this = Arc::new(self);
+        let state = PageState {
+            req,
+            cursor: None,
+            seen: HashSet::new(),
+            pages: 0,
+        };
+        stream::try_unfold(Some(state), move |state| {
+            let this = this.clone();
+            async move {
+                let mut state = match state {
+                    Some(state) => state,
+                    None => return ApiResult::Ok(None),
+                };
+                let (items, next) = this
+                    .fetch_page::<T>(&state.req, state.cursor.as_deref())
+                    .await?;
+                state.pages += 1;
+                let exhausted = this.max_pages.is_some_and(|max| state.pages >= max);
+                let state = match next {
+                    // Stop on repeated cursor, e.g. A -> B -> A, or it will loop forever
+                    Some(next) if !exhausted && state.seen.insert(next.clone()) => {
+                        Some(PageState {
+                            cursor: Some(next),
+                            ..state
+                        })
+                    }
+                    _ => None,
+                };
+                Ok(Some((stream::iter(items.into_iter().map(Ok)), state)))
+            }
+        })
+        .try_flatten()
+        .boxed()
+    }
+
+    /// Fetch all items
+    /// - req: the request of first page, it will be cloned for each page
+    pub async fn collect<T>(self, req: RequestBuilder) -> ApiResult<Vec<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.stream(req).try_collect().await
+    }
+}
+
+/// The state of pagination
+struct PageState {
+    /// The request of first page
+    req: RequestBuilder,
+    /// The cursor of current page
+    cursor: Option<String>,
+    /// All cursors have been followed
+    seen: HashSet<String>,
+    /// The number of fetched pages
+    pages: usize,
+}
+
+/// Replace the url of request, and keep all extensions
+/// - req: the request to update
+/// - url: the new absolute url
+/// - cross_origin: whether the new url could be on another origin
+fn retarget(
+    mut req: RequestBuilder,
+    url: impl IntoUrl,
+    cross_origin: bool,
+) -> ApiResult<RequestBuilder> {
+    let url = url.into_url().map_err(ApiError::InvalidUrl)?;
+    let extensions = std::mem::take(req.extensions());
+    let (client, request) = req.build_split();
+    let mut request = request.map_err(ApiError::BuildRequest)?;
+    if !cross_origin && request.url().origin() != url.origin() {
+        return Err(ApiError::CrossOrigin(url.to_string()));
+    }
+    *request.url_mut() = url;
+    let mut req = RequestBuilder::from_parts(client, request);
+    *req.extensions() = extensions;
+    Ok(req)
+}
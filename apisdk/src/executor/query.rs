@@ -0,0 +1,88 @@
+use serde::Serialize;
+
+use crate::{ApiResult, RequestBuilder};
+
+/// Provides functions to append query params
+pub trait QueryOps: Sized {
+    /// Serialize the struct as query params, and append them to the url
+    /// - query: any `Serialize` struct, map or sequence of pairs
+    ///
+    /// The `None` fields are skipped, and the serde attributes (e.g. `rename`) are respected.
+    /// Unlike `query`, the error is returned immediately, rather than when the request is sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[derive(Serialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct PostFilter {
+    ///     user_id: Option<u64>,
+    ///     title: Option<String>,
+    /// }
+    ///
+    /// let req = api.get("/posts").await?;
+    /// let req = req.with_query(&PostFilter { user_id: Some(1), title: None })?;
+    /// ```
+    fn with_query<T>(self, query: &T) -> ApiResult<Self>
+    where
+        T: Serialize + ?Sized;
+}
+
+impl QueryOps for RequestBuilder {
+    fn with_query<T>(self, query: &T) -> ApiResult<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        let pairs = to_query_pairs(query)?;
+        if pairs.is_empty() {
+            return Ok(self);
+        }
+        Ok(self.query(&pairs))
+    }
+}
+
+/// Serialize the struct as query pairs
+/// - query: any `Serialize` struct, map or sequence of pairs
+fn to_query_pairs<T>(query: &T) -> ApiResult<Vec<(String, String)>>
+where
+    T: Serialize + ?Sized,
+{
+    let encoded = serde_urlencoded::to_string(query)?;
+    Ok(url::form_urlencoded::parse(encoded.as_bytes())
+        .into_owned()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::to_query_pairs;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Filter {
+        user_id: Option<u64>,
+        title: Option<&'static str>,
+        tags: &'static str,
+    }
+
+    #[test]
+    fn test_to_query_pairs() {
+        let filter = Filter {
+            user_id: Some(1),
+            title: None,
+            tags: "a b&c",
+        };
+        assert_eq!(
+            vec![
+                ("userId".to_string(), "1".to_string()),
+                ("tags".to_string(), "a b&c".to_string())
+            ],
+            to_query_pairs(&filter).unwrap()
+        );
+
+        // Nested values are not supported
+        assert!(to_query_pairs(&[("a", vec![1])]).is_err());
+    }
+}
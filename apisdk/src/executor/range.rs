@@ -0,0 +1,177 @@
+use reqwest::header::{HeaderMap, CONTENT_RANGE};
+
+use crate::{ApiError, ApiResult};
+
+/// This struct represents the value of `Content-Range` header, e.g. `bytes 0-99/1000`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    /// The first byte, inclusive
+    pub start: u64,
+    /// The last byte, inclusive
+    pub end: u64,
+    /// The total length of resource, or `None` if it's unknown (`*`)
+    pub total: Option<u64>,
+}
+
+impl ContentRange {
+    /// Parse `Content-Range` header
+    /// - headers: HTTP headers
+    ///
+    /// Return `None` if the header is absent or invalid.
+    pub fn parse(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(Self::parse_value)
+    }
+
+    /// Parse the value of `Content-Range` header
+    /// - value: e.g. `bytes 0-99/1000` or `bytes 0-99/*`
+    ///
+    /// Return `None` for unsatisfied range, e.g. `bytes */1000`.
+    pub fn parse_value(value: &str) -> Option<Self> {
+        let (unit, rest) = value.trim().split_once(' ')?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+        let (range, total) = rest.trim().split_once('/')?;
+        let (start, end) = range.split_once('-')?;
+        let start = start.trim().parse().ok()?;
+        let end = end.trim().parse().ok()?;
+        let total = match total.trim() {
+            "*" => None,
+            total => Some(total.parse().ok()?),
+        };
+        (start <= end).then_some(Self { start, end, total })
+    }
+
+    /// Parse the total length from `Content-Range` header of `416 Range Not Satisfiable`, e.g. `bytes */1000`
+    /// - headers: HTTP headers
+    pub(crate) fn parse_unsatisfied(headers: &HeaderMap) -> Option<u64> {
+        let value = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+        let (unit, rest) = value.trim().split_once(' ')?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+        rest.trim().strip_prefix("*/")?.trim().parse().ok()
+    }
+
+    /// Get the number of bytes in range
+    pub fn size(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Check whether the range is the last part of resource
+    ///
+    /// Return `false` if the total length is unknown.
+    pub fn is_last(&self) -> bool {
+        self.total.is_some_and(|total| self.end + 1 >= total)
+    }
+}
+
+/// This struct holds the body of `206 Partial Content` response
+///
+/// It's returned by `send_range!`, and the `Content-Range` has been validated against the request.
+#[derive(Debug, Clone)]
+pub struct PartialContent {
+    /// The range of body
+    pub range: ContentRange,
+    /// The partial body
+    pub body: Vec<u8>,
+}
+
+impl PartialContent {
+    /// Build the value of `Range` header
+    /// - start: the first byte, inclusive
+    /// - end: the last byte, inclusive, or `None` to read until the end
+    pub(crate) fn range_header(start: u64, end: Option<u64>) -> String {
+        match end {
+            Some(end) => format!("bytes={}-{}", start, end),
+            None => format!("bytes={}-", start),
+        }
+    }
+
+    /// Validate `Content-Range` against the requested range and the length of body
+    /// - range: the parsed `Content-Range` header
+    /// - start: the requested first byte
+    /// - end: the requested last byte
+    /// - body: the partial body
+    pub(crate) fn validate(
+        range: Option<ContentRange>,
+        start: u64,
+        end: Option<u64>,
+        body: Vec<u8>,
+    ) -> ApiResult<Self> {
+        let range = range.ok_or_else(|| {
+            ApiError::InvalidRange("Missing or invalid Content-Range".to_string())
+        })?;
+        if range.start != start || end.is_some_and(|end| range.end > end) {
+            return Err(ApiError::InvalidRange(format!(
+                "Expect {}, actual bytes {}-{}",
+                Self::range_header(start, end),
+                range.start,
+                range.end
+            )));
+        }
+        if body.len() as u64 != range.size() {
+            return Err(ApiError::InvalidRange(format!(
+                "Expect {} bytes, actual {} bytes",
+                range.size(),
+                body.len()
+            )));
+        }
+        Ok(Self { range, body })
+    }
+}
+
+/// This struct is the result of `resumable_download!`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResumedDownload {
+    /// The number of bytes written by this call
+    pub written: u64,
+    /// The length of file
+    pub length: u64,
+    /// Whether the existing part of file is kept
+    pub resumed: bool,
+    /// The `ETag` of resource, which should be kept to resume later
+    pub etag: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContentRange, PartialContent};
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            Some(ContentRange {
+                start: 0,
+                end: 99,
+                total: Some(1000)
+            }),
+            ContentRange::parse_value("bytes 0-99/1000")
+        );
+        let range = ContentRange::parse_value("bytes 900-999/*").unwrap();
+        assert_eq!(None, range.total);
+        assert_eq!(100, range.size());
+        assert!(!range.is_last());
+        assert!(ContentRange::parse_value("bytes 900-999/1000")
+            .unwrap()
+            .is_last());
+
+        assert_eq!(None, ContentRange::parse_value("bytes */1000"));
+        assert_eq!(None, ContentRange::parse_value("items 0-9/10"));
+        assert_eq!(None, ContentRange::parse_value("bytes 9-0/10"));
+    }
+
+    #[test]
+    fn test_validate_partial_content() {
+        let range = ContentRange::parse_value("bytes 2-4/10");
+        assert!(PartialContent::validate(range, 2, Some(4), vec![2, 3, 4]).is_ok());
+        assert!(PartialContent::validate(range, 2, None, vec![2, 3, 4]).is_ok());
+        assert!(PartialContent::validate(range, 0, Some(4), vec![2, 3, 4]).is_err());
+        assert!(PartialContent::validate(range, 2, Some(3), vec![2, 3, 4]).is_err());
+        assert!(PartialContent::validate(range, 2, Some(4), vec![2, 3]).is_err());
+        assert!(PartialContent::validate(None, 2, Some(4), vec![2, 3, 4]).is_err());
+    }
+}
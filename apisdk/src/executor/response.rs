@@ -0,0 +1,63 @@
+use bytes::Bytes;
+use reqwest::{header::HeaderMap, StatusCode, Version};
+
+use crate::{
+    content_disposition::{parse_content_disposition, Disposition},
+    ApiResult, ResponseBody,
+};
+
+/// This struct holds the status, headers and parsed body of response
+///
+/// It's returned by `send!(req, Full<T>)`, so the metadata (e.g. rate-limit headers) could be
+/// accessed along with the body, regardless of the shape of payload.
+///
+/// The response mocked by `MockServer` has only the body, so it's reported as `200 OK` with empty headers.
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T = ResponseBody> {
+    /// HTTP status code
+    pub status: StatusCode,
+    /// HTTP version, e.g. `HTTP/1.1` or `HTTP/2.0`
+    pub version: Version,
+    /// HTTP headers
+    pub headers: HeaderMap,
+    /// The parsed body
+    pub body: T,
+}
+
+impl<T> ApiResponse<T> {
+    /// Get the value of header
+    /// - name: the name of header
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|v| v.to_str().ok())
+    }
+
+    /// Parse `Content-Disposition` header, e.g. to get the filename of download
+    pub fn content_disposition(&self) -> Option<Disposition> {
+        parse_content_disposition(&self.headers)
+    }
+
+    /// Convert body to another type, and keep status and headers
+    /// - f: the function to convert body
+    pub fn try_map<U, F>(self, f: F) -> ApiResult<ApiResponse<U>>
+    where
+        F: FnOnce(T) -> ApiResult<U>,
+    {
+        Ok(ApiResponse {
+            status: self.status,
+            version: self.version,
+            headers: self.headers,
+            body: f(self.body)?,
+        })
+    }
+}
+
+impl ApiResponse<ResponseBody> {
+    /// Convert to `http::Response`, e.g. to proxy the response
+    ///
+    /// See `ResponseBody::into_http_response` for how the headers are adjusted.
+    pub fn into_http_response(self) -> http::Response<Bytes> {
+        let mut res = self.body.into_http_response(self.headers, self.status);
+        *res.version_mut() = self.version;
+        res
+    }
+}
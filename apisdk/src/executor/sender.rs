@@ -0,0 +1,75 @@
+use http::Extensions;
+use reqwest::{Request, Response};
+use reqwest_middleware::ClientWithMiddleware;
+
+use crate::{ApiError, ApiResult, RequestBuilder, ResponseBody};
+
+use super::execute::{send, send_raw, RequestConfigurator};
+
+/// This struct is used to send a built `Request` through the full pipeline later
+///
+/// It keeps the client (with middlewares) and the extensions of `RequestBuilder`,
+/// so the request could be inspected, modified, batched or scheduled before sending.
+///
+/// # Examples
+///
+/// ```
+/// let (req, sender) = client.build_request_split(Method::GET, "/path").await?;
+/// // ... schedule ...
+/// let body = sender.send(req).await?;
+/// ```
+#[derive(Debug)]
+pub struct RequestSender {
+    /// The client with middlewares
+    client: ClientWithMiddleware,
+    /// The extensions of request
+    extensions: Extensions,
+    /// The target of log
+    log_target: &'static str,
+}
+
+impl RequestSender {
+    /// Split `RequestBuilder` into `Request` and `RequestSender`
+    /// - req: the builder to split
+    pub fn split(req: RequestBuilder) -> ApiResult<(Request, Self)> {
+        let mut req = req;
+        let extensions = std::mem::take(req.extensions());
+        let (client, request) = req.build_split();
+        let request = request.map_err(ApiError::BuildRequest)?;
+        Ok((
+            request,
+            Self {
+                client,
+                extensions,
+                log_target: module_path!(),
+            },
+        ))
+    }
+
+    /// Set the target of log
+    /// - log_target: the target of log
+    pub fn with_log_target(self, log_target: &'static str) -> Self {
+        Self { log_target, ..self }
+    }
+
+    /// Send request, and get response body
+    /// - req: the request to send
+    pub async fn send(self, req: Request) -> ApiResult<ResponseBody> {
+        let config = RequestConfigurator::default().merge(self.log_target, false);
+        send(self.join(req), config).await
+    }
+
+    /// Send request, and get raw response
+    /// - req: the request to send
+    pub async fn send_raw(self, req: Request) -> ApiResult<Response> {
+        let config = RequestConfigurator::default().merge(self.log_target, false);
+        send_raw(self.join(req), config).await
+    }
+
+    /// Rebuild `RequestBuilder` with extensions
+    fn join(self, req: Request) -> RequestBuilder {
+        let mut builder = RequestBuilder::from_parts(self.client, req);
+        *builder.extensions() = self.extensions;
+        builder
+    }
+}
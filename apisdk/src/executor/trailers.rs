@@ -0,0 +1,47 @@
+use http_body_util::BodyExt;
+use reqwest::header::HeaderMap;
+
+use crate::{ApiError, ApiResult, MimeType, Response};
+
+/// This struct holds the whole body and the trailers of response
+///
+/// The trailers are only available when the HTTP stack exposes them:
+/// - HTTP/2 responses, e.g. gRPC
+/// - HTTP/1.1 responses with `Transfer-Encoding: chunked`
+///
+/// The trailers will be `None` if the server doesn't send them, the response is mocked, or
+/// the body is decompressed by `reqwest` (`gzip` / `brotli` / `deflate` / `zstd` features).
+#[derive(Debug, Clone, Default)]
+pub struct BodyWithTrailers {
+    /// The whole body
+    pub body: Vec<u8>,
+    /// The trailers
+    pub trailers: Option<HeaderMap>,
+}
+
+impl BodyWithTrailers {
+    /// Read the whole body and the trailers of response
+    /// - res: the raw response, e.g. returned by `send_raw!`
+    pub async fn read(res: Response) -> ApiResult<Self> {
+        let res: http::Response<reqwest::Body> = res.into();
+        let collected = res
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| ApiError::DecodeResponse(MimeType::Empty, e.to_string()))?;
+        let trailers = collected.trailers().cloned();
+        Ok(Self {
+            body: collected.to_bytes().to_vec(),
+            trailers,
+        })
+    }
+
+    /// Get the value of trailer
+    /// - name: the name of trailer
+    pub fn trailer(&self, name: &str) -> Option<&str> {
+        self.trailers
+            .as_ref()
+            .and_then(|t| t.get(name))
+            .and_then(|v| v.to_str().ok())
+    }
+}
@@ -245,8 +245,37 @@ impl WithCarrier for AccessTokenAuth {
     }
 }
 
+/// This trait is used to provide current time, so it could be replaced in tests
+pub trait Clock: 'static + std::fmt::Debug + Send + Sync {
+    /// Get UNIX_TIMESTAMP (in second)
+    fn now(&self) -> u64;
+}
+
+/// The clock which reads system time
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+/// The clock which always returns the same UNIX_TIMESTAMP (in second)
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
+
 /// Hash algorithm
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum HashAlgorithm {
     Md5,
     Sha1,
@@ -311,6 +340,7 @@ pub struct HashedTokenAuth {
     app_secret: String,
     algorithm: HashAlgorithm,
     carrier: Carrier,
+    clock: Arc<dyn Clock>,
 }
 
 impl HashedTokenAuth {
@@ -329,6 +359,7 @@ impl HashedTokenAuth {
             app_secret: app_secret.to_string(),
             algorithm,
             carrier: Carrier::default(),
+            clock: Arc::new(SystemClock),
         }
     }
 
@@ -347,6 +378,16 @@ impl HashedTokenAuth {
             app_secret: app_secret.to_string(),
             algorithm,
             carrier: Carrier::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Set the clock to get timestamp, which is `SystemClock` by default
+    /// - clock: Clock
+    pub fn with_clock(self, clock: impl Clock) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            ..self
         }
     }
 
@@ -375,11 +416,7 @@ impl ApiAuthenticator for HashedTokenAuth {
 #[async_trait]
 impl TokenGenerator for HashedTokenAuth {
     async fn generate_token(&self, _req: &Request) -> Result<String, reqwest_middleware::Error> {
-        let timestamp = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        Ok(self.generate_token_at(timestamp))
+        Ok(self.generate_token_at(self.clock.now()))
     }
 }
 
@@ -403,6 +440,181 @@ impl WithCarrier for HashedTokenAuth {
     }
 }
 
+/// This struct is used to sign request by self-signed JWT (HS256)
+///
+/// # Claims
+///
+/// - `iss` / `sub` / `aud`: set by `with_issuer` / `with_subject` / `with_audience`
+/// - `iat`: the time of generation
+/// - `exp`: `iat` + ttl, which is 5 minutes by default
+/// - custom claims: set by `with_claim`, but could not override `iat` / `exp`
+///
+/// The token is cached, and regenerated when less than 1/5 of ttl remains.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_authenticator(JwtAuth::new("secret").with_issuer("my-app").with_claim("role", "admin"))
+///     .build();
+/// ```
+#[cfg(feature = "jwt")]
+#[derive(Clone)]
+pub struct JwtAuth {
+    /// The key to sign token
+    key: jsonwebtoken::EncodingKey,
+    /// The claims, except `iat` and `exp`
+    claims: serde_json::Map<String, serde_json::Value>,
+    /// The ttl of token, in second
+    ttl: u64,
+    /// The clock to get timestamp
+    clock: Arc<dyn Clock>,
+    /// The carrier of token
+    carrier: Carrier,
+    /// The cached token, and its `exp`
+    cached: Arc<std::sync::RwLock<Option<(String, u64)>>>,
+}
+
+#[cfg(feature = "jwt")]
+impl std::fmt::Debug for JwtAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtAuth")
+            .field("claims", &self.claims)
+            .field("ttl", &self.ttl)
+            .field("clock", &self.clock)
+            .field("carrier", &self.carrier)
+            .finish()
+    }
+}
+
+#[cfg(feature = "jwt")]
+impl JwtAuth {
+    /// Create a new instance
+    /// - secret: the shared secret of HS256
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: jsonwebtoken::EncodingKey::from_secret(secret.as_ref()),
+            claims: serde_json::Map::new(),
+            ttl: 300,
+            clock: Arc::new(SystemClock),
+            carrier: Carrier::default(),
+            cached: Arc::new(std::sync::RwLock::new(None)),
+        }
+    }
+
+    /// Set `iss` claim
+    /// - issuer: the issuer
+    pub fn with_issuer(self, issuer: impl ToString) -> Self {
+        self.with_claim("iss", issuer.to_string())
+    }
+
+    /// Set `sub` claim
+    /// - subject: the subject
+    pub fn with_subject(self, subject: impl ToString) -> Self {
+        self.with_claim("sub", subject.to_string())
+    }
+
+    /// Set `aud` claim
+    /// - audience: the audience
+    pub fn with_audience(self, audience: impl ToString) -> Self {
+        self.with_claim("aud", audience.to_string())
+    }
+
+    /// Set custom claim
+    /// - name: the name of claim
+    /// - value: the value of claim
+    pub fn with_claim(self, name: impl ToString, value: impl Into<serde_json::Value>) -> Self {
+        let mut claims = self.claims;
+        claims.insert(name.to_string(), value.into());
+        Self {
+            claims,
+            cached: Arc::default(),
+            ..self
+        }
+    }
+
+    /// Set the ttl of token, which is used to compute `exp`
+    /// - ttl: the ttl of token
+    pub fn with_ttl(self, ttl: std::time::Duration) -> Self {
+        Self {
+            ttl: ttl.as_secs().max(1),
+            cached: Arc::default(),
+            ..self
+        }
+    }
+
+    /// Set the clock to get timestamp, which is `SystemClock` by default
+    /// - clock: Clock
+    pub fn with_clock(self, clock: impl Clock) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            cached: Arc::default(),
+            ..self
+        }
+    }
+
+    /// Generate token
+    /// - timestamp: UNIX_TIMESTAMP (in second), used as `iat`
+    pub fn generate_token_at(&self, timestamp: u64) -> Result<String, jsonwebtoken::errors::Error> {
+        let mut claims = self.claims.clone();
+        claims.insert("iat".to_string(), timestamp.into());
+        claims.insert("exp".to_string(), (timestamp + self.ttl).into());
+        jsonwebtoken::encode(&jsonwebtoken::Header::default(), &claims, &self.key)
+    }
+}
+
+#[cfg(feature = "jwt")]
+#[async_trait]
+impl ApiAuthenticator for JwtAuth {
+    fn get_carrier(&self) -> &Carrier {
+        &self.carrier
+    }
+}
+
+#[cfg(feature = "jwt")]
+#[async_trait]
+impl TokenGenerator for JwtAuth {
+    async fn generate_token(&self, _req: &Request) -> Result<String, reqwest_middleware::Error> {
+        let now = self.clock.now();
+        if let Ok(cached) = self.cached.read() {
+            if let Some((token, exp)) = cached.as_ref() {
+                if now + self.ttl / 5 < *exp {
+                    return Ok(token.clone());
+                }
+            }
+        }
+
+        let token = self
+            .generate_token_at(now)
+            .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?;
+        if let Ok(mut cached) = self.cached.write() {
+            *cached = Some((token.clone(), now + self.ttl));
+        }
+        Ok(token)
+    }
+}
+
+#[cfg(feature = "jwt")]
+impl WithCarrier for JwtAuth {
+    fn with_carrier(self, carrier: Carrier) -> Self {
+        Self { carrier, ..self }
+    }
+
+    fn with_header_name(self, name: impl ToString) -> Self {
+        Self {
+            carrier: Carrier::Header(name.to_string()),
+            ..self
+        }
+    }
+
+    fn with_query_param(self, name: impl ToString) -> Self {
+        Self {
+            carrier: Carrier::QueryParam(name.to_string()),
+            ..self
+        }
+    }
+}
+
 /// Token Error
 #[derive(Debug, Error)]
 pub enum TokenError {
@@ -431,6 +643,8 @@ pub struct ParsedHashedToken {
     pub timestamp: u64,
     /// sign
     pub sign: String,
+    /// The clock to check expiration
+    clock: Arc<dyn Clock>,
 }
 
 impl ParsedHashedToken {
@@ -456,6 +670,7 @@ impl ParsedHashedToken {
                     .parse()
                     .map_err(TokenError::Timestamp)?,
                 sign: iter.next().unwrap().to_string(),
+                clock: Arc::new(SystemClock),
             }),
             3 => Ok(Self {
                 client_id: None,
@@ -466,24 +681,33 @@ impl ParsedHashedToken {
                     .parse()
                     .map_err(TokenError::Timestamp)?,
                 sign: iter.next().unwrap().to_string(),
+                clock: Arc::new(SystemClock),
             }),
             _ => Err(TokenError::Format),
         }
     }
 
+    /// Set the clock to check expiration, which is `SystemClock` by default
+    /// - clock: Clock
+    pub fn with_clock(self, clock: impl Clock) -> Self {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+
     /// Check the token is expired or not
     /// - deviation: 1 min as default
     pub fn is_expired(&self, expires_in_secs: u64, deviation: Option<u64>) -> bool {
         let deviation = deviation.unwrap_or(60) as i64;
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = self.clock.now();
         let diff = now as i64 - self.timestamp as i64;
         diff < -deviation || diff > expires_in_secs as i64 + deviation
     }
 
     /// Check the token is signed or not
+    ///
+    /// The sign is compared in constant time (see `digest::constant_time_eq`), to avoid timing side channels.
     pub fn is_signed<S, A>(&self, app_secret: S, algorithm: A) -> bool
     where
         S: std::fmt::Display,
@@ -494,7 +718,7 @@ impl ParsedHashedToken {
         let algorithm: HashAlgorithm = algorithm.into();
         let sign = algorithm.apply(plain);
 
-        sign == self.sign
+        digest::constant_time_eq(sign, &self.sign)
     }
 }
 
@@ -0,0 +1,352 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use http::Extensions;
+use reqwest::{
+    header::{
+        HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, COOKIE, IF_NONE_MATCH,
+        PROXY_AUTHORIZATION, VARY,
+    },
+    Method, Request, Response, StatusCode,
+};
+use reqwest_middleware::{Middleware, Next, RequestBuilder, RequestInitialiser};
+
+use crate::{digest, ResponseBody};
+
+/// This struct is used to cache responses by `ETag`, and send conditional requests
+///
+/// The `GET` responses with `ETag` header are cached by url, along with `Accept` and the credentials
+/// (`Authorization`, `Proxy-Authorization` and `Cookie`), so the responses are never shared between users.
+/// The request headers listed in `Vary` of response must match as well, and `Vary: *` disables the cache.
+/// Other credentials (e.g. custom header of ApiAuthenticator) should be listed in `Vary` by the server.
+///
+/// The subsequent requests will carry `If-None-Match`, and the cached body will be returned on `304 Not Modified`,
+/// only if the `ETag` sent matches the cached one, e.g. it's not replaced by an explicit `If-None-Match`.
+/// Otherwise, the `304` response is returned as `ApiError::Redirect`.
+/// It only applies to requests whose payload is parsed, e.g. `send!(req, Json)`, rather than `send_bytes!` or `send!(req, Raw)`.
+/// The body is cached before `__headers__` is injected, so it's shared by all extractors, and the headers of
+/// `304` response replace the cached ones with the same name (e.g. `Date`) when it's injected.
+/// The least recently used entry will be evicted when the capacity is reached.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_response_cache(128)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    /// The maximum number of entries
+    capacity: usize,
+    /// The cached entries, which are shared by all clones
+    state: Arc<Mutex<CacheState>>,
+}
+
+/// The cached entries, and the order of access
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    order: VecDeque<String>,
+}
+
+/// The cached entry
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    etag: String,
+    headers: HeaderMap,
+    body: ResponseBody,
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+}
+
+/// The cached response, which is attached to `304` response by `ResponseCacheMiddleware`
+#[derive(Debug, Clone)]
+pub(crate) struct CachedResponse {
+    /// The `ETag` of cached response
+    pub etag: String,
+    /// The headers of cached response
+    pub headers: HeaderMap,
+    /// The parsed body, before `__headers__` is injected
+    pub body: ResponseBody,
+}
+
+/// This extension marks the request, whose payload will be parsed and could be served from cache
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ConditionalRequest;
+
+/// The key of cache, which is attached to response by `ResponseCacheMiddleware`
+#[derive(Debug, Clone)]
+pub(crate) struct CacheKey {
+    /// The key of entry
+    pub key: String,
+    /// The request headers listed in `Vary` of response
+    pub vary: Vec<(HeaderName, Option<HeaderValue>)>,
+}
+
+impl ResponseCache {
+    /// Create a new instance
+    /// - capacity: the maximum number of entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Default::default(),
+        }
+    }
+
+    /// Get the number of entries
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .map(|s| s.entries.len())
+            .unwrap_or_default()
+    }
+
+    /// Check whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all entries
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.entries.clear();
+            state.order.clear();
+        }
+    }
+
+    /// Get the cached response of key, if the request headers listed in `Vary` are matched, and mark it as recently used
+    pub(crate) fn get(&self, key: &str, headers: &HeaderMap) -> Option<CachedResponse> {
+        let mut state = self.state.lock().ok()?;
+        let cached = state
+            .entries
+            .get(key)
+            .filter(|e| {
+                e.vary
+                    .iter()
+                    .all(|(name, value)| headers.get(name) == value.as_ref())
+            })
+            .map(|e| CachedResponse {
+                etag: e.etag.clone(),
+                headers: e.headers.clone(),
+                body: e.body.clone(),
+            })?;
+        state.touch(key);
+        Some(cached)
+    }
+
+    /// Cache the headers and body of key
+    /// - body: the parsed body, before `__headers__` is injected
+    pub(crate) fn put(&self, key: CacheKey, etag: String, headers: HeaderMap, body: ResponseBody) {
+        if self.capacity == 0 {
+            return;
+        }
+        let CacheKey { key, vary } = key;
+        if let Ok(mut state) = self.state.lock() {
+            let entry = CacheEntry {
+                etag,
+                headers,
+                body,
+                vary,
+            };
+            if state.entries.insert(key.clone(), entry).is_some() {
+                state.touch(&key);
+                return;
+            }
+            state.order.push_back(key);
+            while state.order.len() > self.capacity {
+                if let Some(evicted) = state.order.pop_front() {
+                    state.entries.remove(&evicted);
+                }
+            }
+        }
+    }
+}
+
+impl CacheState {
+    /// Move the key to the end of order
+    fn touch(&mut self, key: &str) {
+        if let Some(index) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(index) {
+                self.order.push_back(key);
+            }
+        }
+    }
+}
+
+/// Apply ResponseCache to all requests
+impl RequestInitialiser for ResponseCache {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        req.with_extension(self.clone())
+    }
+}
+
+/// This middleware is used to apply `ResponseCache`
+///
+/// It should run after authenticating, so the credentials could be included in the key.
+#[derive(Default)]
+pub(crate) struct ResponseCacheMiddleware;
+
+#[async_trait]
+impl Middleware for ResponseCacheMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        let (cache, key) = match (extensions.get::<ResponseCache>(), build_key(&req)) {
+            (Some(cache), Some(key)) => (cache.clone(), key),
+            _ => return next.run(req, extensions).await,
+        };
+
+        // Take the cached response before sending, so it's still available on `304` even if evicted meanwhile
+        let mut req = req;
+        let cached = match extensions.get::<ConditionalRequest>() {
+            Some(_) => cache.get(&key, req.headers()),
+            None => None,
+        };
+        if let Some(cached) = cached.as_ref() {
+            if let Ok(value) = HeaderValue::from_str(&cached.etag) {
+                req.headers_mut().entry(IF_NONE_MATCH).or_insert(value);
+            }
+        }
+        // The explicit `If-None-Match` is kept, so the cached response applies only if it's the same
+        let cached = cached.filter(|cached| {
+            req.headers()
+                .get(IF_NONE_MATCH)
+                .is_some_and(|v| v.as_bytes() == cached.etag.as_bytes())
+        });
+        let headers = req.headers().clone();
+
+        let mut res = next.run(req, extensions).await?;
+        if let (StatusCode::NOT_MODIFIED, Some(cached)) = (res.status(), cached) {
+            res.extensions_mut().insert(cached);
+        }
+        if let Some(vary) = collect_vary(res.headers(), &headers) {
+            res.extensions_mut().insert(CacheKey { key, vary });
+        }
+        Ok(res)
+    }
+}
+
+/// Build the key of `GET` request, which consists of url, `Accept` and the digest of credentials
+fn build_key(req: &Request) -> Option<String> {
+    if req.method() != Method::GET {
+        return None;
+    }
+    let join = |name: &HeaderName| {
+        req.headers()
+            .get_all(name)
+            .iter()
+            .map(|v| String::from_utf8_lossy(v.as_bytes()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let credentials = [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE]
+        .iter()
+        .map(join)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(format!(
+        "{} {} {}",
+        req.url(),
+        join(&ACCEPT),
+        digest::sha256(credentials)
+    ))
+}
+
+/// Collect the request headers listed in `Vary` of response, and return `None` for `Vary: *`
+fn collect_vary(
+    res_headers: &HeaderMap,
+    req_headers: &HeaderMap,
+) -> Option<Vec<(HeaderName, Option<HeaderValue>)>> {
+    let mut vary = vec![];
+    for value in res_headers.get_all(VARY) {
+        for name in value.to_str().ok()?.split(',').map(str::trim) {
+            if name == "*" {
+                return None;
+            }
+            if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+                let value = req_headers.get(&name).cloned();
+                vary.push((name, value));
+            }
+        }
+    }
+    Some(vary)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_LANGUAGE, VARY};
+
+    use super::{collect_vary, CacheKey, ResponseCache};
+    use crate::ResponseBody;
+
+    fn key(key: &str) -> CacheKey {
+        CacheKey {
+            key: key.to_string(),
+            vary: vec![],
+        }
+    }
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let cache = ResponseCache::new(2);
+        cache.put(
+            key("a"),
+            "1".to_string(),
+            HeaderMap::new(),
+            ResponseBody::Json(json!(1)),
+        );
+        cache.put(
+            key("b"),
+            "2".to_string(),
+            HeaderMap::new(),
+            ResponseBody::Json(json!(2)),
+        );
+        assert!(cache.get("a", &HeaderMap::new()).is_some());
+        cache.put(
+            key("c"),
+            "3".to_string(),
+            HeaderMap::new(),
+            ResponseBody::Json(json!(3)),
+        );
+        assert_eq!(2, cache.len());
+        let etag = |key| cache.get(key, &HeaderMap::new()).map(|c| c.etag);
+        assert_eq!(Some("1".to_string()), etag("a"));
+        assert_eq!(None, etag("b"));
+        assert_eq!(Some("3".to_string()), etag("c"));
+    }
+
+    #[test]
+    fn test_vary() {
+        let mut req_headers = HeaderMap::new();
+        req_headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en"));
+        let mut res_headers = HeaderMap::new();
+        res_headers.insert(VARY, HeaderValue::from_static("Accept-Language"));
+
+        let cache = ResponseCache::new(2);
+        let vary = collect_vary(&res_headers, &req_headers).unwrap();
+        cache.put(
+            CacheKey {
+                key: "a".to_string(),
+                vary,
+            },
+            "1".to_string(),
+            HeaderMap::new(),
+            ResponseBody::Json(json!(1)),
+        );
+        assert!(cache.get("a", &req_headers).is_some());
+
+        req_headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("fr"));
+        assert!(cache.get("a", &req_headers).is_none());
+
+        res_headers.insert(VARY, HeaderValue::from_static("*"));
+        assert!(collect_vary(&res_headers, &req_headers).is_none());
+    }
+}
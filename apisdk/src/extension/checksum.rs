@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use http::Extensions;
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Request, Response,
+};
+use reqwest_middleware::{Middleware, Next, RequestBuilder, RequestInitialiser};
+
+use crate::digest;
+
+/// This enum represents the algorithm of checksum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// MD5, encoded as base64, e.g. `Content-MD5`
+    Md5Base64,
+    /// MD5, encoded as hex
+    Md5Hex,
+    /// SHA256, encoded as base64
+    Sha256Base64,
+    /// SHA256, encoded as hex, e.g. `x-amz-content-sha256`
+    Sha256Hex,
+}
+
+impl ChecksumAlgorithm {
+    /// Compute checksum
+    /// - input: the bytes to compute
+    pub fn compute(&self, input: impl AsRef<[u8]>) -> String {
+        match self {
+            Self::Md5Base64 => digest::md5_base64(input),
+            Self::Md5Hex => digest::md5(input),
+            Self::Sha256Base64 => digest::sha256_base64(input),
+            Self::Sha256Hex => digest::sha256(input),
+        }
+    }
+}
+
+/// This struct is used to compute the checksum of request body, and set it as header
+///
+/// The checksum is computed over the serialized body, right before `ApiAuthenticator` runs,
+/// so the signer could include the header. Streaming bodies (e.g. multipart files) are not supported.
+///
+/// # Examples
+///
+/// ```
+/// // Apply to all requests
+/// let client = XxxApi::builder()
+///     .with_body_checksum(BodyChecksum::amz_content_sha256())
+///     .with_authenticator(signer)
+///     .build();
+///
+/// // Apply to single request
+/// let req = client.put("/bucket/key").await?;
+/// let req = req.with_extension(BodyChecksum::content_md5()).body(bytes);
+/// send!(req, ()).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct BodyChecksum {
+    /// The name of header
+    header: HeaderName,
+    /// The algorithm of checksum
+    algorithm: ChecksumAlgorithm,
+}
+
+impl BodyChecksum {
+    /// Create a new instance
+    /// - header: the name of header
+    /// - algorithm: the algorithm of checksum
+    pub fn new(header: HeaderName, algorithm: ChecksumAlgorithm) -> Self {
+        Self { header, algorithm }
+    }
+
+    /// Set `Content-MD5` header, which is base64-encoded MD5
+    pub fn content_md5() -> Self {
+        Self::new(
+            HeaderName::from_static("content-md5"),
+            ChecksumAlgorithm::Md5Base64,
+        )
+    }
+
+    /// Set `x-amz-content-sha256` header, which is hex-encoded SHA256
+    pub fn amz_content_sha256() -> Self {
+        Self::new(
+            HeaderName::from_static("x-amz-content-sha256"),
+            ChecksumAlgorithm::Sha256Hex,
+        )
+    }
+
+    /// Compute checksum, and set header
+    /// - req: the request to update
+    fn apply(&self, req: &mut Request) -> Result<(), reqwest_middleware::Error> {
+        let checksum = match req.body() {
+            Some(body) => match body.as_bytes() {
+                Some(bytes) => self.algorithm.compute(bytes),
+                None => {
+                    return Err(reqwest_middleware::Error::Middleware(anyhow::format_err!(
+                        "Unable to compute {} for streaming body",
+                        self.header
+                    )))
+                }
+            },
+            None => self.algorithm.compute([]),
+        };
+        let value = HeaderValue::from_str(&checksum)
+            .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?;
+        req.headers_mut().insert(self.header.clone(), value);
+        Ok(())
+    }
+}
+
+impl RequestInitialiser for BodyChecksum {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        req.with_extension(self.clone())
+    }
+}
+
+/// This middleware is used to apply `BodyChecksum`
+#[derive(Default)]
+pub(crate) struct BodyChecksumMiddleware;
+
+#[async_trait]
+impl Middleware for BodyChecksumMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        let mut req = req;
+        if let Some(checksum) = extensions.get::<BodyChecksum>() {
+            checksum.apply(&mut req)?;
+        }
+        next.run(req, extensions).await
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::SystemTime;
+
+use reqwest::header::{HeaderValue, DATE};
+use reqwest_middleware::{RequestBuilder, RequestInitialiser};
+
+use crate::DefaultHeaderMap;
+
+/// This initialiser is used to set `Date` header when the request is created
+///
+/// The header is applied as a default header, which is set before user middlewares and
+/// `ApiAuthenticator` run, so the signer could sign the exact value which will be sent.
+/// An explicit `Date` header of the request is kept as is.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_initialiser(DateHeader)
+///     .with_authenticator(signer)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateHeader;
+
+impl RequestInitialiser for DateHeader {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        let value = match HeaderValue::from_str(&httpdate::fmt_http_date(SystemTime::now())) {
+            Ok(value) => value,
+            Err(_) => return req,
+        };
+        let mut headers = req
+            .extensions()
+            .remove::<DefaultHeaderMap>()
+            .map(|DefaultHeaderMap(headers)| headers)
+            .unwrap_or_default();
+        headers.insert(DATE, value);
+        req.with_extension(DefaultHeaderMap(headers))
+    }
+}
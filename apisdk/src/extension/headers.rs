@@ -0,0 +1,190 @@
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use http::Extensions;
+use reqwest::{
+    header::{Entry, HeaderMap, HeaderValue, ACCEPT},
+    Request, Response,
+};
+use reqwest_middleware::{Middleware, Next, RequestBuilder, RequestInitialiser};
+
+use crate::MimeType;
+
+/// This extension will set `Connection: close`, so the server closes the connection after response
+///
+/// It's used to disable keep-alive for specific requests, e.g. for buggy upstreams.
+/// It's ignored by HTTP/2, which doesn't support `Connection` header.
+///
+/// The underlying client still returns the connection to its pool, unless the server answers with
+/// `Connection: close` as well. So the next request to the same host may fail on the closed connection,
+/// and it's recommended to use this extension for the last request to a host only.
+///
+/// # Example
+///
+/// ```
+/// let req = client.get("/path").await?;
+/// let req = req.with_extension(CloseConnection);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CloseConnection;
+
+/// This initialiser will set `Accept` header, if the request doesn't have one
+///
+/// The header is set right before sending, so an explicit `Accept` always takes precedence.
+///
+/// # Examples
+///
+/// ### apply to all requests
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_initialiser(DefaultAccept(MimeType::Json))
+///     .build();
+/// ```
+///
+/// ### apply to single request
+///
+/// ```
+/// let req = client.get("/api/path").await?;
+/// let req = req.with_extension(DefaultAccept(MimeType::Xml));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DefaultAccept(pub MimeType);
+
+/// Apply DefaultAccept to all requests
+impl RequestInitialiser for DefaultAccept {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<DefaultAccept>() {
+            Some(_) => req,
+            None => req.with_extension(self.clone()),
+        }
+    }
+}
+
+/// This middleware is used to apply `DefaultAccept`
+#[derive(Default)]
+pub(crate) struct DefaultAcceptMiddleware;
+
+#[async_trait]
+impl Middleware for DefaultAcceptMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        let mut req = req;
+        if let Some(DefaultAccept(mime)) = extensions.get::<DefaultAccept>() {
+            if !req.headers().contains_key(ACCEPT) {
+                req.headers_mut()
+                    .insert(ACCEPT, HeaderValue::from(mime.clone()));
+            }
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// This initialiser is used to apply default headers, which could be updated after construction
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DefaultHeaders {
+    /// The shared headers
+    headers: Arc<RwLock<HeaderMap>>,
+}
+
+impl DefaultHeaders {
+    /// Insert or update headers
+    /// - headers: the headers to merge
+    pub fn merge(&self, headers: HeaderMap) {
+        if let Ok(mut current) = self.headers.write() {
+            merge_headers(&mut current, headers);
+        }
+    }
+
+    /// Remove header
+    /// - name: the name of header
+    pub fn remove(&self, name: &str) {
+        if let Ok(mut current) = self.headers.write() {
+            current.remove(name);
+        }
+    }
+}
+
+impl RequestInitialiser for DefaultHeaders {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        match self.headers.read() {
+            Ok(headers) if !headers.is_empty() => {
+                req.with_extension(DefaultHeaderMap(headers.clone()))
+            }
+            _ => req,
+        }
+    }
+}
+
+/// The snapshot of default headers, which is attached to request by `DefaultHeaders` and `DateHeader`
+#[derive(Debug, Clone)]
+pub(crate) struct DefaultHeaderMap(pub HeaderMap);
+
+/// The headers layered onto default headers, which is attached to request by `ApiCore::with_default_headers`
+#[derive(Debug, Clone)]
+pub(crate) struct OverlayHeaderMap(pub Arc<HeaderMap>);
+
+/// This middleware is used to apply overlay headers and default headers
+///
+/// The headers are applied right before sending, and only the absent ones are inserted,
+/// so the headers set on `RequestBuilder` always take precedence, and then the overlay ones.
+#[derive(Default)]
+pub(crate) struct DefaultHeadersMiddleware;
+
+#[async_trait]
+impl Middleware for DefaultHeadersMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        let mut req = req;
+        if let Some(OverlayHeaderMap(headers)) = extensions.get::<OverlayHeaderMap>() {
+            insert_absent_headers(req.headers_mut(), headers);
+        }
+        if let Some(DefaultHeaderMap(headers)) = extensions.get::<DefaultHeaderMap>() {
+            insert_absent_headers(req.headers_mut(), headers);
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// Insert headers which are absent, and keep all values of the same name
+/// - current: the headers to update
+/// - headers: the headers to insert
+fn insert_absent_headers(current: &mut HeaderMap, headers: &HeaderMap) {
+    for name in headers.keys() {
+        if let Entry::Vacant(entry) = current.entry(name) {
+            let mut values = headers.get_all(name).iter();
+            if let Some(value) = values.next() {
+                let mut entry = entry.insert_entry(value.clone());
+                for value in values {
+                    entry.append(value.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Merge headers, which replaces the existing headers with the same name
+/// - current: the headers to update
+/// - headers: the headers to merge
+pub(crate) fn merge_headers(current: &mut HeaderMap, headers: HeaderMap) {
+    let mut last_name = None;
+    for (name, value) in headers {
+        // `None` means the same header with multiple values
+        if let Some(name) = name {
+            current.remove(&name);
+            last_name = Some(name);
+        }
+        if let Some(name) = last_name.as_ref() {
+            current.append(name, value);
+        }
+    }
+}
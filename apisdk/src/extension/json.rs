@@ -0,0 +1,212 @@
+use std::{any::type_name, sync::Arc};
+
+use reqwest_middleware::{RequestBuilder, RequestInitialiser};
+use serde_json::Value;
+
+#[cfg(feature = "jsonschema")]
+use crate::{ApiError, ApiResult};
+
+/// This struct is used to control how JSON payload is serialized
+///
+/// # Examples
+///
+/// ### apply to all requests
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_json_options(JsonOptions::default().skip_nulls())
+///     .build();
+/// ```
+///
+/// ### apply to single request
+///
+/// ```
+/// let req = client.post("/api/path").await?;
+/// let req = req.with_extension(JsonOptions::default().skip_nulls());
+/// let res = send_json!(req, payload).await?;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    /// Remove `null` fields from objects recursively
+    pub skip_nulls: bool,
+}
+
+impl JsonOptions {
+    /// Remove `null` fields from objects recursively before sending.
+    ///
+    /// The payload has to be walked through once more after serialization,
+    /// so prefer `#[serde(skip_serializing_if = "Option::is_none")]` for large or hot payloads.
+    pub fn skip_nulls(self) -> Self {
+        Self { skip_nulls: true }
+    }
+
+    /// Apply options to serialized payload
+    pub(crate) fn apply(&self, json: &mut Value) {
+        if self.skip_nulls {
+            strip_nulls(json);
+        }
+    }
+}
+
+/// Apply JsonOptions to all requests
+impl RequestInitialiser for JsonOptions {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<JsonOptions>() {
+            Some(_) => req,
+            None => req.with_extension(*self),
+        }
+    }
+}
+
+/// Validate JSON payload before sending. It should be used with RequestValidator.
+pub trait JsonValidator: 'static + Send + Sync {
+    /// Get type_name, used in Debug
+    fn type_name(&self) -> &str {
+        type_name::<Self>()
+    }
+
+    /// Validate the payload, and return the detail of violation
+    /// - json: the serialized payload
+    fn validate(&self, json: &Value) -> Result<(), String>;
+}
+
+/// Implement JsonValidator for function / closure
+impl<F> JsonValidator for F
+where
+    F: 'static + Send + Sync,
+    F: Fn(&Value) -> Result<(), String>,
+{
+    fn validate(&self, json: &Value) -> Result<(), String> {
+        self(json)
+    }
+}
+
+/// This struct is used to reject invalid JSON payload before sending
+///
+/// The validation failure will be returned as `ApiError::SerializeRequest`,
+/// and the request will not be sent.
+///
+/// JSON Schema is supported by `RequestValidator::schema` (requires `jsonschema` feature).
+/// Any other validator could be plugged in by implementing `JsonValidator`, or by using a closure.
+///
+/// # Examples
+///
+/// ### validate single request
+///
+/// ```
+/// let req = client.post("/api/path").await?;
+/// let req = req.with_extension(RequestValidator::new(|json: &Value| {
+///     match json.get("name") {
+///         Some(Value::String(_)) => Ok(()),
+///         _ => Err("name is required".to_string()),
+///     }
+/// }));
+/// let res = send_json!(req, payload).await?;
+/// ```
+///
+/// ### validate all requests by JSON Schema
+///
+/// ```
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "name": { "type": "string" } },
+///     "required": ["name"]
+/// });
+/// let client = XxxApi::builder()
+///     .with_initialiser(RequestValidator::schema(&schema)?)
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct RequestValidator {
+    /// Internal validator
+    inner: Arc<dyn JsonValidator>,
+}
+
+impl RequestValidator {
+    /// Create a new instance
+    pub fn new(validator: impl JsonValidator) -> Self {
+        Self {
+            inner: Arc::new(validator),
+        }
+    }
+
+    /// Create a new instance by JSON Schema (requires `jsonschema` feature)
+    /// - schema: the JSON Schema
+    ///
+    /// Return `ApiError::Config` if the schema is invalid.
+    /// All violations are reported along with their json pointers, e.g. `1 is not of type "string" at "/name"`.
+    #[cfg(feature = "jsonschema")]
+    pub fn schema(schema: &Value) -> ApiResult<Self> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| ApiError::Config(format!("Invalid json schema: {}", e)))?;
+        Ok(Self::new(move |json: &Value| {
+            let errors: Vec<_> = validator
+                .iter_errors(json)
+                .map(|e| format!("{} at {:?}", e, e.instance_path.to_string()))
+                .collect();
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors.join("; "))
+            }
+        }))
+    }
+}
+
+impl std::fmt::Debug for RequestValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestValidator")
+            .field("inner", &self.inner.type_name())
+            .finish()
+    }
+}
+
+impl JsonValidator for RequestValidator {
+    fn type_name(&self) -> &str {
+        self.inner.type_name()
+    }
+
+    fn validate(&self, json: &Value) -> Result<(), String> {
+        // Delegate to internal validator
+        self.inner.validate(json)
+    }
+}
+
+/// Validate all requests
+impl RequestInitialiser for RequestValidator {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        req.with_extension(self.clone())
+    }
+}
+
+/// Remove `null` fields from objects recursively
+/// - json: the value to update
+fn strip_nulls(json: &mut Value) {
+    match json {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        Value::Array(array) => array.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::JsonOptions;
+
+    #[test]
+    fn test_skip_nulls() {
+        let mut json = json!({
+            "a": null,
+            "b": 1,
+            "c": { "d": null, "e": [null, { "f": null, "g": "x" }] },
+        });
+        JsonOptions::default().skip_nulls().apply(&mut json);
+        assert_eq!(json!({ "b": 1, "c": { "e": [null, { "g": "x" }] } }), json);
+    }
+}
@@ -0,0 +1,64 @@
+use reqwest_middleware::{RequestBuilder, RequestInitialiser};
+
+/// This extension will try to parse `text/plain` and `text/html` response body as JSON
+///
+/// Some servers return JSON payload with a wrong content-type. With this extension, such payload
+/// will be returned as `ResponseBody::Json`, and it falls back to text if the payload is not JSON.
+///
+/// # Examples
+///
+/// ### apply to all requests
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_initialiser(LenientJson)
+///     .build();
+/// ```
+///
+/// ### apply to single request
+///
+/// ```
+/// let req = client.get("/api/path").await?;
+/// let req = req.with_extension(LenientJson);
+/// let res: Data = send!(req, CodeDataMessage).await?;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LenientJson;
+
+/// Apply LenientJson to all requests
+impl RequestInitialiser for LenientJson {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<LenientJson>() {
+            Some(_) => req,
+            None => req.with_extension(*self),
+        }
+    }
+}
+
+/// This extension will sniff the payload to choose parser, when `Content-Type` header is absent
+///
+/// The first non-whitespace byte is checked: `{` or `[` means JSON, `<` means XML, and others
+/// mean text. Sniffing only triggers when the header is absent, so `text/plain` is still treated
+/// as text (see `LenientJson`).
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_content_sniffing(true)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContentSniffing;
+
+/// Apply ContentSniffing to all requests
+impl RequestInitialiser for ContentSniffing {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<ContentSniffing>() {
+            Some(_) => req,
+            None => req.with_extension(*self),
+        }
+    }
+}
@@ -1,15 +1,20 @@
-use std::{collections::HashMap, str::FromStr, sync::OnceLock, time::Instant};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
-use http::Extensions;
+use http::{Extensions, Method, StatusCode};
 use lazy_static::lazy_static;
 use log::{Level, LevelFilter};
 use regex::Regex;
 use reqwest::{Request, Response};
 use reqwest_middleware::{Middleware, Next, RequestBuilder, RequestInitialiser};
-use serde_json::Value;
+use serde_json::{json, Value};
 
-use crate::ResponseBody;
+use crate::{server_timing::ServerTiming, ResponseBody};
 
 static DEFAULT_LOG_LEVEL: OnceLock<LevelFilter> = OnceLock::new();
 
@@ -55,18 +60,38 @@ impl IntoFilter for Level {
     }
 }
 
+/// This enum represents the format of logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines, e.g. `#[request_id] Response Body(Json) @12ms`
+    #[default]
+    Text,
+    /// One JSON object per line, with `request_id`, `phase` and `elapsed_ms` fields,
+    /// and `method` / `url` / `status` when they are known
+    Json,
+}
+
 /// This struct is used to control how to log.
 /// It could be injected into request as an extension.
 #[derive(Debug, Clone)]
 pub struct LogConfig {
     /// Level filter
     pub level: LevelFilter,
+    /// The format of logs
+    pub format: LogFormat,
+    /// Pretty-print JSON bodies in text logs, while the request is still sent as compact JSON
+    pub pretty_json: bool,
+    /// Emit a warn line when the elapsed time of response exceeds this threshold
+    pub slow_threshold: Option<Duration>,
 }
 
 impl Default for LogConfig {
     fn default() -> Self {
         Self {
             level: get_default_log_level(),
+            format: LogFormat::default(),
+            pretty_json: false,
+            slow_threshold: None,
         }
     }
 }
@@ -79,6 +104,20 @@ impl LogConfig {
     {
         Self {
             level: level.into_filter().unwrap_or(get_default_log_level()),
+            format: LogFormat::default(),
+            pretty_json: false,
+            slow_threshold: None,
+        }
+    }
+
+    /// Construct a new instance, which writes logs as JSON lines
+    pub fn json<L>(level: L) -> Self
+    where
+        L: IntoFilter,
+    {
+        Self {
+            format: LogFormat::Json,
+            ..Self::new(level)
         }
     }
 
@@ -86,6 +125,27 @@ impl LogConfig {
     pub fn off() -> Self {
         Self {
             level: LevelFilter::Off,
+            format: LogFormat::default(),
+            pretty_json: false,
+            slow_threshold: None,
+        }
+    }
+
+    /// Set the threshold of slow requests, which is disabled by default
+    /// - threshold: the elapsed time to emit a warn line
+    pub fn with_slow_threshold(self, threshold: Duration) -> Self {
+        Self {
+            slow_threshold: Some(threshold),
+            ..self
+        }
+    }
+
+    /// Set whether to pretty-print JSON bodies in text logs
+    /// - pretty_json: true to pretty-print
+    pub fn with_pretty_json(self, pretty_json: bool) -> Self {
+        Self {
+            pretty_json,
+            ..self
         }
     }
 }
@@ -111,12 +171,13 @@ impl Middleware for LogMiddleware {
         extensions: &mut Extensions,
         next: Next<'_>,
     ) -> Result<Response, reqwest_middleware::Error> {
+        let method = req.method().clone();
         if let Some(logger) = extensions.get::<Logger>() {
             logger.log_request(&req);
         }
         let res = next.run(req, extensions).await?;
         if let Some(logger) = extensions.get::<Logger>() {
-            logger.log_response(&res);
+            logger.log_response(&method, &res);
         }
         Ok(res)
     }
@@ -129,6 +190,80 @@ pub(crate) enum RequestPayload {
     Xml(String),
     Form(HashMap<String, String>),
     Multipart(HashMap<String, String>),
+    Stream(String),
+}
+
+/// This struct represents a log record, which is sent to `LogSink`
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+    /// The level of log
+    pub level: Level,
+    /// The target of log, which is the path of api function
+    pub target: String,
+    /// The X-Request-ID value
+    pub request_id: String,
+    /// The message
+    pub message: String,
+}
+
+/// This struct is used to receive log records, instead of writing them via `log` facade.
+/// It could be injected into request as an extension.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_log_sink(|record: LogRecord| println!("{} {}", record.level, record.message))
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct LogSink(Arc<dyn Fn(LogRecord) + Send + Sync>);
+
+impl std::fmt::Debug for LogSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LogSink").finish()
+    }
+}
+
+impl LogSink {
+    /// Create a new instance
+    /// - sink: the callback to receive log records
+    pub fn new<F>(sink: F) -> Self
+    where
+        F: Fn(LogRecord) + Send + Sync + 'static,
+    {
+        Self(Arc::new(sink))
+    }
+
+    /// Send the log record
+    pub fn emit(&self, record: LogRecord) {
+        (self.0)(record)
+    }
+}
+
+impl RequestInitialiser for LogSink {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<LogSink>() {
+            Some(_) => req,
+            None => req.with_extension(self.clone()),
+        }
+    }
+}
+
+/// This struct holds the name of api, which is used to label logs and spans.
+/// It could be injected into request as an extension.
+#[derive(Debug, Clone)]
+pub struct ApiName(pub String);
+
+impl RequestInitialiser for ApiName {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<ApiName>() {
+            Some(_) => req,
+            None => req.with_extension(self.clone()),
+        }
+    }
 }
 
 /// This struct is used to write information to log
@@ -144,6 +279,14 @@ pub(crate) struct Logger {
     pub start: Instant,
     /// The request payload
     pub payload: Option<RequestPayload>,
+    /// The sink of log, which replaces `log` facade
+    pub sink: Option<LogSink>,
+    /// The format of log
+    pub format: LogFormat,
+    /// Pretty-print JSON bodies or not
+    pub pretty_json: bool,
+    /// The threshold of slow requests
+    pub slow_threshold: Option<Duration>,
 }
 
 lazy_static! {
@@ -159,6 +302,10 @@ impl Logger {
             request_id,
             start: Instant::now(),
             payload: None,
+            sink: None,
+            format: LogFormat::default(),
+            pretty_json: false,
+            slow_threshold: None,
         }
     }
 
@@ -167,6 +314,38 @@ impl Logger {
         self.log_level.is_some()
     }
 
+    /// Extends with the name of api, which prefixes the target of log
+    pub fn with_name(mut self, name: Option<&ApiName>) -> Self {
+        if let Some(name) = name {
+            self.log_target = format!("{}::{}", name.0, self.log_target);
+        }
+        self
+    }
+
+    /// Extends with log sink
+    pub fn with_sink(mut self, sink: Option<LogSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Extends with log format
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Extends with pretty-print option of JSON bodies
+    pub fn with_pretty_json(mut self, pretty_json: bool) -> Self {
+        self.pretty_json = pretty_json;
+        self
+    }
+
+    /// Extends with the threshold of slow requests
+    pub fn with_slow_threshold(mut self, slow_threshold: Option<Duration>) -> Self {
+        self.slow_threshold = slow_threshold;
+        self
+    }
+
     /// Extends with json payload
     pub fn with_json(mut self, json: Value) -> Self {
         self.payload = Some(RequestPayload::Json(json));
@@ -190,59 +369,204 @@ impl Logger {
         self.payload = Some(RequestPayload::Multipart(meta));
         self
     }
+
+    /// Extends with streamed payload, which is logged as a placeholder
+    pub fn with_stream(mut self, content_type: String) -> Self {
+        self.payload = Some(RequestPayload::Stream(content_type));
+        self
+    }
 }
 
 impl Logger {
-    /// Log request
+    /// Write message to sink, or `log` facade
+    fn write(&self, level: Level, message: std::fmt::Arguments) {
+        match self.sink.as_ref() {
+            Some(sink) => sink.emit(LogRecord {
+                level,
+                target: self.log_target.clone(),
+                request_id: self.request_id.clone(),
+                message: message.to_string(),
+            }),
+            None => match self.format {
+                LogFormat::Text => {
+                    log::log!(target: &self.log_target, level, "#[{}] {}", self.request_id, message)
+                }
+                LogFormat::Json => log::log!(target: &self.log_target, level, "{}", message),
+            },
+        }
+    }
+
+    /// Write event as formatted message, or JSON object
+    /// - level: the level of log
+    /// - phase: the phase of event, e.g. `request` or `response`
+    /// - fields: the extra fields of JSON object, which are built only in JSON format
+    /// - message: the formatted message
+    fn event<F>(&self, level: Level, phase: &str, fields: F, message: std::fmt::Arguments)
+    where
+        F: FnOnce() -> Value,
+    {
+        match self.format {
+            LogFormat::Text => self.write(level, message),
+            LogFormat::Json => {
+                let mut event = json!({
+                    "request_id": self.request_id,
+                    "phase": phase,
+                    "elapsed_ms": self.start.elapsed().as_millis() as u64,
+                });
+                if let (Value::Object(event), Value::Object(fields)) = (&mut event, fields()) {
+                    event.extend(fields);
+                }
+                self.write(level, format_args!("{}", event));
+            }
+        }
+    }
+
+    /// Format JSON body for text logs
+    fn format_json(&self, json: &Value) -> String {
+        match self.pretty_json {
+            true => format!("{:#}", json),
+            false => json.to_string(),
+        }
+    }
+
+    /// Log request, as a compact summary line, e.g. `#[request_id] --> GET https://host/path`
+    ///
+    /// The payload, if any, is logged as separate lines at the same level.
     pub fn log_request(&self, req: &Request) {
         if let Some(level) = self.log_level {
-            log::log!(target: &self.log_target, level, "#[{}] {:?}", self.request_id, req);
+            self.log_request_summary(level, req);
             if let Some(payload) = self.payload.as_ref() {
                 self.log_request_payload(level, payload);
             }
         }
     }
 
+    fn log_request_summary(&self, level: Level, req: &Request) {
+        self.event(
+            level,
+            "request",
+            || json!({ "method": req.method().as_str(), "url": req.url().as_str() }),
+            format_args!("--> {} {}", req.method(), req.url()),
+        );
+    }
+
     fn log_request_payload(&self, level: Level, payload: &RequestPayload) {
         match payload {
             RequestPayload::Json(json) => {
-                log::log!(target: &self.log_target, level, "#[{}] Request Json\n{}", self.request_id, json);
+                self.event(
+                    level,
+                    "request.json",
+                    || json!({ "body": json }),
+                    format_args!("Request Json\n{}", self.format_json(json)),
+                );
             }
             RequestPayload::Xml(xml) => {
-                log::log!(target: &self.log_target, level, "#[{}] Request Xml\n{:?}", self.request_id, xml);
+                self.event(
+                    level,
+                    "request.xml",
+                    || json!({ "body": xml }),
+                    format_args!("Request Xml\n{:?}", xml),
+                );
             }
             RequestPayload::Form(meta) => {
-                log::log!(target: &self.log_target, level, "#[{}] Request Form\n{:?}", self.request_id, meta);
+                self.event(
+                    level,
+                    "request.form",
+                    || json!({ "body": meta }),
+                    format_args!("Request Form\n{:?}", meta),
+                );
             }
             RequestPayload::Multipart(meta) => {
-                log::log!(target: &self.log_target, level, "#[{}] Request Multipart\n{:?}", self.request_id, meta);
+                self.event(
+                    level,
+                    "request.multipart",
+                    || json!({ "body": meta }),
+                    format_args!("Request Multipart\n{:?}", meta),
+                );
+            }
+            RequestPayload::Stream(content_type) => {
+                self.event(
+                    level,
+                    "request.stream",
+                    || json!({ "content_type": content_type }),
+                    format_args!("Request Stream\n<streamed body of {}>", content_type),
+                );
             }
         }
     }
 
     /// Log response
-    pub fn log_response(&self, res: &Response) {
+    pub fn log_response(&self, method: &Method, res: &Response) {
         if let Some(level) = self.log_level {
-            log::log!(
-                target: &self.log_target,
+            self.event(
                 level,
-                "#[{}] {:?} @{}ms",
-                self.request_id,
-                res,
-                self.start.elapsed().as_millis()
+                "response",
+                || {
+                    json!({
+                        "method": method.as_str(),
+                        "url": res.url().as_str(),
+                        "status": res.status().as_u16(),
+                    })
+                },
+                format_args!("{:?} @{}ms", res, self.start.elapsed().as_millis()),
             );
+            if let Some(timing) = ServerTiming::parse(res.headers()) {
+                self.log_server_timing(level, &timing);
+            }
+            if let Some(threshold) = self.slow_threshold {
+                self.log_slow_response(level, threshold);
+            }
         }
     }
 
+    /// Log slow response as warn or higher level, if the elapsed time exceeds threshold
+    fn log_slow_response(&self, level: Level, threshold: Duration) {
+        let elapsed = self.start.elapsed();
+        if elapsed > threshold {
+            self.event(
+                level.min(Level::Warn),
+                "response.slow",
+                || json!({ "threshold_ms": threshold.as_millis() as u64 }),
+                format_args!(
+                    "Slow request @{}ms, which exceeds {}ms",
+                    elapsed.as_millis(),
+                    threshold.as_millis()
+                ),
+            );
+        }
+    }
+
+    /// Log `Server-Timing` metrics, along with client-measured elapsed
+    fn log_server_timing(&self, level: Level, timing: &ServerTiming) {
+        self.event(
+            level,
+            "response.server_timing",
+            || {
+                json!({
+                    "server_ms": timing.total().as_millis() as u64,
+                    "server_timing": timing.to_string(),
+                })
+            },
+            format_args!(
+                "Server-Timing @{}ms: {}ms by server\n{}",
+                self.start.elapsed().as_millis(),
+                timing.total().as_millis(),
+                timing
+            ),
+        );
+    }
+
     /// Log empty response
     pub fn log_response_empty(&self) {
         if let Some(level) = self.log_level {
-            log::log!(
-                target: &self.log_target,
+            self.event(
                 level,
-                "#[{}] Response Body(Empty) @{}ms",
-                self.request_id,
-                self.start.elapsed().as_millis(),
+                "response.empty",
+                || json!({}),
+                format_args!(
+                    "Response Body(Empty) @{}ms",
+                    self.start.elapsed().as_millis()
+                ),
             );
         }
     }
@@ -250,13 +574,15 @@ impl Logger {
     /// Log response json payload
     pub fn log_response_json(&self, json: &Value) {
         if let Some(level) = self.log_level {
-            log::log!(
-                target: &self.log_target,
+            self.event(
                 level,
-                "#[{}] Response Body(Json) @{}ms\n{}",
-                self.request_id,
-                self.start.elapsed().as_millis(),
-                serde_json::to_string(json).unwrap_or_default()
+                "response.json",
+                || json!({ "body": json }),
+                format_args!(
+                    "Response Body(Json) @{}ms\n{}",
+                    self.start.elapsed().as_millis(),
+                    self.format_json(json)
+                ),
             );
         }
     }
@@ -264,13 +590,16 @@ impl Logger {
     /// Log response xml payload
     pub fn log_response_xml(&self, xml: &str) {
         if let Some(level) = self.log_level {
-            log::log!(
-                target: &self.log_target,
+            let xml = &xml[0..1024.min(xml.len())];
+            self.event(
                 level,
-                "#[{}] Response Body(Xml) @{}ms\n{}",
-                self.request_id,
-                self.start.elapsed().as_millis(),
-                &xml[0..1024.min(xml.len())]
+                "response.xml",
+                || json!({ "body": xml }),
+                format_args!(
+                    "Response Body(Xml) @{}ms\n{}",
+                    self.start.elapsed().as_millis(),
+                    xml
+                ),
             );
         }
     }
@@ -278,13 +607,49 @@ impl Logger {
     /// Log response text payload
     pub fn log_response_text(&self, text: &str) {
         if let Some(level) = self.log_level {
-            log::log!(
-                target: &self.log_target,
+            let text = &text[0..1024.min(text.len())];
+            self.event(
                 level,
-                "#[{}] Response Body(Text) @{}ms\n{}",
-                self.request_id,
-                self.start.elapsed().as_millis(),
-                &text[0..1024.min(text.len())]
+                "response.text",
+                || json!({ "body": text }),
+                format_args!(
+                    "Response Body(Text) @{}ms\n{}",
+                    self.start.elapsed().as_millis(),
+                    text
+                ),
+            );
+        }
+    }
+
+    /// Log response bytes
+    pub fn log_response_bytes(&self, bytes: &[u8]) {
+        if let Some(level) = self.log_level {
+            self.event(
+                level,
+                "response.bytes",
+                || json!({ "size": bytes.len() }),
+                format_args!(
+                    "Response Body(Bytes) @{}ms\n{} bytes",
+                    self.start.elapsed().as_millis(),
+                    bytes.len()
+                ),
+            );
+        }
+    }
+
+    /// Log response body which is written into file
+    pub fn log_response_file(&self, path: &std::path::Path, written: u64) {
+        if let Some(level) = self.log_level {
+            self.event(
+                level,
+                "response.file",
+                || json!({ "size": written, "path": path.display().to_string() }),
+                format_args!(
+                    "Response Body(File) @{}ms\n{} bytes => {}",
+                    self.start.elapsed().as_millis(),
+                    written,
+                    path.display()
+                ),
             );
         }
     }
@@ -292,9 +657,27 @@ impl Logger {
     /// Log mock request and response
     pub fn log_mock_request_and_response(&self, req: &Request, mock_name: &str) {
         if let Some(level) = self.log_level {
-            log::log!(target: &self.log_target, level, "#[{}] {:?}", self.request_id, req);
-            log::log!(target: &self.log_target, level, "#[{}] Response (MOCK) <= {}", self.request_id, mock_name);
+            self.log_request_summary(level, req);
+            self.event(
+                level,
+                "response.mock",
+                || json!({ "mock": mock_name }),
+                format_args!("Response (MOCK) <= {}", mock_name),
+            );
+        }
+    }
+
+    /// Log cached response body, which is returned on `304 Not Modified`
+    pub fn log_response_cached(&self, body: &ResponseBody) {
+        if let Some(level) = self.log_level {
+            self.event(
+                level,
+                "response.cached",
+                || json!({ "status": StatusCode::NOT_MODIFIED.as_u16() }),
+                format_args!("Response (CACHED) <= 304 Not Modified"),
+            );
         }
+        self.log_mock_response_body(body);
     }
 
     /// Log mock response body
@@ -303,20 +686,19 @@ impl Logger {
             ResponseBody::Empty => self.log_response_empty(),
             ResponseBody::Json(json) => self.log_response_json(json),
             ResponseBody::Xml(xml) => self.log_response_xml(xml),
-            ResponseBody::Text(text) => self.log_response_text(text),
+            ResponseBody::Html(text) | ResponseBody::Text(text) => self.log_response_text(text),
+            ResponseBody::Raw(bytes) => self.log_response_bytes(bytes),
         }
     }
 
     /// Log error as warn or higher level
     pub fn log_error(&self, e: impl std::fmt::Display) {
         let level = self.log_level.unwrap_or(Level::Debug).min(Level::Warn);
-        log::log!(
-            target: &self.log_target,
+        self.event(
             level,
-            "#[{}] Error @{}ms: {}",
-            self.request_id,
-            self.start.elapsed().as_millis(),
-            e
+            "error",
+            || json!({ "error": e.to_string() }),
+            format_args!("Error @{}ms: {}", self.start.elapsed().as_millis(), e),
         );
     }
 }
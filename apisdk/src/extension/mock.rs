@@ -1,7 +1,10 @@
-use std::{any::type_name, sync::Arc};
+use std::{
+    any::type_name,
+    sync::{Arc, Mutex, MutexGuard},
+};
 
 use async_trait::async_trait;
-use reqwest::Request;
+use reqwest::{Method, Request};
 use reqwest_middleware::{RequestBuilder, RequestInitialiser};
 
 use crate::ResponseBody;
@@ -33,6 +36,10 @@ where
 
 /// This middleware is used to mock the response
 ///
+/// The Responder supplies the body only, so the mocked response is always `200 OK` without headers.
+/// Therefore `send!(req, Full<T>)`, `send!(req, Allow)` and `send!(req, ResourceInfo)` can't inspect
+/// status or headers of a mocked response.
+///
 /// # Examples
 ///
 /// ### mock single request
@@ -92,3 +99,204 @@ impl RequestInitialiser for MockServer {
         req.with_extension(self.clone())
     }
 }
+
+/// The expected call of MockController
+#[derive(Debug)]
+struct Expectation {
+    /// HTTP method
+    method: Method,
+    /// The path of url, e.g. `/v1/posts`
+    path: String,
+    /// The expected times
+    times: usize,
+    /// The actual times
+    calls: usize,
+    /// The response body
+    body: ResponseBody,
+}
+
+/// The state of MockController
+#[derive(Debug, Default)]
+struct Expectations {
+    /// The expected calls
+    expected: Vec<Expectation>,
+    /// The unexpected calls, e.g. `GET /v1/posts`
+    unexpected: Vec<String>,
+}
+
+impl Expectations {
+    /// Collect the unmet expectations and unexpected calls
+    fn problems(&self) -> Vec<String> {
+        let mut problems: Vec<String> = self
+            .expected
+            .iter()
+            .filter(|e| e.calls != e.times)
+            .map(|e| {
+                format!(
+                    "{} {} is expected {} time(s), but called {} time(s)",
+                    e.method, e.path, e.times, e.calls
+                )
+            })
+            .collect();
+        problems.extend(
+            self.unexpected
+                .iter()
+                .map(|call| format!("{} is unexpected", call)),
+        );
+        problems
+    }
+}
+
+/// This struct is used to mock the responses by recorded expectations, and verify the calls
+///
+/// Each request is matched by method and path of url (including the path of base url), and the
+/// expectation which has remaining times is used. Any other request is recorded as unexpected,
+/// and fails with `ApiError::Middleware`.
+///
+/// The calls are verified when the controller is dropped, and it panics if any expected call
+/// wasn't made, or any unexpected one occurred. Use `verify` to check them explicitly.
+///
+/// # Examples
+///
+/// ```
+/// let mock = MockController::new();
+/// mock.expect(Method::GET, "/v1/posts/1")
+///     .times(2)
+///     .respond(ResponseBody::Json(json!({ "id": 1 })));
+///
+/// let client = XxxApi::builder().with_initialiser(mock.server()).build();
+/// // ... call client
+///
+/// // Panics if `GET /v1/posts/1` is not called twice
+/// drop(mock);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockController {
+    /// The shared state
+    state: Arc<Mutex<Expectations>>,
+}
+
+impl MockController {
+    /// Create a new instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect a call, which is expected once by default
+    /// - method: HTTP method
+    /// - path: the path of url, e.g. `/v1/posts`
+    #[must_use = "the expectation is recorded only when `respond` is called"]
+    pub fn expect(&self, method: Method, path: impl ToString) -> ExpectationBuilder<'_> {
+        ExpectationBuilder {
+            controller: self,
+            method,
+            path: path.to_string(),
+            times: 1,
+        }
+    }
+
+    /// Get the MockServer, which could be used as initialiser or extension
+    pub fn server(&self) -> MockServer {
+        MockServer::new(MockControllerResponder {
+            state: self.state.clone(),
+        })
+    }
+
+    /// Verify the calls
+    ///
+    /// Return all unmet expectations and unexpected calls as error.
+    pub fn verify(&self) -> Result<(), Vec<String>> {
+        let problems = self.lock().problems();
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(problems),
+        }
+    }
+
+    /// Lock the state, and ignore the poison, since it's only used in tests
+    fn lock(&self) -> MutexGuard<'_, Expectations> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Drop for MockController {
+    fn drop(&mut self) {
+        // Avoid double panics
+        if std::thread::panicking() {
+            return;
+        }
+        if let Err(problems) = self.verify() {
+            panic!("MockController is not satisfied:\n{}", problems.join("\n"));
+        }
+    }
+}
+
+/// This struct is used to build an expected call of MockController
+///
+/// The expectation is recorded by `respond` only, so it must be called.
+#[derive(Debug)]
+#[must_use = "the expectation is recorded only when `respond` is called"]
+pub struct ExpectationBuilder<'a> {
+    /// The owner
+    controller: &'a MockController,
+    /// HTTP method
+    method: Method,
+    /// The path of url
+    path: String,
+    /// The expected times
+    times: usize,
+}
+
+impl ExpectationBuilder<'_> {
+    /// Set the expected times, and `0` means it should never be called
+    /// - times: the expected times
+    pub fn times(self, times: usize) -> Self {
+        Self { times, ..self }
+    }
+
+    /// Set the response body, and record the expectation
+    /// - body: the response body
+    pub fn respond(self, body: ResponseBody) {
+        let expectation = Expectation {
+            method: self.method,
+            path: self.path,
+            times: self.times,
+            calls: 0,
+            body,
+        };
+        self.controller.lock().expected.push(expectation);
+    }
+}
+
+/// The Responder of MockController
+struct MockControllerResponder {
+    /// The shared state
+    state: Arc<Mutex<Expectations>>,
+}
+
+#[async_trait]
+impl Responder for MockControllerResponder {
+    fn type_name(&self) -> &str {
+        type_name::<MockController>()
+    }
+
+    async fn handle(&self, req: Request) -> anyhow::Result<ResponseBody> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let path = req.url().path();
+        let matched = state
+            .expected
+            .iter_mut()
+            .find(|e| e.method == req.method() && e.path == path && e.calls < e.times);
+        match matched {
+            Some(expectation) => {
+                expectation.calls += 1;
+                Ok(expectation.body.clone())
+            }
+            None => {
+                let call = format!("{} {}", req.method(), path);
+                state.unexpected.push(call.clone());
+                Err(anyhow::format_err!("Unexpected request: {}", call))
+            }
+        }
+    }
+}
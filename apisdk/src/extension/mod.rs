@@ -1,9 +1,29 @@
 mod auth;
+mod cache;
+mod checksum;
+mod date;
+mod headers;
+mod json;
+mod lenient;
 mod logger;
 mod mock;
+mod raw;
+mod refresh;
+mod signature;
+mod signer;
 mod trace;
 
 pub use auth::*;
+pub use cache::*;
+pub use checksum::*;
+pub use date::*;
+pub use headers::*;
+pub use json::*;
+pub use lenient::*;
 pub use logger::*;
 pub use mock::*;
+pub use raw::*;
+pub use refresh::*;
+pub use signature::*;
+pub use signer::*;
 pub use trace::*;
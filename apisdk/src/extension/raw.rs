@@ -0,0 +1,16 @@
+/// This extension will keep the raw bytes of response body, rather than decoding them
+///
+/// The response body will be returned as `ResponseBody::Raw`, so the exact bytes could be used to
+/// verify signature, and then be decoded by `ResponseBody::parse_json` or `ResponseBody::parse_xml`.
+/// `send!(req, Raw)` and `send!(req, RawJson)` apply it implicitly.
+///
+/// # Example
+///
+/// ```
+/// let req = client.get("/path").await?;
+/// let body = send!(req, Raw).await?;
+/// verify(body.raw_bytes().unwrap_or_default())?;
+/// let data: Data = body.parse_json()?;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetainRawBytes;
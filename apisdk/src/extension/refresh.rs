@@ -0,0 +1,163 @@
+use std::{
+    future::Future,
+    sync::{Arc, RwLock},
+};
+
+use async_trait::async_trait;
+use futures::{future::BoxFuture, lock::Mutex};
+use http::{Extensions, StatusCode};
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+
+use crate::{Carrier, WithCarrier};
+
+/// The closure to retrieve a new token
+type RefreshFn =
+    dyn Fn() -> BoxFuture<'static, Result<String, reqwest_middleware::Error>> + Send + Sync;
+
+/// This middleware is used to set token, and refresh it once when `401 Unauthorized` is returned
+///
+/// On `401 Unauthorized`, the `refresh` closure will be invoked to retrieve a new token,
+/// and the request will be retried once with it. Concurrent requests which fail with the same token
+/// share one refresh. Requests with streaming body could not be retried.
+///
+/// It sets the token by itself, so it should be used instead of `with_authenticator`.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_middleware(RefreshOn401Middleware::new(|| async { fetch_token().await }))
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct RefreshOn401Middleware {
+    /// The closure to retrieve a new token
+    refresh: Arc<RefreshFn>,
+    /// The current token, and its generation
+    state: Arc<RwLock<(Option<String>, u64)>>,
+    /// The lock to make sure only one refresh is in progress
+    refreshing: Arc<Mutex<()>>,
+    /// The carrier of token
+    carrier: Carrier,
+}
+
+impl std::fmt::Debug for RefreshOn401Middleware {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshOn401Middleware")
+            .field("carrier", &self.carrier)
+            .finish()
+    }
+}
+
+impl RefreshOn401Middleware {
+    /// Create a new instance
+    /// - refresh: the closure to retrieve a new token, which is also used to get the first token
+    pub fn new<F, Fut>(refresh: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, reqwest_middleware::Error>> + Send + 'static,
+    {
+        Self {
+            refresh: Arc::new(move || -> BoxFuture<'static, _> { Box::pin(refresh()) }),
+            state: Arc::new(RwLock::new((None, 0))),
+            refreshing: Arc::new(Mutex::new(())),
+            carrier: Carrier::default(),
+        }
+    }
+
+    /// Set the initial token
+    /// - token: the token
+    pub fn with_token(self, token: impl ToString) -> Self {
+        if let Ok(mut state) = self.state.write() {
+            state.0 = Some(token.to_string());
+        }
+        self
+    }
+
+    /// Get the current token
+    pub fn token(&self) -> Option<String> {
+        self.current().0
+    }
+
+    /// Get the current token, and its generation
+    fn current(&self) -> (Option<String>, u64) {
+        self.state
+            .read()
+            .map(|state| state.clone())
+            .unwrap_or_default()
+    }
+
+    /// Refresh the token, unless it has been refreshed since `generation`
+    /// - generation: the generation of the rejected token
+    async fn refresh_after(
+        &self,
+        generation: u64,
+    ) -> Result<(String, u64), reqwest_middleware::Error> {
+        let _guard = self.refreshing.lock().await;
+        if let (Some(token), current) = self.current() {
+            if current != generation {
+                return Ok((token, current));
+            }
+        }
+
+        let token = (self.refresh)().await?;
+        let mut state = self
+            .state
+            .write()
+            .map_err(|e| reqwest_middleware::Error::Middleware(anyhow::format_err!("{}", e)))?;
+        *state = (Some(token.clone()), state.1 + 1);
+        Ok((token, state.1))
+    }
+}
+
+impl WithCarrier for RefreshOn401Middleware {
+    fn with_carrier(self, carrier: Carrier) -> Self {
+        Self { carrier, ..self }
+    }
+
+    fn with_header_name(self, name: impl ToString) -> Self {
+        Self {
+            carrier: Carrier::Header(name.to_string()),
+            ..self
+        }
+    }
+
+    fn with_query_param(self, name: impl ToString) -> Self {
+        Self {
+            carrier: Carrier::QueryParam(name.to_string()),
+            ..self
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for RefreshOn401Middleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        let (token, generation) = match self.current() {
+            (Some(token), generation) => (token, generation),
+            (None, generation) => self.refresh_after(generation).await?,
+        };
+
+        let retry = req.try_clone();
+        let res = next
+            .clone()
+            .run(self.carrier.apply(req, token), extensions)
+            .await?;
+        if res.status() != StatusCode::UNAUTHORIZED {
+            return Ok(res);
+        }
+        let retry = match retry {
+            Some(retry) => retry,
+            None => return Ok(res),
+        };
+
+        let (token, _) = self.refresh_after(generation).await?;
+        next.run(self.carrier.apply(retry, token), extensions).await
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use reqwest::header::HeaderMap;
+use reqwest_middleware::{RequestBuilder, RequestInitialiser};
+
+use crate::{
+    digest::{constant_time_eq, hmac_sha256_raw},
+    ApiError, ApiResult,
+};
+
+/// This struct is used to verify the HMAC-SHA256 signature of response body
+///
+/// The signature is computed over the raw response body before decoding, and compared with
+/// the hex-encoded value of the given header. `ApiError::InvalidSignature` will be returned on mismatch.
+///
+/// Mocked responses will not be verified.
+///
+/// # Examples
+///
+/// ```
+/// // Verify the response of single request
+/// let req = client.get("/webhook/events").await?;
+/// let req = req.with_extension(SignatureVerifier::new("secret", "X-Signature"));
+/// let res: Data = send!(req, Json).await?;
+///
+/// // Verify the responses of all requests
+/// let client = XxxApi::builder()
+///     .with_initialiser(SignatureVerifier::new("secret", "X-Signature").with_prefix("sha256="))
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct SignatureVerifier {
+    /// The secret of HMAC
+    secret: Arc<Vec<u8>>,
+    /// The name of header
+    header: String,
+    /// The prefix of header value, e.g. `sha256=`
+    prefix: Option<String>,
+}
+
+impl std::fmt::Debug for SignatureVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignatureVerifier")
+            .field("header", &self.header)
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl SignatureVerifier {
+    /// Create a new instance
+    /// - secret: the secret of HMAC
+    /// - header: the name of header which carries the signature
+    pub fn new(secret: impl AsRef<[u8]>, header: impl ToString) -> Self {
+        Self {
+            secret: Arc::new(secret.as_ref().to_vec()),
+            header: header.to_string(),
+            prefix: None,
+        }
+    }
+
+    /// Set the prefix of header value, which will be stripped before comparing
+    /// - prefix: the prefix, e.g. `sha256=`
+    pub fn with_prefix(self, prefix: impl ToString) -> Self {
+        Self {
+            prefix: Some(prefix.to_string()),
+            ..self
+        }
+    }
+
+    /// Verify the signature
+    /// - headers: the headers of response
+    /// - body: the raw body of response
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> ApiResult<()> {
+        let value = headers
+            .get(self.header.as_str())
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                ApiError::InvalidSignature(format!("Missing signature header {}", self.header))
+            })?;
+        let value = match self.prefix.as_deref() {
+            Some(prefix) => value.strip_prefix(prefix).unwrap_or(value),
+            None => value,
+        };
+        let actual = hex::decode(value.trim()).map_err(|_| {
+            ApiError::InvalidSignature(format!("Malformed signature header {}", self.header))
+        })?;
+        let expected = hmac_sha256_raw(self.secret.as_slice(), body);
+        if constant_time_eq(expected, actual) {
+            Ok(())
+        } else {
+            Err(ApiError::InvalidSignature(format!(
+                "Signature mismatch of header {}",
+                self.header
+            )))
+        }
+    }
+}
+
+impl RequestInitialiser for SignatureVerifier {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        req.with_extension(self.clone())
+    }
+}
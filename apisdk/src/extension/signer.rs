@@ -0,0 +1,359 @@
+use std::{sync::Arc, time::SystemTime};
+
+use async_trait::async_trait;
+use http::Extensions;
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Request,
+};
+
+use crate::{digest, ApiAuthenticator, HashAlgorithm, TokenGenerator};
+
+/// This struct is used to sign request by HMAC-SHA256, in AWS SigV4-style
+///
+/// # Canonical request
+///
+/// ```text
+/// canonical = $METHOD + "\n"
+///           + $path + "\n"
+///           + $sorted_query + "\n"
+///           + $timestamp + "\n"
+///           + sha256($body)
+/// signature = hmac_sha256($secret, canonical)
+/// ```
+///
+/// - `sorted_query` is the raw query string, with pairs sorted by key and value
+/// - `timestamp` is UNIX_TIMESTAMP (in second)
+/// - `sha256` and `hmac_sha256` are hex-encoded, and the body is empty if absent
+///
+/// The signature and timestamp are set as `X-Signature` and `X-Timestamp` by default.
+/// Streaming bodies (e.g. multipart files) are not supported.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_authenticator(HmacSigner::new("secret").with_signature_header(HeaderName::from_static("x-api-signature")))
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct HmacSigner {
+    /// The secret of HMAC
+    secret: Arc<Vec<u8>>,
+    /// The name of signature header
+    signature_header: HeaderName,
+    /// The name of timestamp header
+    timestamp_header: HeaderName,
+}
+
+impl std::fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("signature_header", &self.signature_header)
+            .field("timestamp_header", &self.timestamp_header)
+            .finish()
+    }
+}
+
+impl HmacSigner {
+    /// Create a new instance
+    /// - secret: the secret of HMAC
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: Arc::new(secret.as_ref().to_vec()),
+            signature_header: HeaderName::from_static("x-signature"),
+            timestamp_header: HeaderName::from_static("x-timestamp"),
+        }
+    }
+
+    /// Set the name of signature header
+    /// - name: the name of header
+    pub fn with_signature_header(self, name: HeaderName) -> Self {
+        Self {
+            signature_header: name,
+            ..self
+        }
+    }
+
+    /// Set the name of timestamp header
+    /// - name: the name of header
+    pub fn with_timestamp_header(self, name: HeaderName) -> Self {
+        Self {
+            timestamp_header: name,
+            ..self
+        }
+    }
+
+    /// Build the canonical request
+    /// - req: HTTP request
+    /// - timestamp: UNIX_TIMESTAMP (in second)
+    pub fn canonical_request(
+        req: &Request,
+        timestamp: u64,
+    ) -> Result<String, reqwest_middleware::Error> {
+        let body_hash = match req.body() {
+            Some(body) => match body.as_bytes() {
+                Some(bytes) => digest::sha256(bytes),
+                None => {
+                    return Err(reqwest_middleware::Error::Middleware(anyhow::format_err!(
+                        "Unable to sign streaming body"
+                    )))
+                }
+            },
+            None => digest::sha256([]),
+        };
+
+        let mut pairs: Vec<&str> = req
+            .url()
+            .query()
+            .map(|q| q.split('&').filter(|p| !p.is_empty()).collect())
+            .unwrap_or_default();
+        pairs.sort_by(|a, b| {
+            let a = a.split_once('=').unwrap_or((a, ""));
+            let b = b.split_once('=').unwrap_or((b, ""));
+            a.cmp(&b)
+        });
+
+        Ok(format!(
+            "{}\n{}\n{}\n{}\n{}",
+            req.method().as_str(),
+            req.url().path(),
+            pairs.join("&"),
+            timestamp,
+            body_hash
+        ))
+    }
+
+    /// Sign the request at specified time
+    /// - req: HTTP request
+    /// - timestamp: UNIX_TIMESTAMP (in second)
+    pub fn sign(&self, req: &Request, timestamp: u64) -> Result<String, reqwest_middleware::Error> {
+        let canonical = Self::canonical_request(req, timestamp)?;
+        Ok(digest::hmac_sha256(self.secret.as_slice(), canonical))
+    }
+
+    /// Get current timestamp
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+#[async_trait]
+impl TokenGenerator for HmacSigner {
+    async fn generate_token(&self, req: &Request) -> Result<String, reqwest_middleware::Error> {
+        self.sign(req, Self::now())
+    }
+}
+
+#[async_trait]
+impl ApiAuthenticator for HmacSigner {
+    async fn authenticate(
+        &self,
+        req: Request,
+        _extensions: &Extensions,
+    ) -> Result<Request, reqwest_middleware::Error> {
+        let mut req = req;
+        let timestamp = Self::now();
+        let signature = self.sign(&req, timestamp)?;
+        req.headers_mut()
+            .insert(self.timestamp_header.clone(), HeaderValue::from(timestamp));
+        req.headers_mut().insert(
+            self.signature_header.clone(),
+            HeaderValue::from_str(&signature)
+                .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?,
+        );
+        Ok(req)
+    }
+}
+
+/// This struct is used to sign all query params, and append the signature as query param
+///
+/// It's common in payment APIs.
+///
+/// # Sign algorithm
+///
+/// ```text
+/// payload = $sorted_query + $body + $secret
+/// sign = hash($payload)
+/// ```
+///
+/// - `sorted_query` is `k1=v1&k2=v2`, with decoded pairs sorted by key and value
+/// - `body` is included only when `with_body` is enabled, and empty if absent
+/// - `hash` is md5 | sha1 | sha256, hex-encoded
+///
+/// The signature is appended as `sign` query param by default.
+/// Streaming bodies (e.g. multipart files) could not be signed.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_authenticator(QuerySigner::new("secret", HashAlgorithm::Md5).with_body(true))
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct QuerySigner {
+    /// The secret to sign
+    secret: String,
+    /// The algorithm of hash
+    algorithm: HashAlgorithm,
+    /// The name of signature param
+    sign_param: String,
+    /// Whether to include body in signature
+    include_body: bool,
+}
+
+impl std::fmt::Debug for QuerySigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuerySigner")
+            .field("algorithm", &self.algorithm)
+            .field("sign_param", &self.sign_param)
+            .field("include_body", &self.include_body)
+            .finish()
+    }
+}
+
+impl QuerySigner {
+    /// Create a new instance
+    /// - secret: the secret to sign
+    /// - algorithm: the algorithm of hash
+    pub fn new(secret: impl ToString, algorithm: HashAlgorithm) -> Self {
+        Self {
+            secret: secret.to_string(),
+            algorithm,
+            sign_param: "sign".to_string(),
+            include_body: false,
+        }
+    }
+
+    /// Set the name of signature param
+    /// - name: the name of query param
+    pub fn with_sign_param(self, name: impl ToString) -> Self {
+        Self {
+            sign_param: name.to_string(),
+            ..self
+        }
+    }
+
+    /// Set whether to include body in signature
+    /// - include_body: true to include body
+    pub fn with_body(self, include_body: bool) -> Self {
+        Self {
+            include_body,
+            ..self
+        }
+    }
+
+    /// Build the payload to sign
+    /// - req: HTTP request
+    ///
+    /// The existing signature param will be excluded.
+    pub fn payload(&self, req: &Request) -> Result<Vec<u8>, reqwest_middleware::Error> {
+        let mut pairs: Vec<(String, String)> = req
+            .url()
+            .query_pairs()
+            .filter(|(k, _)| k != self.sign_param.as_str())
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect();
+        pairs.sort();
+
+        let mut payload = pairs
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&")
+            .into_bytes();
+        if self.include_body {
+            if let Some(body) = req.body() {
+                match body.as_bytes() {
+                    Some(bytes) => payload.extend_from_slice(bytes),
+                    None => {
+                        return Err(reqwest_middleware::Error::Middleware(anyhow::format_err!(
+                            "Unable to sign streaming body"
+                        )))
+                    }
+                }
+            }
+        }
+        payload.extend_from_slice(self.secret.as_bytes());
+        Ok(payload)
+    }
+
+    /// Sign the request
+    /// - req: HTTP request
+    pub fn sign(&self, req: &Request) -> Result<String, reqwest_middleware::Error> {
+        Ok(self.algorithm.apply(self.payload(req)?))
+    }
+}
+
+#[async_trait]
+impl TokenGenerator for QuerySigner {
+    async fn generate_token(&self, req: &Request) -> Result<String, reqwest_middleware::Error> {
+        self.sign(req)
+    }
+}
+
+#[async_trait]
+impl ApiAuthenticator for QuerySigner {
+    async fn authenticate(
+        &self,
+        req: Request,
+        _extensions: &Extensions,
+    ) -> Result<Request, reqwest_middleware::Error> {
+        let mut req = req;
+        let sign = self.sign(&req)?;
+        req.url_mut()
+            .query_pairs_mut()
+            .append_pair(&self.sign_param, &sign);
+        Ok(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{Method, Request, Url};
+
+    use super::{HmacSigner, QuerySigner};
+    use crate::{digest, HashAlgorithm};
+
+    #[test]
+    fn test_canonical_request() {
+        let mut req = Request::new(
+            Method::POST,
+            Url::parse("http://localhost/v1/items?b=2&a=3&a=1").unwrap(),
+        );
+        *req.body_mut() = Some("{}".into());
+
+        let canonical = HmacSigner::canonical_request(&req, 1700000000).unwrap();
+        assert_eq!(
+            format!(
+                "POST\n/v1/items\na=1&a=3&b=2\n1700000000\n{}",
+                digest::sha256("{}")
+            ),
+            canonical
+        );
+        assert_eq!(
+            digest::hmac_sha256("secret", &canonical),
+            HmacSigner::new("secret").sign(&req, 1700000000).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_query_signer_payload() {
+        let mut req = Request::new(
+            Method::POST,
+            Url::parse("http://localhost/pay?b=2&sign=old&a=x%20y").unwrap(),
+        );
+        *req.body_mut() = Some("{}".into());
+
+        let signer = QuerySigner::new("secret", HashAlgorithm::Md5);
+        assert_eq!(b"a=x y&b=2secret".to_vec(), signer.payload(&req).unwrap());
+        assert_eq!(digest::md5("a=x y&b=2secret"), signer.sign(&req).unwrap());
+
+        let signer = signer.with_body(true);
+        assert_eq!(b"a=x y&b=2{}secret".to_vec(), signer.payload(&req).unwrap());
+    }
+}
@@ -1,7 +1,22 @@
+use std::collections::HashMap;
+#[cfg(feature = "tracing")]
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use http::Extensions;
-use reqwest::{header::HeaderValue, Request, Response};
-use reqwest_middleware::{Middleware, Next, RequestBuilder};
+use rand::RngCore;
+#[cfg(feature = "tracing")]
+use reqwest::header::{HeaderMap, AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, SET_COOKIE};
+use reqwest::{
+    header::{HeaderName, HeaderValue},
+    Request, Response,
+};
+use reqwest_middleware::{Middleware, Next, RequestBuilder, RequestInitialiser};
+
+use crate::digest;
+
+/// The name of W3C `traceparent` header
+const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
 
 /// Generate a new id for `X-Request-ID` or `X-Trace-ID`
 #[cfg(not(feature = "uuid"))]
@@ -15,6 +30,49 @@ fn generate_id() -> String {
     uuid::Uuid::new_v4().to_string()
 }
 
+/// Generate a random W3C-compatible id of `N` bytes, which is not all zeros
+fn generate_w3c_id<const N: usize>() -> String {
+    loop {
+        let mut bytes = [0u8; N];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        if bytes.iter().any(|b| *b != 0) {
+            return hex::encode(bytes);
+        }
+    }
+}
+
+/// Check whether the id is `len` lowercase hex chars, and not all zeros
+fn is_w3c_id(id: &str, len: usize) -> bool {
+    id.len() == len
+        && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+        && id.bytes().any(|b| b != b'0')
+}
+
+/// Parse the value of W3C `traceparent` header, e.g. `00-{trace_id}-{span_id}-01`
+///
+/// Return `None` if the value is invalid.
+fn parse_traceparent(value: &str) -> Option<(String, String)> {
+    let parts: Vec<_> = value.trim().split('-').collect();
+    match parts.as_slice() {
+        [_, trace_id, span_id, _] if is_w3c_id(trace_id, 32) && is_w3c_id(span_id, 16) => {
+            Some((trace_id.to_string(), span_id.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Convert the id to `len` lowercase hex chars
+///
+/// UUID-like ids will be kept after removing dashes, and others will be hashed by SHA256.
+fn to_w3c_id(id: &str, len: usize) -> String {
+    let normalized = id.replace('-', "").to_ascii_lowercase();
+    if is_w3c_id(&normalized, len) {
+        normalized
+    } else {
+        digest::sha256(id)[..len].to_string()
+    }
+}
+
 /// This extension will set the `X-Request-ID` header
 ///
 /// # Example
@@ -76,6 +134,247 @@ impl TraceId {
             span_id: span_id.map(|id| id.to_string()),
         }
     }
+
+    /// Create a new TraceId with random W3C-compatible ids
+    pub fn w3c() -> Self {
+        Self {
+            trace_id: generate_w3c_id::<16>(),
+            span_id: Some(generate_w3c_id::<8>()),
+        }
+    }
+
+    /// Create a new TraceId with W3C-compatible ids
+    /// - trace_id: 32 hex chars, and not all zeros
+    /// - span_id: 16 hex chars, and not all zeros; a random one will be generated if absent
+    ///
+    /// Return `None` if any id is invalid.
+    pub fn try_w3c(trace_id: impl ToString, span_id: Option<impl ToString>) -> Option<Self> {
+        let trace_id = trace_id.to_string().to_ascii_lowercase();
+        let span_id = match span_id {
+            Some(id) => id.to_string().to_ascii_lowercase(),
+            None => generate_w3c_id::<8>(),
+        };
+        (is_w3c_id(&trace_id, 32) && is_w3c_id(&span_id, 16)).then_some(Self {
+            trace_id,
+            span_id: Some(span_id),
+        })
+    }
+
+    /// Build the value of W3C `traceparent` header, e.g. `00-{trace_id}-{span_id}-01`
+    ///
+    /// Incompatible ids will be converted to hex, and a random span id will be generated if absent.
+    pub fn traceparent(&self) -> String {
+        let span_id = match self.span_id.as_ref() {
+            Some(id) => to_w3c_id(id, 16),
+            None => generate_w3c_id::<8>(),
+        };
+        format!("00-{}-{}-01", to_w3c_id(&self.trace_id, 32), span_id)
+    }
+}
+
+/// This extension decides which headers will be used to propagate RequestId and TraceId
+///
+/// # Example
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_initialiser(TracePropagation::Both)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TracePropagation {
+    /// Set `X-Request-ID`, `X-Trace-ID` and `X-Span-ID` headers, which could be renamed by `TraceHeaderNames`
+    #[default]
+    Custom,
+    /// Set W3C `traceparent` header only
+    W3c,
+    /// Set both of them
+    Both,
+}
+
+impl RequestInitialiser for TracePropagation {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<TracePropagation>() {
+            Some(_) => req,
+            None => req.with_extension(*self),
+        }
+    }
+}
+
+/// The headers which will never be recorded into span, since they carry credentials
+#[cfg(feature = "tracing")]
+const SENSITIVE_HEADERS: [HeaderName; 4] = [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE, SET_COOKIE];
+
+/// This extension holds the allowlist of request headers, which will be recorded as `req.headers` field of span
+///
+/// Sensitive headers (`Authorization`, `Proxy-Authorization`, `Cookie` and `Set-Cookie`) are always skipped.
+///
+/// # Example
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_initialiser(SpanHeaders::new(["x-correlation-id"]))
+///     .build();
+/// ```
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone)]
+pub struct SpanHeaders {
+    /// The names of headers
+    names: Arc<Vec<HeaderName>>,
+}
+
+#[cfg(feature = "tracing")]
+impl SpanHeaders {
+    /// Create a new SpanHeaders
+    /// - names: the names of headers, and the invalid ones will be ignored
+    pub fn new<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            names: Arc::new(
+                names
+                    .into_iter()
+                    .filter_map(|name| HeaderName::from_bytes(name.as_ref().as_bytes()).ok())
+                    .filter(|name| !SENSITIVE_HEADERS.contains(name))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Format allowlisted headers, e.g. `x-correlation-id=abc, x-tenant=t1`
+    /// - headers: HTTP headers
+    ///
+    /// Return `None` if there is no allowlisted header.
+    pub(crate) fn format(&self, headers: &HeaderMap) -> Option<String> {
+        let pairs: Vec<_> = self
+            .names
+            .iter()
+            .flat_map(|name| {
+                headers
+                    .get_all(name)
+                    .iter()
+                    .filter_map(move |v| v.to_str().ok().map(|v| format!("{}={}", name, v)))
+            })
+            .collect();
+        (!pairs.is_empty()).then(|| pairs.join(", "))
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl RequestInitialiser for SpanHeaders {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<SpanHeaders>() {
+            Some(_) => req,
+            None => req.with_extension(self.clone()),
+        }
+    }
+}
+
+/// This struct is used to record allowlisted headers into the span of API call
+///
+/// It runs after `DefaultHeadersMiddleware` and `AuthenticateMiddleware`, so the final headers are recorded.
+#[cfg(feature = "tracing")]
+pub(crate) struct SpanHeadersMiddleware;
+
+#[cfg(feature = "tracing")]
+#[async_trait]
+impl Middleware for SpanHeadersMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        if let Some(span_headers) = extensions.get::<SpanHeaders>() {
+            if let Some(value) = span_headers.format(req.headers()) {
+                tracing::Span::current().record("req.headers", value);
+            }
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// This extension holds the names of headers, which carry request id, trace id and span id
+///
+/// The defaults are `X-Request-ID`, `X-Trace-ID` and `X-Span-ID`.
+///
+/// # Example
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_trace_headers(TraceHeaderNames {
+///         request_id: HeaderName::from_static("x-correlation-id"),
+///         ..Default::default()
+///     })
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct TraceHeaderNames {
+    /// The name of request id header
+    pub request_id: HeaderName,
+    /// The name of trace id header
+    pub trace_id: HeaderName,
+    /// The name of span id header
+    pub span_id: HeaderName,
+}
+
+impl Default for TraceHeaderNames {
+    fn default() -> Self {
+        Self {
+            request_id: HeaderName::from_static("x-request-id"),
+            trace_id: HeaderName::from_static("x-trace-id"),
+            span_id: HeaderName::from_static("x-span-id"),
+        }
+    }
+}
+
+impl TraceHeaderNames {
+    /// Expose the configured headers of response by the default names as well
+    /// - headers: HTTP headers, with lowercase names
+    ///
+    /// So `CodeDataMessage::get_request_id` and others follow the names in use.
+    /// The trace id and span id are taken from W3C `traceparent` header if still absent.
+    pub(crate) fn alias_defaults(&self, headers: &mut HashMap<String, String>) {
+        let defaults = Self::default();
+        for (name, default) in [
+            (&self.request_id, &defaults.request_id),
+            (&self.trace_id, &defaults.trace_id),
+            (&self.span_id, &defaults.span_id),
+        ] {
+            if name == default || headers.contains_key(default.as_str()) {
+                continue;
+            }
+            if let Some(value) = headers.get(name.as_str()).cloned() {
+                headers.insert(default.to_string(), value);
+            }
+        }
+
+        if let Some((trace_id, span_id)) = headers
+            .get(TRACEPARENT.as_str())
+            .and_then(|v| parse_traceparent(v))
+        {
+            headers
+                .entry(defaults.trace_id.to_string())
+                .or_insert(trace_id);
+            headers
+                .entry(defaults.span_id.to_string())
+                .or_insert(span_id);
+        }
+    }
+}
+
+impl RequestInitialiser for TraceHeaderNames {
+    fn init(&self, req: RequestBuilder) -> RequestBuilder {
+        let mut req = req;
+        match req.extensions().get::<TraceHeaderNames>() {
+            Some(_) => req,
+            None => req.with_extension(self.clone()),
+        }
+    }
 }
 
 /// This struct is used to inject RequestId and/or TraceId to request
@@ -93,7 +392,7 @@ impl RequestTraceIdMiddleware {
                 .map(|id| id.request_id.clone()),
             req.extensions()
                 .get::<TraceId>()
-                .map(|id| (Some(id.trace_id.clone())))
+                .map(|id| Some(id.trace_id.clone()))
                 .unwrap_or(None),
         );
 
@@ -112,27 +411,49 @@ impl RequestTraceIdMiddleware {
     /// This function will be invoked at the end of send()
     pub(crate) fn inject_header(req: Request, extensions: &Extensions) -> Request {
         let mut req = req;
+        let propagation = extensions
+            .get::<TracePropagation>()
+            .copied()
+            .unwrap_or_default();
+        let names = extensions
+            .get::<TraceHeaderNames>()
+            .cloned()
+            .unwrap_or_default();
         let headers = req.headers_mut();
 
-        // X-Request-ID
-        if !headers.contains_key("X-Request-ID") {
-            let request_id = extensions
-                .get::<RequestId>()
-                .map(|id| id.request_id.clone())
-                .unwrap_or_else(generate_id);
-            headers.insert("X-Request-ID", HeaderValue::from_str(&request_id).unwrap());
+        if propagation != TracePropagation::W3c {
+            // X-Request-ID
+            if !headers.contains_key(&names.request_id) {
+                let request_id = extensions
+                    .get::<RequestId>()
+                    .map(|id| id.request_id.clone())
+                    .unwrap_or_else(generate_id);
+                headers.insert(
+                    names.request_id,
+                    HeaderValue::from_str(&request_id).unwrap(),
+                );
+            }
+
+            // X-Trace-ID & X-Span-ID
+            if !headers.contains_key(&names.trace_id) {
+                let (trace_id, span_id) = match extensions.get::<TraceId>() {
+                    Some(id) => (id.trace_id.clone(), id.span_id.clone()),
+                    None => (generate_id(), None),
+                };
+                headers.insert(names.trace_id, HeaderValue::from_str(&trace_id).unwrap());
+                if let Some(span_id) = span_id {
+                    headers.insert(names.span_id, HeaderValue::from_str(&span_id).unwrap());
+                }
+            }
         }
 
-        // X-Trace-ID & X-Span-ID
-        if !headers.contains_key("X-Trace-ID") {
-            let (trace_id, span_id) = match extensions.get::<TraceId>() {
-                Some(id) => (id.trace_id.clone(), id.span_id.clone()),
-                None => (generate_id(), None),
+        // traceparent
+        if propagation != TracePropagation::Custom && !headers.contains_key(TRACEPARENT) {
+            let traceparent = match extensions.get::<TraceId>() {
+                Some(id) => id.traceparent(),
+                None => TraceId::w3c().traceparent(),
             };
-            headers.insert("X-Trace-ID", HeaderValue::from_str(&trace_id).unwrap());
-            if let Some(span_id) = span_id {
-                headers.insert("X-Span-ID", HeaderValue::from_str(&span_id).unwrap());
-            }
+            headers.insert(TRACEPARENT, HeaderValue::from_str(&traceparent).unwrap());
         }
 
         req
@@ -149,6 +470,25 @@ impl Middleware for RequestTraceIdMiddleware {
         next: Next<'_>,
     ) -> Result<Response, reqwest_middleware::Error> {
         let req = Self::inject_header(req, extensions);
-        next.run(req, extensions).await
+
+        // The span of API call is created before the request is built,
+        // so method and host are recorded here, when the final url is known
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            span.record("http.method", req.method().as_str());
+            if let Some(host) = req.url().host_str() {
+                span.record("http.host", host);
+            }
+        }
+
+        let res = next.run(req, extensions).await;
+        #[cfg(feature = "tracing")]
+        if let Ok(res) = res.as_ref() {
+            let span = tracing::Span::current();
+            span.record("http.status_code", res.status().as_u16());
+            span.record("http.version", format!("{:?}", res.version()));
+        }
+        res
     }
 }
@@ -0,0 +1,68 @@
+use reqwest::{
+    header::{HeaderMap, ACCESS_CONTROL_ALLOW_METHODS, ALLOW},
+    Method,
+};
+
+/// This struct is used to parse allowed methods from `Allow` and `Access-Control-Allow-Methods` headers
+///
+/// It's used by `send!(req, Allow)`, which is usually applied to `OPTIONS` request.
+/// It's always empty for the response mocked by `MockServer`, which has no header.
+///
+/// # Examples
+///
+/// ```
+/// let req = client.options("/path").await?;
+/// let methods: Vec<Method> = send!(req, Allow).await?;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Allow;
+
+impl Allow {
+    /// Parse allowed methods, in the order of appearance without duplicates
+    /// - headers: HTTP headers
+    ///
+    /// The invalid methods and `*` wildcard will be ignored.
+    pub fn parse(headers: &HeaderMap) -> Vec<Method> {
+        let mut methods: Vec<Method> = vec![];
+        let values = headers
+            .get_all(ALLOW)
+            .iter()
+            .chain(headers.get_all(ACCESS_CONTROL_ALLOW_METHODS).iter())
+            .filter_map(|v| v.to_str().ok());
+        for value in values {
+            for method in value.split(',').map(str::trim).filter(|m| *m != "*") {
+                if let Ok(method) = Method::from_bytes(method.to_uppercase().as_bytes()) {
+                    if !methods.contains(&method) {
+                        methods.push(method);
+                    }
+                }
+            }
+        }
+        methods
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{
+        header::{HeaderMap, HeaderValue, ACCESS_CONTROL_ALLOW_METHODS, ALLOW},
+        Method,
+    };
+
+    use super::Allow;
+
+    #[test]
+    fn test_parse_allow() {
+        let mut headers = HeaderMap::new();
+        headers.append(ALLOW, HeaderValue::from_static("GET, head,OPTIONS"));
+        headers.append(
+            ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_static("POST, GET, *, "),
+        );
+        assert_eq!(
+            vec![Method::GET, Method::HEAD, Method::OPTIONS, Method::POST],
+            Allow::parse(&headers)
+        );
+        assert!(Allow::parse(&HeaderMap::new()).is_empty());
+    }
+}
@@ -1,9 +1,8 @@
 use serde::de::DeserializeOwned;
-use serde_json::Value;
 
 use crate::{ApiError, ApiResult, Json, ResponseBody, Xml};
 
-use super::MimeType;
+use super::parse_empty;
 
 /// This struct is used to parse response body to json or xml
 #[derive(Debug)]
@@ -16,17 +15,59 @@ impl Auto {
         T: 'static + DeserializeOwned,
     {
         match &body {
-            ResponseBody::Empty => serde_json::from_value(Value::Null).map_err(|_| {
-                ApiError::DecodeResponse(
-                    MimeType::Empty,
-                    "Failed to decode empty response to result type.".to_string(),
-                )
-            }),
+            ResponseBody::Empty => parse_empty(),
             ResponseBody::Json(_) => Json::try_parse(body),
             ResponseBody::Xml(_) => Xml::try_parse(body),
-            ResponseBody::Text(_) => {
+            ResponseBody::Html(_) | ResponseBody::Text(_) | ResponseBody::Raw(_) => {
                 Json::try_parse(body.clone()).or_else(|_| Xml::try_parse(body))
             }
         }
     }
 }
+
+/// This enum is used to parse response body by content-type, and keep the format as variant
+///
+/// # Examples
+///
+/// ```
+/// // JSON on success, and XML on error
+/// let req = client.get("/api/path").await?;
+/// let res: JsonOrXml<Data, XmlError> = send!(req, Body).await?;
+/// let data = res.into_result()?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonOrXml<J, X> {
+    /// The response is JSON
+    Json(J),
+    /// The response is XML
+    Xml(X),
+}
+
+impl<J, X> JsonOrXml<J, X> {
+    /// Treat JSON as result, and convert XML to error
+    pub fn into_result(self) -> ApiResult<J>
+    where
+        X: Into<ApiError>,
+    {
+        match self {
+            Self::Json(json) => Ok(json),
+            Self::Xml(xml) => Err(xml.into()),
+        }
+    }
+}
+
+impl<J, X> TryFrom<ResponseBody> for JsonOrXml<J, X>
+where
+    J: 'static + DeserializeOwned,
+    X: 'static + DeserializeOwned,
+{
+    type Error = ApiError;
+
+    fn try_from(body: ResponseBody) -> Result<Self, Self::Error> {
+        match body {
+            ResponseBody::Json(_) => Json::try_parse(body).map(Self::Json),
+            ResponseBody::Xml(_) => Xml::try_parse(body).map(Self::Xml),
+            _ => Err(ApiError::UnsupportedContentType(body.mime_type())),
+        }
+    }
+}
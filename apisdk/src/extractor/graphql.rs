@@ -0,0 +1,161 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ApiError, ApiResult, JsonExtractor, MimeType, ResponseBody};
+
+/// This struct represents the payload of GraphQL request
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQLQuery {
+    /// The query document
+    pub query: String,
+    /// The variables of query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Value>,
+    /// The name of operation to execute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+}
+
+impl GraphQLQuery {
+    /// Create a new instance
+    /// - query: the query document
+    pub fn new(query: impl ToString) -> Self {
+        Self {
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Set variables
+    /// - variables: any serializable value, `null` will be omitted
+    pub fn with_variables<V>(self, variables: &V) -> ApiResult<Self>
+    where
+        V: Serialize + ?Sized,
+    {
+        let variables =
+            serde_json::to_value(variables).map_err(|e| ApiError::SerializeRequest {
+                mime: MimeType::Json,
+                detail: e.to_string(),
+            })?;
+        Ok(Self {
+            variables: (!variables.is_null()).then_some(variables),
+            ..self
+        })
+    }
+
+    /// Set operation name
+    /// - operation_name: the name of operation to execute
+    pub fn with_operation_name(self, operation_name: impl ToString) -> Self {
+        Self {
+            operation_name: Some(operation_name.to_string()),
+            ..self
+        }
+    }
+}
+
+impl From<&str> for GraphQLQuery {
+    fn from(query: &str) -> Self {
+        Self::new(query)
+    }
+}
+
+impl From<String> for GraphQLQuery {
+    fn from(query: String) -> Self {
+        Self::new(query)
+    }
+}
+
+impl From<&String> for GraphQLQuery {
+    fn from(query: &String) -> Self {
+        Self::new(query)
+    }
+}
+
+/// This struct represents an error of GraphQL response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphQLError {
+    /// The description of error
+    pub message: String,
+    /// The locations in query document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locations: Option<Vec<GraphQLLocation>>,
+    /// The path of response field which experienced the error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<Value>>,
+    /// The extra information provided by server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Value>,
+}
+
+/// This struct represents a location in GraphQL query document
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct GraphQLLocation {
+    /// Line number, starts from 1
+    pub line: u32,
+    /// Column number, starts from 1
+    pub column: u32,
+}
+
+/// This struct is used to parse `{data, errors}` GraphQL response, and return `data` field
+#[derive(Debug, Deserialize)]
+pub struct GraphQLResponse {
+    /// The result of execution
+    #[serde(default)]
+    pub data: Option<Value>,
+    /// The errors of execution
+    #[serde(default)]
+    pub errors: Vec<GraphQLError>,
+    /// The extra information provided by server
+    #[serde(default)]
+    pub extensions: Option<Value>,
+}
+
+impl TryFrom<ResponseBody> for GraphQLResponse {
+    type Error = ApiError;
+
+    fn try_from(body: ResponseBody) -> Result<Self, Self::Error> {
+        body.parse_json()
+    }
+}
+
+impl JsonExtractor for GraphQLResponse {
+    fn try_extract<T>(self) -> ApiResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        if !self.errors.is_empty() {
+            return Err(ApiError::GraphQL(self.errors));
+        }
+        serde_json::from_value(self.data.unwrap_or_default()).map_err(ApiError::DecodeJson)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::GraphQLQuery;
+
+    #[test]
+    fn test_serialize_query() {
+        let query = GraphQLQuery::new("query { me { id } }");
+        assert_eq!(
+            json!({ "query": "query { me { id } }" }),
+            serde_json::to_value(query).unwrap()
+        );
+
+        let query = GraphQLQuery::from("query Q($id: ID!) { user(id: $id) { name } }")
+            .with_variables(&json!({ "id": 1 }))
+            .unwrap()
+            .with_operation_name("Q");
+        assert_eq!(
+            json!({
+                "query": "query Q($id: ID!) { user(id: $id) { name } }",
+                "variables": { "id": 1 },
+                "operationName": "Q",
+            }),
+            serde_json::to_value(query).unwrap()
+        );
+    }
+}
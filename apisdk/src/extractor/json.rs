@@ -1,40 +1,61 @@
-use std::{any::TypeId, collections::HashMap};
+use std::{
+    any::{type_name, TypeId},
+    collections::HashMap,
+};
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{value::RawValue, Value};
 
-use crate::{ApiError, ApiResult, MimeType};
+use crate::{ApiError, ApiResult, MimeType, RequestBuilder, RetainRawBytes, TraceHeaderNames};
 
-use super::ResponseBody;
+use super::{parse_empty, unwrap_envelope, ResponseBody};
 
 /// This struct is used to parse response body to json
 #[derive(Debug)]
 pub struct Json;
 
 impl Json {
+    /// Keep raw bytes of response, if the parser targets `Box<RawValue>`, so the payload is kept verbatim
+    /// - req: RequestBuilder
+    /// - _parser: the parser, which decides the target type, e.g. `Json::try_parse`
+    #[doc(hidden)]
+    pub fn retain_raw_for<T, F>(req: RequestBuilder, _parser: &F) -> RequestBuilder
+    where
+        T: 'static,
+        F: Fn(ResponseBody) -> ApiResult<T>,
+    {
+        if TypeId::of::<T>() == TypeId::of::<Box<RawValue>>() {
+            req.with_extension(RetainRawBytes)
+        } else {
+            req
+        }
+    }
+
     pub(crate) fn do_try_parse<T>(text: String) -> ApiResult<T>
     where
         T: 'static + DeserializeOwned,
     {
         let type_id = TypeId::of::<T>();
         if type_id == TypeId::of::<()>() {
-            serde_json::from_value(Value::Null).map_err(|_| ApiError::Impossible)
+            serde_json::from_value(Value::Null).map_err(ApiError::DecodeJson)
         } else if type_id == TypeId::of::<String>() {
             let value = serde_json::Value::String(text);
-            serde_json::from_value(value).map_err(|_| ApiError::Impossible)
+            serde_json::from_value(value).map_err(ApiError::DecodeJson)
         } else {
             serde_json::from_str(&text).map_err(ApiError::DecodeJson)
         }
     }
 
     /// Try to parse response
+    ///
+    /// The `Empty` response will be treated as `null`, so it could be decoded as `Option<T>` or `()`.
     pub fn try_parse<T>(body: ResponseBody) -> ApiResult<T>
     where
         T: 'static + DeserializeOwned,
     {
         let type_id = TypeId::of::<T>();
         if type_id == TypeId::of::<()>() {
-            return serde_json::from_value(Value::Null).map_err(|_| ApiError::Impossible);
+            return serde_json::from_value(Value::Null).map_err(ApiError::DecodeJson);
         }
 
         match body {
@@ -46,10 +67,12 @@ impl Json {
                     serde_json::from_value(json).map_err(ApiError::DecodeJson)
                 }
             }
-            ResponseBody::Text(text) => {
+            ResponseBody::Html(text) | ResponseBody::Text(text) => {
                 log::debug!("Treat text as json for decoding");
                 Self::do_try_parse(text)
             }
+            ResponseBody::Raw(bytes) => Self::do_try_parse(ResponseBody::raw_text(&bytes)?),
+            ResponseBody::Empty => parse_empty(),
             _ => Err(ApiError::IncompatibleContentType(
                 MimeType::Json,
                 body.mime_type(),
@@ -111,6 +134,8 @@ impl Json {
 ///     - an alias of serde_json::Value
 /// - apisdk::CodeDataMessage
 ///     - parse `{code, data, message}` json payload, and return `data` field
+/// - apisdk::Either
+///     - parse json payload as one of two types
 pub trait JsonExtractor {
     /// The extractor needs response HTTP headers or not.
     fn require_headers() -> bool {
@@ -119,7 +144,8 @@ pub trait JsonExtractor {
 
     /// Try to extract result from response.
     ///
-    /// The HTTP headers will be inject as `__headers__` field if possible.
+    /// If `require_headers` returns `true`, the HTTP headers will be injected as `__headers__` field.
+    /// Non-object payload (e.g. array or scalar) will be wrapped as `{"__body__": payload, "__headers__": {...}}`.
     fn try_extract<T>(self) -> ApiResult<T>
     where
         T: DeserializeOwned;
@@ -138,7 +164,7 @@ impl JsonExtractor for Value {
     where
         T: DeserializeOwned,
     {
-        serde_json::from_value(self).map_err(|_| ApiError::IllegalJson(Value::Null))
+        serde_json::from_value(self).map_err(ApiError::DecodeJson)
     }
 }
 
@@ -150,17 +176,19 @@ impl TryFrom<ResponseBody> for String {
             ResponseBody::Empty => Ok("".to_string()),
             ResponseBody::Json(json) => {
                 // Remove __headers__
-                let json = match json {
+                let json = match unwrap_envelope(json) {
                     Value::Object(mut map) => {
                         map.remove("__headers__");
                         Value::Object(map)
                     }
-                    _ => json,
+                    json => json,
                 };
                 Ok(json.to_string())
             }
             ResponseBody::Xml(xml) => Ok(xml),
+            ResponseBody::Html(html) => Ok(html),
             ResponseBody::Text(text) => Ok(text),
+            ResponseBody::Raw(bytes) => ResponseBody::raw_text(&bytes),
         }
     }
 }
@@ -170,7 +198,7 @@ impl JsonExtractor for String {
     where
         T: DeserializeOwned,
     {
-        serde_json::from_value(Value::String(self)).map_err(|_| ApiError::IllegalJson(Value::Null))
+        serde_json::from_value(Value::String(self)).map_err(ApiError::DecodeJson)
     }
 }
 
@@ -236,9 +264,12 @@ impl<T> CodeDataMessage<T> {
     }
 
     /// Get any header
-    /// - name: header name
+    /// - name: header name, case-insensitive
     pub fn get_header(&self, name: &str) -> Option<&str> {
-        self.headers.get(name).map(|v| v.as_str())
+        self.headers
+            .get(name)
+            .or_else(|| self.headers.get(&name.to_ascii_lowercase()))
+            .map(|v| v.as_str())
     }
 
     /// Get any unknown field
@@ -252,19 +283,23 @@ impl<T> CodeDataMessage<T> {
             .and_then(|v| serde_json::from_value(v.clone()).ok())
     }
 
-    /// Get `X-Request-ID` header
+    /// Get request id header, which is `X-Request-ID` or renamed by `TraceHeaderNames`
     pub fn get_request_id(&self) -> Option<&str> {
-        self.get_header("X-Request-ID")
+        self.get_header(TraceHeaderNames::default().request_id.as_str())
     }
 
-    /// Get `X-Trace-ID` header
+    /// Get trace id header, which is `X-Trace-ID` or renamed by `TraceHeaderNames`
+    ///
+    /// W3C `traceparent` header is used if absent.
     pub fn get_trace_id(&self) -> Option<&str> {
-        self.get_header("X-Trace-ID")
+        self.get_header(TraceHeaderNames::default().trace_id.as_str())
     }
 
-    /// Get `X-Span-ID` header
+    /// Get span id header, which is `X-Span-ID` or renamed by `TraceHeaderNames`
+    ///
+    /// W3C `traceparent` header is used if absent.
     pub fn get_span_id(&self) -> Option<&str> {
-        self.get_header("X-Span-ID")
+        self.get_header(TraceHeaderNames::default().span_id.as_str())
     }
 }
 
@@ -285,11 +320,22 @@ impl JsonExtractor for CodeDataMessage {
             0 => {
                 // Extract `data` field when `code` is 0
                 match self.data {
-                    Some(data) => {
-                        serde_json::from_value(data).map_err(|_| ApiError::IllegalJson(Value::Null))
+                    // Treat `{}` as null if it doesn't fit, e.g. `Option<T>`
+                    Some(Value::Object(map)) if map.is_empty() => {
+                        serde_json::from_value(Value::Object(map)).or_else(|e| {
+                            serde_json::from_value(Value::Null).map_err(|_| ApiError::DecodeJson(e))
+                        })
                     }
-                    None => serde_json::from_value(Value::Null)
-                        .map_err(|_| ApiError::IllegalJson(Value::Null)),
+                    Some(data) => serde_json::from_value(data).map_err(ApiError::DecodeJson),
+                    None => serde_json::from_value(Value::Null).map_err(|_| {
+                        ApiError::DecodeResponse(
+                            MimeType::Json,
+                            format!(
+                                "`data` field is missing or null, which could not be decoded as {}",
+                                type_name::<T>()
+                            ),
+                        )
+                    }),
                 }
             }
             code => {
@@ -300,6 +346,129 @@ impl JsonExtractor for CodeDataMessage {
     }
 }
 
+/// This struct is used to parse `{code, data, message}` payload, and keep `message` along with `data`.
+///
+/// When it's used as `JsonExtractor`, a non-zero `code` is treated as failure only when `data` is missing or null.
+/// So `{"code": 1, "data": {...}, "message": "partially saved"}` will be returned as it is.
+///
+/// # Examples
+///
+/// ```
+/// async fn save_items(&self) -> ApiResult<WithMessage<Summary>> {
+///     let req = client.post("/api/path").await?;
+///     send!(req, WithMessage).await
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WithMessage<T = Option<Value>> {
+    /// `code` field
+    #[serde(default)]
+    pub code: i64,
+    /// `data` field
+    pub data: T,
+    /// `message` or `msg` field
+    #[serde(alias = "msg")]
+    pub message: Option<String>,
+}
+
+impl TryFrom<ResponseBody> for WithMessage {
+    type Error = ApiError;
+
+    fn try_from(body: ResponseBody) -> Result<Self, Self::Error> {
+        body.parse_json()
+    }
+}
+
+impl JsonExtractor for WithMessage {
+    fn try_extract<T>(self) -> ApiResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        if self.code != 0 && self.data.is_none() {
+            return Err(ApiError::ServiceError(self.code, self.message));
+        }
+        serde_json::to_value(self)
+            .and_then(serde_json::from_value)
+            .map_err(ApiError::DecodeJson)
+    }
+}
+
+/// This enum is used to parse payload which could be one of two shapes.
+///
+/// When it's used as `JsonExtractor`, it will try to deserialize payload as `L` first, then `R`.
+/// If neither matches, an `ApiError::DecodeJson` will be returned.
+///
+/// # Examples
+///
+/// ```
+/// async fn get_item(&self) -> ApiResult<Either<Item, ItemList>> {
+///     let req = client.get("/api/path").await?;
+///     send!(req, Either<Item, ItemList>).await
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Either<L, R> {
+    /// Payload matches `L`
+    Left(L),
+    /// Payload matches `R`
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Check whether it's `Left`
+    pub fn is_left(&self) -> bool {
+        matches!(self, Self::Left(_))
+    }
+
+    /// Check whether it's `Right`
+    pub fn is_right(&self) -> bool {
+        matches!(self, Self::Right(_))
+    }
+
+    /// Get `Left` value
+    pub fn left(self) -> Option<L> {
+        match self {
+            Self::Left(l) => Some(l),
+            Self::Right(_) => None,
+        }
+    }
+
+    /// Get `Right` value
+    pub fn right(self) -> Option<R> {
+        match self {
+            Self::Left(_) => None,
+            Self::Right(r) => Some(r),
+        }
+    }
+}
+
+impl<L, R> TryFrom<ResponseBody> for Either<L, R>
+where
+    L: DeserializeOwned,
+    R: DeserializeOwned,
+{
+    type Error = ApiError;
+
+    fn try_from(body: ResponseBody) -> Result<Self, Self::Error> {
+        body.parse_json()
+    }
+}
+
+impl<L, R> JsonExtractor for Either<L, R>
+where
+    L: Serialize,
+    R: Serialize,
+{
+    fn try_extract<T>(self) -> ApiResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let value = serde_json::to_value(self).map_err(ApiError::DecodeJson)?;
+        serde_json::from_value(value).map_err(ApiError::DecodeJson)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use serde::Deserialize;
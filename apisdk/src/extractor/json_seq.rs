@@ -0,0 +1,103 @@
+use std::collections::VecDeque;
+
+use futures::{stream, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+
+use crate::{ApiError, ApiResult};
+
+/// The record separator of JSON text sequences
+const RS: u8 = 0x1e;
+
+/// This struct is used to decode JSON text sequences (application/json-seq, RFC 7464)
+#[derive(Debug, Default)]
+pub(crate) struct JsonSeqDecoder {
+    /// Bytes of the record under construction
+    buffer: Vec<u8>,
+}
+
+impl JsonSeqDecoder {
+    /// Feed bytes, and return all completed records
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<Vec<u8>> {
+        let mut records = vec![];
+        for &b in bytes {
+            match b {
+                RS => records.extend(self.take()),
+                _ => self.buffer.push(b),
+            }
+        }
+        records
+    }
+
+    /// Take the last record, when there are no more bytes
+    pub fn finish(&mut self) -> Option<Vec<u8>> {
+        self.take()
+    }
+
+    /// Take the record under construction, and skip blank one
+    fn take(&mut self) -> Option<Vec<u8>> {
+        let record = std::mem::take(&mut self.buffer);
+        (!record.trim_ascii().is_empty()).then_some(record)
+    }
+}
+
+/// Decode bytes stream as JSON text sequences
+/// - bytes: the bytes stream of response
+///
+/// Each record will be decoded independently, so a malformed record will not stop the stream.
+pub(crate) fn decode_json_seq<T, S, B>(bytes: S) -> impl Stream<Item = ApiResult<T>>
+where
+    T: DeserializeOwned,
+    S: Stream<Item = Result<B, reqwest::Error>>,
+    B: AsRef<[u8]>,
+{
+    let state = (
+        Box::pin(bytes),
+        JsonSeqDecoder::default(),
+        VecDeque::<Vec<u8>>::new(),
+        false,
+    );
+    stream::unfold(
+        state,
+        |(mut bytes, mut decoder, mut queue, mut done)| async move {
+            loop {
+                if let Some(record) = queue.pop_front() {
+                    let item = serde_json::from_slice::<T>(&record).map_err(ApiError::DecodeJson);
+                    return Some((item, (bytes, decoder, queue, done)));
+                }
+                if done {
+                    return None;
+                }
+                match bytes.next().await {
+                    Some(Ok(chunk)) => queue.extend(decoder.feed(chunk.as_ref())),
+                    Some(Err(e)) => return Some((Err(e.into()), (bytes, decoder, queue, true))),
+                    None => {
+                        queue.extend(decoder.finish());
+                        done = true;
+                    }
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonSeqDecoder;
+
+    #[test]
+    fn test_decode_records() {
+        let mut decoder = JsonSeqDecoder::default();
+        let mut records = decoder.feed(b"\x1e{\"id\":1}\n\x1e[1,2]\n\x1e\n\x1e\"te");
+        assert!(decoder.feed(b"xt\"").is_empty());
+        records.extend(decoder.finish());
+        assert_eq!(
+            vec![
+                b"{\"id\":1}\n".to_vec(),
+                b"[1,2]\n".to_vec(),
+                b"\"text\"".to_vec()
+            ],
+            records
+        );
+        assert_eq!(None, decoder.finish());
+    }
+}
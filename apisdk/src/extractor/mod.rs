@@ -1,30 +1,58 @@
-use hyper::header::HeaderValue;
+use std::any::type_name;
+
+use bytes::Bytes;
+use hyper::header::{
+    HeaderMap, HeaderValue, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, TRANSFER_ENCODING,
+};
+use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
+mod allow;
 mod auto;
+mod graphql;
 mod json;
+#[cfg(not(target_arch = "wasm32"))]
+mod json_seq;
+mod resource;
+#[cfg(feature = "sse")]
+mod sse;
 mod text;
 mod xml;
 
+pub use allow::*;
 pub use auto::*;
+pub use graphql::*;
 pub use json::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use json_seq::decode_json_seq;
+pub use resource::*;
+#[cfg(feature = "sse")]
+pub(crate) use sse::decode_sse;
+#[cfg(feature = "sse")]
+pub use sse::SseEvent;
 pub use text::*;
 pub use xml::*;
 
 use crate::{ApiError, ApiResult};
 
 /// MimeType (aka. ContentType)
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum MimeType {
     /// No mime-type
     Empty,
-    /// Json (application/json)
+    /// Json (application/json | text/json | */*+json, e.g. application/problem+json)
     Json,
-    /// Xml (application/xml | text/xml)
+    /// Xml (application/xml | text/xml | */*+xml, e.g. application/atom+xml)
     Xml,
+    /// Html (text/html)
+    Html,
     /// Text (text/plain | text/*)
     Text,
+    /// Server-Sent Events (text/event-stream)
+    EventStream,
+    /// JSON text sequences (application/json-seq)
+    JsonSeq,
     /// Other
     Other(String),
 }
@@ -35,7 +63,10 @@ impl std::fmt::Display for MimeType {
             Self::Empty => write!(f, "application/octet-stream"),
             Self::Json => write!(f, "application/json"),
             Self::Xml => write!(f, "application/xml"),
+            Self::Html => write!(f, "text/html"),
             Self::Text => write!(f, "text/plain"),
+            Self::EventStream => write!(f, "text/event-stream"),
+            Self::JsonSeq => write!(f, "application/json-seq"),
             Self::Other(v) => write!(f, "{}", v),
         }
     }
@@ -50,10 +81,16 @@ impl From<&str> for MimeType {
         .trim()
         .to_lowercase();
 
-        if value == "application/json" {
+        if value.ends_with("/json") || value.ends_with("+json") {
             Self::Json
-        } else if value == "text/xml" || value == "application/xml" {
+        } else if value.ends_with("/xml") || value.ends_with("+xml") {
             Self::Xml
+        } else if value == "text/html" {
+            Self::Html
+        } else if value == "text/event-stream" {
+            Self::EventStream
+        } else if value == "application/json-seq" {
+            Self::JsonSeq
         } else if value.starts_with("text/") {
             Self::Text
         } else {
@@ -62,6 +99,20 @@ impl From<&str> for MimeType {
     }
 }
 
+impl MimeType {
+    /// Guess mime-type by the first non-whitespace byte of payload
+    /// - bytes: the payload
+    ///
+    /// `{` or `[` means Json, `<` means Xml, and others are treated as Text.
+    pub fn sniff(bytes: &[u8]) -> Self {
+        match bytes.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'{') | Some(b'[') => Self::Json,
+            Some(b'<') => Self::Xml,
+            _ => Self::Text,
+        }
+    }
+}
+
 impl From<MimeType> for HeaderValue {
     fn from(value: MimeType) -> Self {
         HeaderValue::from_str(value.to_string().as_str())
@@ -72,14 +123,18 @@ impl From<MimeType> for HeaderValue {
 /// This enum represents the payload of respones
 #[derive(Debug, Clone)]
 pub enum ResponseBody {
-    /// No response
+    /// No response (204 No Content, or zero-length payload)
     Empty,
     /// Json (content-type = application/json)
     Json(Value),
     /// Xml (content-type = text/xml | application/xml)
     Xml(String),
-    /// Text (content-type = text/plain | text/html | text/*)
+    /// Html (content-type = text/html)
+    Html(String),
+    /// Text (content-type = text/plain | text/*)
     Text(String),
+    /// Raw bytes, which are not decoded (see `RetainRawBytes`)
+    Raw(Bytes),
 }
 
 impl ResponseBody {
@@ -89,17 +144,93 @@ impl ResponseBody {
             Self::Empty => MimeType::Empty,
             Self::Json(_) => MimeType::Json,
             Self::Xml(_) => MimeType::Xml,
+            Self::Html(_) => MimeType::Html,
             Self::Text(_) => MimeType::Text,
+            Self::Raw(_) => MimeType::Other("application/octet-stream".to_string()),
+        }
+    }
+
+    /// Check whether there is no payload
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    /// Get the raw bytes, if they are retained by `RetainRawBytes`
+    pub fn raw_bytes(&self) -> Option<&Bytes> {
+        match self {
+            Self::Raw(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Convert to bytes, and the json payload will be serialized
+    pub fn into_bytes(self) -> Bytes {
+        match self {
+            Self::Empty => Bytes::new(),
+            Self::Json(json) => Bytes::from(json.to_string()),
+            Self::Xml(xml) => Bytes::from(xml),
+            Self::Html(html) => Bytes::from(html),
+            Self::Text(text) => Bytes::from(text),
+            Self::Raw(bytes) => bytes,
         }
     }
 
+    /// Convert to `http::Response`, e.g. to proxy the response
+    /// - headers: HTTP headers of response
+    /// - status: HTTP status code
+    ///
+    /// The payload may be re-encoded, so `Content-Length`, `Content-Encoding` and `Transfer-Encoding`
+    /// headers are removed, and `Content-Type` is set by the body if absent.
+    /// The `__headers__` field injected into json payload is removed as well.
+    /// Use `reqwest::Response::from` to convert it further.
+    pub fn into_http_response(
+        self,
+        headers: HeaderMap,
+        status: StatusCode,
+    ) -> http::Response<Bytes> {
+        let mut headers = headers;
+        for name in [CONTENT_LENGTH, CONTENT_ENCODING, TRANSFER_ENCODING] {
+            headers.remove(name);
+        }
+        if !self.is_empty() && !headers.contains_key(CONTENT_TYPE) {
+            headers.insert(CONTENT_TYPE, self.mime_type().into());
+        }
+
+        let body = match self {
+            Self::Json(json) => match unwrap_envelope(json) {
+                Value::Object(mut m) => {
+                    m.remove("__headers__");
+                    Self::Json(Value::Object(m))
+                }
+                json => Self::Json(json),
+            },
+            body => body,
+        };
+        let mut res = http::Response::new(body.into_bytes());
+        *res.status_mut() = status;
+        *res.headers_mut() = headers;
+        res
+    }
+
+    /// Decode raw bytes as UTF-8 text
+    pub(crate) fn raw_text(bytes: &Bytes) -> ApiResult<String> {
+        String::from_utf8(bytes.to_vec()).map_err(|_| ApiError::DecodeText)
+    }
+
     /// Parse as json to target type
+    ///
+    /// The `{"__body__": payload, "__headers__": {...}}` wrapper of non-object payload will be removed.
+    /// The `Empty` response will be treated as `null`, so it could be decoded as `Option<T>` or `()`.
     pub fn parse_json<T>(self) -> ApiResult<T>
     where
         T: DeserializeOwned,
     {
         match self {
-            Self::Json(json) => serde_json::from_value(json).map_err(ApiError::DecodeJson),
+            Self::Empty => parse_empty(),
+            Self::Json(json) => {
+                serde_json::from_value(unwrap_envelope(json)).map_err(ApiError::DecodeJson)
+            }
+            Self::Raw(bytes) => serde_json::from_slice(&bytes).map_err(ApiError::DecodeJson),
             _ => Err(ApiError::IncompatibleContentType(
                 MimeType::Json,
                 self.mime_type(),
@@ -108,16 +239,22 @@ impl ResponseBody {
     }
 
     /// Parse as xml to target type
+    ///
+    /// The `Empty` response will be treated as `null`, so it could be decoded as `Option<T>` or `()`.
     pub fn parse_xml<T>(self) -> ApiResult<T>
     where
         T: DeserializeOwned,
     {
         match self {
+            Self::Empty => parse_empty(),
             Self::Xml(xml) => quick_xml::de::from_str(&xml).map_err(ApiError::DecodeXml),
             Self::Text(text) => {
                 log::debug!("Treat text as xml for decoding");
                 quick_xml::de::from_str(&text).map_err(ApiError::DecodeXml)
             }
+            Self::Raw(bytes) => {
+                quick_xml::de::from_str(&Self::raw_text(&bytes)?).map_err(ApiError::DecodeXml)
+            }
             _ => Err(ApiError::IncompatibleContentType(
                 MimeType::Xml,
                 self.mime_type(),
@@ -126,6 +263,35 @@ impl ResponseBody {
     }
 }
 
+/// Decode `Empty` response as `null`, which only succeeds for `Option<T>`, `()` or similar types
+pub(crate) fn parse_empty<T>() -> ApiResult<T>
+where
+    T: DeserializeOwned,
+{
+    log::debug!("Treat empty as null for decoding");
+    serde_json::from_value(Value::Null).map_err(|_| {
+        ApiError::DecodeResponse(
+            MimeType::Empty,
+            format!(
+                "Empty response could not be decoded as {}",
+                type_name::<T>()
+            ),
+        )
+    })
+}
+
+/// Unwrap `{"__body__": payload, "__headers__": {...}}`, which is used to carry headers for non-object payload
+pub(crate) fn unwrap_envelope(json: Value) -> Value {
+    match json {
+        Value::Object(mut m)
+            if m.len() == 2 && m.contains_key("__body__") && m.contains_key("__headers__") =>
+        {
+            m.remove("__body__").unwrap_or_default()
+        }
+        _ => json,
+    }
+}
+
 /// This struct is used to parse response body to xml
 #[derive(Debug)]
 pub struct Body;
@@ -140,3 +306,67 @@ impl Body {
         T::try_from(body).map_err(|e| e.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use reqwest::{
+        header::{HeaderMap, HeaderValue, CONTENT_LENGTH, CONTENT_TYPE},
+        StatusCode,
+    };
+    use serde_json::{json, Value};
+
+    use super::{MimeType, ResponseBody};
+
+    #[test]
+    fn test_mime_type_from() {
+        for value in [
+            "application/json",
+            "application/json; charset=utf-8",
+            "application/vnd.github+json",
+            "application/problem+json",
+            "application/hal+json;charset=UTF-8",
+            "text/json",
+        ] {
+            assert!(matches!(MimeType::from(value), MimeType::Json), "{}", value);
+        }
+        for value in ["application/xml", "text/xml", "application/atom+xml"] {
+            assert!(matches!(MimeType::from(value), MimeType::Xml), "{}", value);
+        }
+        assert!(matches!(
+            MimeType::from("application/json-seq"),
+            MimeType::JsonSeq
+        ));
+        assert!(matches!(MimeType::from("text/html"), MimeType::Html));
+        assert!(matches!(
+            MimeType::from("application/jsonp"),
+            MimeType::Other(_)
+        ));
+    }
+
+    #[test]
+    fn test_into_http_response() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("999"));
+        headers.insert("x-request-id", HeaderValue::from_static("abc"));
+        let body = ResponseBody::Json(json!({
+            "id": 1,
+            "__headers__": { "x-request-id": "abc" },
+        }));
+
+        let res = body.into_http_response(headers, StatusCode::CREATED);
+        assert_eq!(StatusCode::CREATED, res.status());
+        assert_eq!(None, res.headers().get(CONTENT_LENGTH));
+        assert_eq!("application/json", res.headers()[CONTENT_TYPE]);
+        assert_eq!("abc", res.headers()["x-request-id"]);
+        let json: Value = serde_json::from_slice(res.body()).unwrap();
+        assert_eq!(json!({ "id": 1 }), json);
+
+        let body = ResponseBody::Json(json!({ "__body__": [1, 2], "__headers__": {} }));
+        let res = body.into_http_response(HeaderMap::new(), StatusCode::OK);
+        assert_eq!("[1,2]", String::from_utf8_lossy(res.body()));
+
+        let res = ResponseBody::Empty.into_http_response(HeaderMap::new(), StatusCode::NO_CONTENT);
+        assert_eq!(None, res.headers().get(CONTENT_TYPE));
+        assert!(res.body().is_empty());
+    }
+}
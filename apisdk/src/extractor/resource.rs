@@ -0,0 +1,79 @@
+use reqwest::header::{HeaderMap, ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_TYPE, ETAG};
+
+/// This struct represents the meta of resource, which is parsed from headers of `HEAD` response
+///
+/// It's used by `send!(req, ResourceInfo)`, so callers could decide how to download the resource.
+/// All fields are unset for the response mocked by `MockServer`, which has no header.
+///
+/// # Examples
+///
+/// ```
+/// let req = client.head("/path/file").await?;
+/// let info = send!(req, ResourceInfo).await?;
+/// if info.accepts_ranges {
+///     // download by range requests
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceInfo {
+    /// `Content-Length` header
+    pub content_length: Option<u64>,
+    /// Whether `Accept-Ranges: bytes` is present
+    pub accepts_ranges: bool,
+    /// `Content-Type` header
+    pub content_type: Option<String>,
+    /// `ETag` header
+    pub etag: Option<String>,
+}
+
+impl ResourceInfo {
+    /// Parse the meta of resource
+    /// - headers: HTTP headers
+    pub fn parse(headers: &HeaderMap) -> Self {
+        let get = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+        };
+        Self {
+            content_length: get(CONTENT_LENGTH).and_then(|v| v.parse().ok()),
+            accepts_ranges: get(ACCEPT_RANGES)
+                .map(|v| v.split(',').any(|u| u.trim().eq_ignore_ascii_case("bytes")))
+                .unwrap_or_default(),
+            content_type: get(CONTENT_TYPE),
+            etag: get(ETAG),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_RANGES, CONTENT_LENGTH, ETAG};
+
+    use super::ResourceInfo;
+
+    #[test]
+    fn test_parse_resource_info() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("1024"));
+        headers.insert(ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        assert_eq!(
+            ResourceInfo {
+                content_length: Some(1024),
+                accepts_ranges: true,
+                content_type: None,
+                etag: Some("\"v1\"".to_string()),
+            },
+            ResourceInfo::parse(&headers)
+        );
+
+        headers.insert(ACCEPT_RANGES, HeaderValue::from_static("none"));
+        assert!(!ResourceInfo::parse(&headers).accepts_ranges);
+        assert_eq!(
+            ResourceInfo::default(),
+            ResourceInfo::parse(&HeaderMap::new())
+        );
+    }
+}
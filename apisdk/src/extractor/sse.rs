@@ -0,0 +1,169 @@
+use std::collections::VecDeque;
+
+use futures::{stream, Stream, StreamExt};
+
+use crate::ApiResult;
+
+/// This struct represents an event of Server-Sent Events (text/event-stream)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// `event` field
+    pub event: Option<String>,
+    /// `data` field, multiple `data` lines will be joined by `\n`
+    pub data: String,
+    /// `id` field
+    pub id: Option<String>,
+}
+
+/// This struct is used to decode SSE wire format
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    /// Bytes which are not terminated by line break yet
+    buffer: Vec<u8>,
+    /// The last byte is `\r`, so the following `\n` should be skipped
+    skip_lf: bool,
+    /// The event under construction
+    event: Option<String>,
+    /// The data lines under construction
+    data: Option<String>,
+    /// The last event id
+    id: Option<String>,
+}
+
+impl SseDecoder {
+    /// Feed bytes, and return all dispatched events
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<SseEvent> {
+        let mut events = vec![];
+        for &b in bytes {
+            if self.skip_lf {
+                self.skip_lf = false;
+                if b == b'\n' {
+                    continue;
+                }
+            }
+            match b {
+                b'\r' | b'\n' => {
+                    self.skip_lf = b == b'\r';
+                    let line = std::mem::take(&mut self.buffer);
+                    if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                        events.push(event);
+                    }
+                }
+                _ => self.buffer.push(b),
+            }
+        }
+        events
+    }
+
+    /// Process a single line
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        // Dispatch the event on blank line
+        if line.is_empty() {
+            let event = self.event.take();
+            return self.data.take().map(|data| SseEvent {
+                event,
+                data,
+                id: self.id.clone(),
+            });
+        }
+
+        // Ignore comments
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => match self.data.as_mut() {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_string()),
+            },
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// Decode bytes stream as SSE events
+/// - bytes: the bytes stream of response
+pub(crate) fn decode_sse<S, B>(bytes: S) -> impl Stream<Item = ApiResult<SseEvent>>
+where
+    S: Stream<Item = Result<B, reqwest::Error>>,
+    B: AsRef<[u8]>,
+{
+    let state = (
+        Box::pin(bytes),
+        SseDecoder::default(),
+        VecDeque::new(),
+        false,
+    );
+    stream::unfold(
+        state,
+        |(mut bytes, mut decoder, mut queue, mut done)| async move {
+            loop {
+                if let Some(event) = queue.pop_front() {
+                    return Some((Ok(event), (bytes, decoder, queue, done)));
+                }
+                if done {
+                    return None;
+                }
+                match bytes.next().await {
+                    Some(Ok(chunk)) => queue.extend(decoder.feed(chunk.as_ref())),
+                    Some(Err(e)) => return Some((Err(e.into()), (bytes, decoder, queue, true))),
+                    None => done = true,
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SseDecoder, SseEvent};
+
+    #[test]
+    fn test_decode_events() {
+        let mut decoder = SseDecoder::default();
+        let events = decoder.feed(b"event: greeting\ndata: hello\ndata: world\nid: 1\n\n: comment\ndata:{\"k\":1}\r\n\r\n");
+        assert_eq!(
+            vec![
+                SseEvent {
+                    event: Some("greeting".to_string()),
+                    data: "hello\nworld".to_string(),
+                    id: Some("1".to_string()),
+                },
+                SseEvent {
+                    event: None,
+                    data: "{\"k\":1}".to_string(),
+                    id: Some("1".to_string()),
+                },
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn test_decode_chunked() {
+        let mut decoder = SseDecoder::default();
+        assert!(decoder.feed(b"data: par").is_empty());
+        assert!(decoder.feed(b"tial\r").is_empty());
+        let events = decoder.feed(b"\n\r\n");
+        assert_eq!(1, events.len());
+        assert_eq!("partial", events[0].data);
+    }
+
+    #[test]
+    fn test_ignore_empty_data() {
+        let mut decoder = SseDecoder::default();
+        let events = decoder.feed(b"event: ping\n\n");
+        assert!(events.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::{ApiError, ApiResult, ResponseBody};
+use crate::{ApiError, ApiResult, MimeType, ResponseBody};
 
 /// This struct is used to parse response body to text
 #[derive(Debug)]
@@ -16,8 +16,36 @@ impl Text {
             ResponseBody::Empty => "".to_string(),
             ResponseBody::Json(json) => json.to_string(),
             ResponseBody::Xml(xml) => xml,
+            ResponseBody::Html(html) => html,
             ResponseBody::Text(text) => text,
+            ResponseBody::Raw(bytes) => ResponseBody::raw_text(&bytes)?,
         };
         T::from_str(&text).map_err(|_| ApiError::DecodeText)
     }
 }
+
+/// This struct is used to parse response body to html
+#[derive(Debug)]
+pub struct Html;
+
+impl Html {
+    /// Try to parse response
+    ///
+    /// Only `text/html` response is accepted, so that it could be distinguished from plain text.
+    pub fn try_parse<T>(body: ResponseBody) -> ApiResult<T>
+    where
+        T: FromStr,
+    {
+        let html = match body {
+            ResponseBody::Empty => "".to_string(),
+            ResponseBody::Html(html) => html,
+            _ => {
+                return Err(ApiError::IncompatibleContentType(
+                    MimeType::Html,
+                    body.mime_type(),
+                ))
+            }
+        };
+        T::from_str(&html).map_err(|_| ApiError::DecodeText)
+    }
+}
@@ -5,6 +5,8 @@ use serde_json::Value;
 
 use crate::{ApiError, ApiResult, MimeType, ResponseBody};
 
+use super::parse_empty;
+
 /// This struct is used to parse response body to xml
 #[derive(Debug)]
 pub struct Xml;
@@ -16,31 +18,35 @@ impl Xml {
     {
         let type_id = TypeId::of::<T>();
         if type_id == TypeId::of::<()>() {
-            serde_json::from_value(Value::Null).map_err(|_| ApiError::Impossible)
+            serde_json::from_value(Value::Null).map_err(ApiError::DecodeJson)
         } else if type_id == TypeId::of::<String>() {
             let value = serde_json::Value::String(text);
-            serde_json::from_value(value).map_err(|_| ApiError::Impossible)
+            serde_json::from_value(value).map_err(ApiError::DecodeJson)
         } else {
             quick_xml::de::from_str(&text).map_err(ApiError::DecodeXml)
         }
     }
 
     /// Try to parse response
+    ///
+    /// The `Empty` response will be treated as `null`, so it could be decoded as `Option<T>` or `()`.
     pub fn try_parse<T>(body: ResponseBody) -> ApiResult<T>
     where
         T: 'static + DeserializeOwned,
     {
         let type_id = TypeId::of::<T>();
         if type_id == TypeId::of::<()>() {
-            return serde_json::from_value(Value::Null).map_err(|_| ApiError::Impossible);
+            return serde_json::from_value(Value::Null).map_err(ApiError::DecodeJson);
         }
 
         match body {
             ResponseBody::Xml(xml) => Self::do_try_parse(xml),
-            ResponseBody::Text(text) => {
+            ResponseBody::Html(text) | ResponseBody::Text(text) => {
                 log::debug!("Treat text as xml for decoding");
                 Self::do_try_parse(text)
             }
+            ResponseBody::Raw(bytes) => Self::do_try_parse(ResponseBody::raw_text(&bytes)?),
+            ResponseBody::Empty => parse_empty(),
             _ => Err(ApiError::IncompatibleContentType(
                 MimeType::Xml,
                 body.mime_type(),
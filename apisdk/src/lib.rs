@@ -1,11 +1,14 @@
 //! A highlevel API client framework for Rust.
 
+pub mod content_disposition;
 mod core;
 pub mod digest;
 mod executor;
 mod extension;
 mod extractor;
+pub mod rate_limit;
 mod result;
+pub mod server_timing;
 mod url;
 
 pub use crate::core::*;
@@ -31,12 +34,17 @@ pub use quick_xml;
 pub use reqwest::dns;
 pub use reqwest::header;
 pub use reqwest::multipart;
+pub use reqwest::redirect;
 pub use reqwest::ClientBuilder;
 pub use reqwest::IntoUrl;
 pub use reqwest::Method;
+pub use reqwest::NoProxy;
+pub use reqwest::Proxy;
 pub use reqwest::Request;
 pub use reqwest::Response;
+pub use reqwest::StatusCode;
 pub use reqwest::Url;
+pub use reqwest::Version;
 
 // Re-export reqwest_middleware types
 pub use reqwest_middleware::ClientWithMiddleware as Client;
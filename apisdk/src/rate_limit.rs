@@ -0,0 +1,160 @@
+//! Helpers to parse and write `Retry-After` and `RateLimit-*` headers.
+
+use std::time::{Duration, SystemTime};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+
+/// The name of `RateLimit-Limit` header
+pub const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+/// The name of `RateLimit-Remaining` header
+pub const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+/// The name of `RateLimit-Reset` header
+pub const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+/// This enum represents the value of `Retry-After` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    /// delay-seconds
+    Delay(Duration),
+    /// HTTP-date
+    Date(SystemTime),
+}
+
+impl RetryAfter {
+    /// Parse `Retry-After` header
+    /// - headers: HTTP headers
+    pub fn parse(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(Self::parse_value)
+    }
+
+    /// Parse the value of `Retry-After` header
+    /// - value: delay-seconds or HTTP-date
+    pub fn parse_value(value: &str) -> Option<Self> {
+        let value = value.trim();
+        match value.parse::<u64>() {
+            Ok(secs) => Some(Self::Delay(Duration::from_secs(secs))),
+            Err(_) => httpdate::parse_http_date(value).ok().map(Self::Date),
+        }
+    }
+
+    /// Get the duration to wait from now
+    pub fn delay(&self) -> Duration {
+        match self {
+            Self::Delay(delay) => *delay,
+            Self::Date(date) => date
+                .duration_since(SystemTime::now())
+                .unwrap_or(Duration::ZERO),
+        }
+    }
+
+    /// Write `Retry-After` header
+    /// - headers: HTTP headers to update
+    pub fn write(&self, headers: &mut HeaderMap) {
+        let value = match self {
+            Self::Delay(delay) => delay.as_secs().to_string(),
+            Self::Date(date) => httpdate::fmt_http_date(*date),
+        };
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(RETRY_AFTER, value);
+        }
+    }
+}
+
+/// This struct represents the values of `RateLimit-*` headers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// `RateLimit-Limit`, the quota in current window
+    pub limit: Option<u64>,
+    /// `RateLimit-Remaining`, the remaining quota in current window
+    pub remaining: Option<u64>,
+    /// `RateLimit-Reset`, the seconds until the quota resets
+    pub reset: Option<u64>,
+}
+
+impl RateLimit {
+    /// Parse `RateLimit-*` headers, and fallback to `X-RateLimit-*` headers
+    /// - headers: HTTP headers
+    ///
+    /// Return `None` if none of them is present.
+    pub fn parse(headers: &HeaderMap) -> Option<Self> {
+        let get = |name: &str| {
+            [name.to_string(), format!("x-{}", name)]
+                .iter()
+                .find_map(|name| headers.get(name.as_str()))
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+        let rate_limit = Self {
+            limit: get(RATELIMIT_LIMIT.as_str()),
+            remaining: get(RATELIMIT_REMAINING.as_str()),
+            reset: get(RATELIMIT_RESET.as_str()),
+        };
+        (rate_limit != Self::default()).then_some(rate_limit)
+    }
+
+    /// Write `RateLimit-*` headers, and the absent values will be skipped
+    /// - headers: HTTP headers to update
+    pub fn write(&self, headers: &mut HeaderMap) {
+        for (name, value) in [
+            (RATELIMIT_LIMIT, self.limit),
+            (RATELIMIT_REMAINING, self.remaining),
+            (RATELIMIT_RESET, self.reset),
+        ] {
+            if let Some(value) = value {
+                headers.insert(name, HeaderValue::from(value));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+    use crate::rate_limit::*;
+
+    #[test]
+    fn test_retry_after_delay() {
+        let mut headers = HeaderMap::new();
+        RetryAfter::Delay(Duration::from_secs(120)).write(&mut headers);
+        assert_eq!("120", headers[RETRY_AFTER]);
+        assert_eq!(
+            Some(RetryAfter::Delay(Duration::from_secs(120))),
+            RetryAfter::parse(&headers)
+        );
+    }
+
+    #[test]
+    fn test_retry_after_date() {
+        let date = SystemTime::UNIX_EPOCH + Duration::from_secs(784111777);
+        let mut headers = HeaderMap::new();
+        RetryAfter::Date(date).write(&mut headers);
+        assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT", headers[RETRY_AFTER]);
+        assert_eq!(Some(RetryAfter::Date(date)), RetryAfter::parse(&headers));
+        assert_eq!(Duration::ZERO, RetryAfter::Date(date).delay());
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let rate_limit = RateLimit {
+            limit: Some(100),
+            remaining: Some(0),
+            reset: None,
+        };
+        let mut headers = HeaderMap::new();
+        rate_limit.write(&mut headers);
+        assert_eq!(2, headers.len());
+        assert_eq!(Some(rate_limit), RateLimit::parse(&headers));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("30"));
+        assert_eq!(Some(30), RateLimit::parse(&headers).and_then(|r| r.reset));
+
+        assert_eq!(None, RateLimit::parse(&HeaderMap::new()));
+    }
+}
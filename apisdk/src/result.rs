@@ -1,20 +1,63 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
 
-use crate::{MiddlewareError, MimeType};
+use crate::{GraphQLError, MiddlewareError, MimeType, StatusCode};
 
 /// Api Error
+///
+/// # Convert into custom error
+///
+/// SDK could wrap it into its own error type, so `?` works for both.
+///
+/// ```
+/// #[derive(Debug, thiserror::Error)]
+/// pub enum MyError {
+///     #[error("Api error: {0}")]
+///     Api(#[from] ApiError),
+///     #[error("Not found: {0}")]
+///     NotFound(u64),
+/// }
+///
+/// async fn get_user(api: &MyApi, id: u64) -> Result<User, MyError> {
+///     let req = api.get(format!("/user/{}", id)).await?;
+///     let user: Option<User> = send!(req).await?;
+///     user.ok_or(MyError::NotFound(id))
+/// }
+/// ```
+///
+/// Or use `into_anyhow` to convert it into `anyhow::Error`.
 #[derive(Debug, Error)]
 pub enum ApiError {
     /// Service discovery error
     #[error("Service discovery error: {0}")]
     ServiceDiscovery(anyhow::Error),
+    /// Misconfiguration of ApiBuilder
+    #[error("Config error: {0}")]
+    Config(String),
     /// Invalid URL
     #[error("Invalid URL: {0}")]
     InvalidUrl(reqwest::Error),
+    /// Invalid path, which is rendered from `PathTemplate`
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
     /// Build request error
     #[error("Build request error: {0}")]
     BuildRequest(reqwest::Error),
+    /// The request could not be cloned, e.g. its body is a stream
+    #[error("Request could not be cloned")]
+    UncloneableRequest,
+    /// Timeout error, when connecting or waiting for response
+    #[error("Timeout error: {0}")]
+    Timeout(reqwest::Error),
+    /// Connect error, such as connection refused or DNS failure
+    #[error("Connect error: {0}")]
+    Connect(reqwest::Error),
+    /// Request error, when sending request
+    #[error("Request error: {0}")]
+    Request(reqwest::Error),
     /// Generic reqwest error
     #[error("Generic reqwest error: {0}")]
     Reqwest(reqwest::Error),
@@ -24,6 +67,21 @@ pub enum ApiError {
     /// Invalid multipart form
     #[error("Invalid multipart form")]
     MultipartForm,
+    /// Serialize request payload error
+    /// - mime: the mime-type of payload
+    /// - detail: message
+    #[error("Serialize request error: {mime} => {detail}")]
+    SerializeRequest {
+        /// The mime-type of payload
+        mime: MimeType,
+        /// The detail of error
+        detail: String,
+    },
+    /// HTTP Redirect, which is not followed, or `304 Not Modified` which is not served from `ResponseCache`
+    /// - 0: status code
+    /// - 1: value of `Location` header
+    #[error("HTTP Redirect: [{0}] {1:?}")]
+    Redirect(u16, Option<String>),
     /// HTTP Client status error
     #[error("HTTP Client status error: [{0}] {1}")]
     HttpClientStatus(u16, String),
@@ -53,12 +111,35 @@ pub enum ApiError {
     /// Illegal json
     #[error("Illegal json: {0}")]
     IllegalJson(Value),
-    /// Decode xml error
+    /// Illegal xml, which could not be serialized
+    ///
+    /// `quick_xml::SeError` is converted into `SerializeRequest` by `?`, so it's only built explicitly.
     #[error("Illegal xml: {0}")]
-    IllegalXml(#[from] quick_xml::SeError),
+    IllegalXml(quick_xml::SeError),
+    /// Invalid `206 Partial Content` response, e.g. `Content-Range` mismatches `Range`
+    #[error("Invalid range response: {0}")]
+    InvalidRange(String),
+    /// The URL from response is on another origin, e.g. the next page of paginator
+    #[error("Cross-origin URL: {0}")]
+    CrossOrigin(String),
+    /// Invalid signature of response
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
     /// Service error
     #[error("Service error: {0} - {1:?}")]
     ServiceError(i64, Option<String>),
+    /// GraphQL error, which is reported by `errors` field of response
+    #[error("GraphQL error: {}", .0.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join("; "))]
+    GraphQL(Vec<GraphQLError>),
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The error of batched request, which is shared by all callers in the same batch
+    #[error("Batch error: {0}")]
+    Batch(Arc<ApiError>),
+    /// The batched request was cancelled before replying, e.g. the runtime is shutting down
+    #[error("Batch cancelled")]
+    BatchCancelled,
     /// Other error
     #[error("Other error: {0}")]
     Other(String),
@@ -67,21 +148,106 @@ pub enum ApiError {
     Impossible,
 }
 
+/// Serialize xml error, which is raised when building request
+impl From<quick_xml::SeError> for ApiError {
+    fn from(e: quick_xml::SeError) -> Self {
+        Self::SerializeRequest {
+            mime: MimeType::Xml,
+            detail: e.to_string(),
+        }
+    }
+}
+
+/// Serialize urlencoded form or query error, which is raised when building request
+impl From<serde_urlencoded::ser::Error> for ApiError {
+    fn from(e: serde_urlencoded::ser::Error) -> Self {
+        Self::SerializeRequest {
+            mime: MimeType::Other("application/x-www-form-urlencoded".to_string()),
+            detail: e.to_string(),
+        }
+    }
+}
+
 impl ApiError {
     /// Build ApiError by using `code` and `message`
     pub fn new(code: i64, message: impl ToString) -> Self {
         Self::ServiceError(code, Some(message.to_string()))
     }
 
+    /// Build ApiError by using HTTP status code
+    /// - status: HTTP status code
+    /// - message: the message of error
+    pub fn from_status(status: u16, message: impl ToString) -> Self {
+        if (400..500).contains(&status) {
+            Self::HttpClientStatus(status, message.to_string())
+        } else {
+            Self::HttpServerStatus(status, message.to_string())
+        }
+    }
+
+    /// Try to downcast the error raised by middleware to concrete type
+    ///
+    /// Return `None` if it's not `ApiError::Middleware`, or the type is mismatched.
+    pub fn downcast_middleware<E>(&self) -> Option<&E>
+    where
+        E: std::fmt::Display + std::fmt::Debug + Send + Sync + 'static,
+    {
+        match self {
+            Self::Middleware(e) => e.downcast_ref::<E>(),
+            _ => None,
+        }
+    }
+
+    /// Convert into `anyhow::Error`, which could be downcasted back to `ApiError`
+    pub fn into_anyhow(self) -> anyhow::Error {
+        anyhow::Error::new(self)
+    }
+
+    /// Check whether it's caused by HTTP 4xx status
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            Self::Batch(e) => e.is_client_error(),
+            _ => matches!(self, Self::HttpClientStatus(..)),
+        }
+    }
+
+    /// Check whether it's caused by HTTP 5xx status
+    pub fn is_server_error(&self) -> bool {
+        match self {
+            Self::Batch(e) => e.is_server_error(),
+            _ => matches!(self, Self::HttpServerStatus(..)),
+        }
+    }
+
+    /// Check whether the request could be retried
+    ///
+    /// Return `true` for HTTP 5xx status, HTTP 429 status, connect errors and timeout errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::HttpServerStatus(..) | Self::Timeout(..) | Self::Connect(..) => true,
+            Self::HttpClientStatus(c, _) => *c == 429,
+            Self::Batch(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
     /// Try to retrieve `error_code`
     pub fn as_error_code(&self) -> i32 {
         match self {
             Self::ServiceDiscovery(..)
+            | Self::Config(..)
             | Self::InvalidUrl(..)
+            | Self::InvalidPath(..)
             | Self::BuildRequest(..)
+            | Self::UncloneableRequest
+            | Self::Request(..)
             | Self::Reqwest(..)
             | Self::Middleware(..)
-            | Self::MultipartForm => 400,
+            | Self::MultipartForm
+            | Self::SerializeRequest { .. } => 400,
+            Self::Timeout(..) => 504,
+            Self::Connect(..) => 503,
+            Self::Redirect(c, _) => *c as i32,
             Self::HttpClientStatus(c, _) => *c as i32,
             Self::HttpServerStatus(c, _) => *c as i32,
             Self::UnsupportedContentType(..)
@@ -91,8 +257,15 @@ impl ApiError {
             | Self::DecodeXml(..)
             | Self::DecodeText
             | Self::IllegalJson(..)
-            | Self::IllegalXml(..) => 500,
+            | Self::IllegalXml(..)
+            | Self::InvalidRange(..)
+            | Self::CrossOrigin(..)
+            | Self::InvalidSignature(..) => 500,
             Self::ServiceError(c, _) => *c as i32,
+            Self::GraphQL(..) => 500,
+            Self::Io(..) => 500,
+            Self::Batch(e) => e.as_error_code(),
+            Self::BatchCancelled => 500,
             Self::Other(..) | Self::Impossible => 500,
         }
     }
@@ -107,6 +280,12 @@ impl From<reqwest::Error> for ApiError {
             } else {
                 ApiError::HttpServerStatus(status.as_u16(), status.to_string())
             }
+        } else if e.is_timeout() {
+            ApiError::Timeout(e)
+        } else if e.is_connect() {
+            ApiError::Connect(e)
+        } else if e.is_request() {
+            ApiError::Request(e)
         } else {
             ApiError::Reqwest(e)
         }
@@ -116,11 +295,165 @@ impl From<reqwest::Error> for ApiError {
 impl From<MiddlewareError> for ApiError {
     fn from(e: MiddlewareError) -> Self {
         match e {
-            MiddlewareError::Reqwest(e) => Self::Reqwest(e),
+            MiddlewareError::Reqwest(e) => e.into(),
             MiddlewareError::Middleware(e) => Self::Middleware(e),
         }
     }
 }
 
+/// This struct represents the problem details of RFC 7807.
+///
+/// It's used to parse `application/problem+json` payload of failed responses.
+///
+/// # Examples
+///
+/// ```
+/// let problem: ProblemDetails = serde_json::from_str(r#"{
+///     "type": "https://example.com/probs/out-of-credit",
+///     "title": "You do not have enough credit.",
+///     "status": 403,
+///     "detail": "Your current balance is 30, but that costs 50."
+/// }"#)?;
+/// let e: ApiError = problem.into();
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    /// `type` field, a URI reference that identifies the problem type
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_uri: Option<String>,
+    /// `title` field, a short summary of the problem type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `status` field, the HTTP status code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// `detail` field, an explanation specific to this occurrence of the problem
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// `instance` field, a URI reference that identifies the specific occurrence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Hold extension members
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+impl ProblemDetails {
+    /// Check whether the content-type is `application/problem+json`
+    /// - content_type: value of content-type
+    pub fn is_problem_json(content_type: &str) -> bool {
+        let value = match content_type.split_once(';') {
+            Some((left, _)) => left,
+            _ => content_type,
+        };
+        value
+            .trim()
+            .eq_ignore_ascii_case("application/problem+json")
+    }
+
+    /// Get any extension member
+    /// - name: member name
+    pub fn get_extra<D>(&self, name: &str) -> Option<D>
+    where
+        D: DeserializeOwned,
+    {
+        self.extra
+            .get(name)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Build a readable message by using `title` and `detail`
+    pub fn message(&self) -> Option<String> {
+        match (self.title.as_ref(), self.detail.as_ref()) {
+            (Some(title), Some(detail)) => Some(format!("{}: {}", title, detail)),
+            (Some(title), None) => Some(title.clone()),
+            (None, Some(detail)) => Some(detail.clone()),
+            (None, None) => self.type_uri.clone(),
+        }
+    }
+
+    /// Build ApiError by using HTTP status code
+    /// - status: HTTP status code of the response
+    ///
+    /// The canonical reason (e.g. `404 Not Found`) is used, if there is no `title`, `detail` or `type`.
+    pub fn into_api_error(self, status: u16) -> ApiError {
+        match self.message() {
+            Some(message) => ApiError::from_status(status, message),
+            None => match StatusCode::from_u16(status) {
+                Ok(code) => ApiError::from_status(status, code),
+                Err(_) => ApiError::from_status(status, status),
+            },
+        }
+    }
+}
+
+impl From<ProblemDetails> for ApiError {
+    fn from(problem: ProblemDetails) -> Self {
+        let status = problem.status.unwrap_or(500);
+        problem.into_api_error(status)
+    }
+}
+
 /// An alias of Result<T, ApiError
 pub type ApiResult<T> = Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use crate::MimeType;
+
+    use super::ApiError;
+
+    #[test]
+    fn test_is_retryable() {
+        let e = ApiError::from_status(503, "Service Unavailable");
+        assert!(e.is_server_error());
+        assert!(!e.is_client_error());
+        assert!(e.is_retryable());
+
+        let e = ApiError::from_status(429, "Too Many Requests");
+        assert!(e.is_client_error());
+        assert!(e.is_retryable());
+
+        let e = ApiError::from_status(404, "Not Found");
+        assert!(e.is_client_error());
+        assert!(!e.is_server_error());
+        assert!(!e.is_retryable());
+
+        let e = ApiError::DecodeResponse(MimeType::Json, "broken".to_string());
+        assert!(!e.is_client_error());
+        assert!(!e.is_server_error());
+        assert!(!e.is_retryable());
+
+        assert!(!ApiError::DecodeText.is_retryable());
+        assert!(!ApiError::new(1, "business").is_retryable());
+    }
+
+    #[test]
+    fn test_from_serde_errors() {
+        let e: ApiError = quick_xml::se::to_string(&vec![1]).unwrap_err().into();
+        assert!(matches!(
+            e,
+            ApiError::SerializeRequest {
+                mime: MimeType::Xml,
+                ..
+            }
+        ));
+
+        let e: ApiError = quick_xml::de::from_str::<u32>("<a>x</a>")
+            .unwrap_err()
+            .into();
+        assert!(matches!(e, ApiError::DecodeXml(_)));
+
+        let e: ApiError = serde_urlencoded::to_string(vec![1]).unwrap_err().into();
+        match e {
+            ApiError::SerializeRequest {
+                mime: MimeType::Other(mime),
+                ..
+            } => assert_eq!("application/x-www-form-urlencoded", mime),
+            e => panic!("unexpected error: {:?}", e),
+        }
+
+        let e: ApiError = serde_json::from_str::<u32>("x").unwrap_err().into();
+        assert!(matches!(e, ApiError::DecodeJson(_)));
+    }
+}
@@ -0,0 +1,265 @@
+//! Helpers to parse and write `Server-Timing` header.
+
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+/// The name of `Server-Timing` header
+pub const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+/// This struct represents a metric of `Server-Timing` header
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerTimingMetric {
+    /// The name of metric
+    pub name: String,
+    /// `dur` param, the duration of metric
+    pub duration: Option<Duration>,
+    /// `desc` param, the description of metric
+    pub description: Option<String>,
+}
+
+impl ServerTimingMetric {
+    /// Create a metric without params
+    /// - name: the name of metric
+    pub fn new(name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            duration: None,
+            description: None,
+        }
+    }
+
+    /// Set the duration of metric
+    /// - duration: the duration
+    pub fn with_duration(self, duration: Duration) -> Self {
+        Self {
+            duration: Some(duration),
+            ..self
+        }
+    }
+
+    /// Set the description of metric
+    /// - description: the description
+    pub fn with_description(self, description: impl ToString) -> Self {
+        Self {
+            description: Some(description.to_string()),
+            ..self
+        }
+    }
+
+    /// Parse a single metric, e.g. `db;dur=53.2;desc="Database"`
+    fn parse(value: &str) -> Option<Self> {
+        let mut parts = split_unquoted(value, ';').into_iter();
+        let name = parts.next()?.trim();
+        if name.is_empty() {
+            return None;
+        }
+        let mut metric = Self::new(name);
+        for param in parts {
+            let (key, value) = match param.split_once('=') {
+                Some((key, value)) => (key.trim(), unquote(value.trim())),
+                None => (param.trim(), String::new()),
+            };
+            if key.eq_ignore_ascii_case("dur") {
+                metric.duration = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|ms| ms.is_finite() && *ms >= 0.0)
+                    .map(|ms| Duration::from_nanos((ms * 1_000_000.0).round() as u64));
+            } else if key.eq_ignore_ascii_case("desc") {
+                metric.description = Some(value);
+            }
+        }
+        Some(metric)
+    }
+}
+
+impl std::fmt::Display for ServerTimingMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        // Write milliseconds with microsecond precision, e.g. `23.500`, rather than float noise
+        if let Some(duration) = self.duration {
+            let micros = duration.as_micros();
+            write!(f, ";dur={}.{:03}", micros / 1000, micros % 1000)?;
+        }
+        if let Some(description) = self.description.as_ref() {
+            write!(f, ";desc={:?}", description)?;
+        }
+        Ok(())
+    }
+}
+
+/// This struct represents the metrics of `Server-Timing` headers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerTiming {
+    /// All metrics, in the order of appearance
+    pub metrics: Vec<ServerTimingMetric>,
+}
+
+impl ServerTiming {
+    /// Parse all `Server-Timing` headers
+    /// - headers: HTTP headers
+    ///
+    /// Return `None` if there is no valid metric.
+    pub fn parse(headers: &HeaderMap) -> Option<Self> {
+        let metrics: Vec<_> = headers
+            .get_all(SERVER_TIMING)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| Self::parse_value(v).metrics)
+            .collect();
+        (!metrics.is_empty()).then_some(Self { metrics })
+    }
+
+    /// Parse the value of `Server-Timing` header
+    /// - value: comma separated metrics, e.g. `cache;desc="Cache Read";dur=23.2, db;dur=53`
+    pub fn parse_value(value: &str) -> Self {
+        Self {
+            metrics: split_unquoted(value, ',')
+                .into_iter()
+                .filter_map(ServerTimingMetric::parse)
+                .collect(),
+        }
+    }
+
+    /// Get the metric by name
+    /// - name: the name of metric
+    pub fn get(&self, name: &str) -> Option<&ServerTimingMetric> {
+        self.metrics.iter().find(|m| m.name == name)
+    }
+
+    /// Get the sum of all durations
+    pub fn total(&self) -> Duration {
+        self.metrics.iter().filter_map(|m| m.duration).sum()
+    }
+
+    /// Write `Server-Timing` header
+    /// - headers: HTTP headers to update
+    pub fn write(&self, headers: &mut HeaderMap) {
+        if let Ok(value) = HeaderValue::from_str(&self.to_string()) {
+            headers.insert(SERVER_TIMING, value);
+        }
+    }
+}
+
+impl std::fmt::Display for ServerTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, metric) in self.metrics.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", metric)?;
+        }
+        Ok(())
+    }
+}
+
+/// Split value by separator, which is not enclosed by double quotes
+pub(crate) fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Remove double quotes, and unescape the quoted string
+pub(crate) fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(inner) => {
+            let mut text = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => text.extend(chars.next()),
+                    c => text.push(c),
+                }
+            }
+            text
+        }
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+
+    use super::{ServerTiming, ServerTimingMetric, SERVER_TIMING};
+
+    #[test]
+    fn test_parse_metrics() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            SERVER_TIMING,
+            HeaderValue::from_static(r#"cache;desc="Cache Read, L2";dur=23.5, db;dur=53"#),
+        );
+        headers.append(SERVER_TIMING, HeaderValue::from_static("miss, ;dur=1"));
+
+        let timing = ServerTiming::parse(&headers).unwrap();
+        assert_eq!(
+            vec![
+                ServerTimingMetric::new("cache")
+                    .with_description("Cache Read, L2")
+                    .with_duration(Duration::from_micros(23500)),
+                ServerTimingMetric::new("db").with_duration(Duration::from_millis(53)),
+                ServerTimingMetric::new("miss"),
+            ],
+            timing.metrics
+        );
+        assert_eq!(Duration::from_micros(76500), timing.total());
+        assert_eq!(
+            Some(Duration::from_millis(53)),
+            timing.get("db").and_then(|m| m.duration)
+        );
+    }
+
+    #[test]
+    fn test_write_metrics() {
+        let timing = ServerTiming {
+            metrics: vec![
+                ServerTimingMetric::new("app").with_duration(Duration::from_millis(12)),
+                ServerTimingMetric::new("edge").with_description("say \"hi\""),
+            ],
+        };
+        let mut headers = HeaderMap::new();
+        timing.write(&mut headers);
+        assert_eq!(
+            r#"app;dur=12.000, edge;desc="say \"hi\"""#,
+            headers[SERVER_TIMING]
+        );
+        assert_eq!(Some(timing), ServerTiming::parse(&headers));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = r#"cache;dur=23.500;desc="Cache Read", db;dur=0.001, app;dur=1234.567"#;
+        let timing = ServerTiming::parse_value(value);
+        assert_eq!(
+            Some(Duration::from_micros(1)),
+            timing.get("db").and_then(|m| m.duration)
+        );
+        assert_eq!(value, timing.to_string());
+        assert_eq!(timing, ServerTiming::parse_value(&timing.to_string()));
+    }
+
+    #[test]
+    fn test_parse_absent() {
+        assert_eq!(None, ServerTiming::parse(&HeaderMap::new()));
+    }
+}
@@ -0,0 +1,195 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+use crate::{ApiError, ApiResult, DnsResolver, SocketAddrs};
+
+/// The HostsFile performs DNS queries by using a static hosts-style file
+///
+/// Each line of the file maps a host to one or more addresses, which are separated by whitespaces.
+/// The port is optional, and it's only used when the url has no explicit port.
+/// Lines starting with `#` are ignored.
+///
+/// ```text
+/// # host          addr[:port] ...
+/// api.internal    10.0.0.1:8080 10.0.0.2:8080
+/// db.internal     10.0.1.1
+/// ```
+///
+/// The file will be reloaded once the TTL is expired. If it fails to reload, previous entries are kept.
+/// The reloading is performed by one query at a time, without blocking the runtime, and other queries
+/// are answered by previous entries meanwhile.
+pub struct HostsFile {
+    /// The path of file
+    path: PathBuf,
+    /// The TTL of loaded entries
+    ttl: Duration,
+    /// The loaded entries, and when they were loaded
+    state: RwLock<(HashMap<String, Vec<SocketAddr>>, Instant)>,
+    /// Indicate whether the file is being reloaded
+    reloading: AtomicBool,
+}
+
+impl HostsFile {
+    /// The default TTL of loaded entries
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+    /// Create an instance by loading the file
+    /// - path: the path of file
+    pub fn new(path: impl AsRef<Path>) -> ApiResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = Self::load(&path)?;
+        Ok(Self {
+            path,
+            ttl: Self::DEFAULT_TTL,
+            state: RwLock::new((entries, Instant::now())),
+            reloading: AtomicBool::new(false),
+        })
+    }
+
+    /// Set the TTL of loaded entries
+    /// - ttl: the file will be reloaded after `ttl`
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        Self { ttl, ..self }
+    }
+
+    /// Load and parse the file, which blocks current thread
+    fn load(path: &Path) -> ApiResult<HashMap<String, Vec<SocketAddr>>> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content)
+    }
+
+    /// Parse the content of file
+    fn parse(content: &str) -> ApiResult<HashMap<String, Vec<SocketAddr>>> {
+        let mut entries = HashMap::new();
+        for line in content.lines() {
+            let line = line.split_once('#').map(|(l, _)| l).unwrap_or(line).trim();
+            let mut parts = line.split_whitespace();
+            let host = match parts.next() {
+                Some(host) => host,
+                None => continue,
+            };
+            let addrs = parts
+                .map(|addr| {
+                    addr.parse::<SocketAddr>()
+                        .or_else(|_| addr.parse::<IpAddr>().map(|ip| SocketAddr::from((ip, 0))))
+                        .map_err(|_| {
+                            ApiError::Config(format!("Invalid address of {}: {}", host, addr))
+                        })
+                })
+                .collect::<ApiResult<Vec<_>>>()?;
+            if addrs.is_empty() {
+                return Err(ApiError::Config(format!("No address of {}", host)));
+            }
+            entries.insert(host.to_lowercase(), addrs);
+        }
+        Ok(entries)
+    }
+
+    /// Reload the file if TTL is expired
+    ///
+    /// The file is read by `tokio::fs`, which runs on the blocking pool, and the lock is only held to swap entries.
+    async fn reload_if_expired(&self) {
+        let expired = self
+            .state
+            .read()
+            .map(|state| state.1.elapsed() >= self.ttl)
+            .unwrap_or_default();
+        if !expired
+            || self
+                .reloading
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                .is_err()
+        {
+            return;
+        }
+        let _guard = ReloadGuard(&self.reloading);
+
+        let result = match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => Self::parse(&content),
+            Err(e) => Err(e.into()),
+        };
+        if let Ok(mut state) = self.state.write() {
+            match result {
+                Ok(entries) => state.0 = entries,
+                Err(e) => log::warn!("Failed to reload {:?}: {}", self.path, e),
+            }
+            state.1 = Instant::now();
+        }
+    }
+}
+
+/// Reset the reloading flag, even if the query is cancelled
+struct ReloadGuard<'a>(&'a AtomicBool);
+
+impl Drop for ReloadGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+#[async_trait]
+impl DnsResolver for HostsFile {
+    async fn resolve(&self, name: &str) -> Option<SocketAddrs> {
+        self.reload_if_expired().await;
+        let state = self.state.read().ok()?;
+        state
+            .0
+            .get(&name.to_lowercase())
+            .map(|addrs| SocketAddrs::new_multi(addrs.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::HostsFile;
+
+    #[test]
+    fn test_parse() {
+        let entries = HostsFile::parse(
+            r#"
+            # comment
+            api.internal    10.0.0.1:8080 10.0.0.2:8080
+            DB.internal     10.0.1.1 # trailing comment
+            v6.internal     [::1]:443 ::1
+            "#,
+        )
+        .unwrap();
+        assert_eq!(3, entries.len());
+        assert_eq!(
+            vec![
+                "10.0.0.1:8080".parse::<SocketAddr>().unwrap(),
+                "10.0.0.2:8080".parse().unwrap()
+            ],
+            entries["api.internal"]
+        );
+        assert_eq!(
+            vec!["10.0.1.1:0".parse::<SocketAddr>().unwrap()],
+            entries["db.internal"]
+        );
+        assert_eq!(
+            vec![
+                "[::1]:443".parse::<SocketAddr>().unwrap(),
+                "[::1]:0".parse().unwrap()
+            ],
+            entries["v6.internal"]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(HostsFile::parse("api.internal not-an-ip").is_err());
+        assert!(HostsFile::parse("api.internal").is_err());
+    }
+}
@@ -1,10 +1,16 @@
 use url::Url;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod hosts;
 mod resolver;
 mod rewriter;
+mod template;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use hosts::*;
 pub use resolver::*;
 pub use rewriter::*;
+pub use template::*;
 
 #[cfg(feature = "dns")]
 mod hickory;
@@ -12,6 +18,13 @@ mod hickory;
 #[cfg(feature = "dns")]
 pub use hickory::*;
 
+/// This struct holds the url of request, after being rewritten by `UrlRewriter` and `DnsResolver`
+///
+/// It's attached to the request by `ApiCore::build_request`, so middlewares could read the exact url
+/// which is used, rather than calling `build_url` again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedUrl(pub Url);
+
 /// This trait provides URL related functions
 pub trait UrlOps {
     /// Merge path
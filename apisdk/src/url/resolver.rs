@@ -42,6 +42,14 @@ impl SocketAddrs {
     }
 }
 
+impl Iterator for SocketAddrs {
+    type Item = SocketAddr;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
 impl From<IpAddr> for SocketAddrs {
     fn from(value: IpAddr) -> Self {
         SocketAddrs::new_single(SocketAddr::from((value, 0)))
@@ -120,11 +128,85 @@ where
 
 #[async_trait]
 impl DnsResolver for Box<dyn DnsResolver> {
+    fn get_scheme(&self) -> Option<&str> {
+        self.as_ref().get_scheme()
+    }
+
+    fn get_port(&self) -> Option<u16> {
+        self.as_ref().get_port()
+    }
+
+    async fn resolve(&self, name: &str) -> Option<SocketAddrs> {
+        self.as_ref().resolve(name).await
+    }
+}
+
+/// The shared `DnsResolver`, which could be passed to multiple builders, so its state (e.g. cache) is shared
+#[async_trait]
+impl DnsResolver for Arc<dyn DnsResolver> {
+    fn get_scheme(&self) -> Option<&str> {
+        self.as_ref().get_scheme()
+    }
+
+    fn get_port(&self) -> Option<u16> {
+        self.as_ref().get_port()
+    }
+
     async fn resolve(&self, name: &str) -> Option<SocketAddrs> {
         self.as_ref().resolve(name).await
     }
 }
 
+/// This enum is used to order the resolved addresses of dual-stack hosts
+///
+/// The connector races the address family of the first address (Happy Eyeballs),
+/// and falls back to the other family after a short delay. So the preferred family is moved to the front.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AddrPreference {
+    /// Keep the order of resolver
+    #[default]
+    System,
+    /// Try IPv4 addresses first, then IPv6
+    Ipv4First,
+    /// Try IPv6 addresses first, then IPv4
+    Ipv6First,
+    /// Only use IPv4 addresses
+    Ipv4Only,
+    /// Only use IPv6 addresses
+    Ipv6Only,
+}
+
+impl AddrPreference {
+    /// Order the addresses, and the relative order in the same family is kept
+    /// - addrs: the resolved addresses
+    pub fn apply(&self, addrs: impl IntoIterator<Item = SocketAddr>) -> Vec<SocketAddr> {
+        let addrs = addrs.into_iter();
+        match self {
+            Self::System => addrs.collect(),
+            Self::Ipv4First => {
+                let (v4, v6): (Vec<_>, Vec<_>) = addrs.partition(|a| a.is_ipv4());
+                v4.into_iter().chain(v6).collect()
+            }
+            Self::Ipv6First => {
+                let (v6, v4): (Vec<_>, Vec<_>) = addrs.partition(|a| a.is_ipv6());
+                v6.into_iter().chain(v4).collect()
+            }
+            Self::Ipv4Only => addrs.filter(|a| a.is_ipv4()).collect(),
+            Self::Ipv6Only => addrs.filter(|a| a.is_ipv6()).collect(),
+        }
+    }
+}
+
+/// This struct is used to skip the custom DNS queries, so the fallback resolver is always used
+struct SystemResolver;
+
+#[async_trait]
+impl DnsResolver for SystemResolver {
+    async fn resolve(&self, _name: &str) -> Option<SocketAddrs> {
+        None
+    }
+}
+
 /// This is default DNS Resolver of reqwest
 #[derive(Clone)]
 struct FallbackResolver(GaiResolver);
@@ -157,6 +239,8 @@ pub(crate) struct ReqwestDnsResolver {
     /// The provided `DnsResolver`
     resolver: Arc<dyn DnsResolver>,
     fallback: FallbackResolver,
+    /// The preference of address family
+    preference: AddrPreference,
 }
 
 impl ReqwestDnsResolver {
@@ -168,9 +252,20 @@ impl ReqwestDnsResolver {
             type_name: type_name::<T>(),
             resolver: Arc::new(resolver),
             fallback: FallbackResolver(GaiResolver::new()),
+            preference: AddrPreference::System,
         }
     }
 
+    /// Create an instance, which only uses the default DNS resolver of reqwest
+    pub fn system() -> Self {
+        Self::new(SystemResolver)
+    }
+
+    /// Set the preference of address family
+    pub fn with_preference(self, preference: AddrPreference) -> Self {
+        Self { preference, ..self }
+    }
+
     pub fn type_name(&self) -> &'static str {
         self.type_name
     }
@@ -195,10 +290,53 @@ impl Resolve for ReqwestDnsResolver {
     fn resolve(&self, name: Name) -> Resolving {
         let me = self.clone();
         Box::pin(async move {
-            if let Some(addrs) = me.resolver.resolve(name.as_str()).await {
-                return Ok(addrs.iter);
+            let addrs = match me.resolver.resolve(name.as_str()).await {
+                Some(addrs) => addrs.iter,
+                None => me.fallback.resolve(name).await?,
+            };
+            match me.preference {
+                AddrPreference::System => Ok(addrs),
+                preference => Ok(Box::new(preference.apply(addrs).into_iter()) as Addrs),
             }
-            me.fallback.resolve(name).await
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::AddrPreference;
+
+    #[test]
+    fn test_addr_preference() {
+        let addrs: Vec<SocketAddr> = ["[::1]:80", "127.0.0.1:80", "[::2]:80", "127.0.0.2:80"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+        let to_string = |addrs: Vec<SocketAddr>| -> Vec<String> {
+            addrs.iter().map(|a| a.to_string()).collect()
+        };
+
+        assert_eq!(
+            vec!["[::1]:80", "127.0.0.1:80", "[::2]:80", "127.0.0.2:80"],
+            to_string(AddrPreference::System.apply(addrs.clone()))
+        );
+        assert_eq!(
+            vec!["127.0.0.1:80", "127.0.0.2:80", "[::1]:80", "[::2]:80"],
+            to_string(AddrPreference::Ipv4First.apply(addrs.clone()))
+        );
+        assert_eq!(
+            vec!["[::1]:80", "[::2]:80", "127.0.0.1:80", "127.0.0.2:80"],
+            to_string(AddrPreference::Ipv6First.apply(addrs.clone()))
+        );
+        assert_eq!(
+            vec!["127.0.0.1:80", "127.0.0.2:80"],
+            to_string(AddrPreference::Ipv4Only.apply(addrs.clone()))
+        );
+        assert_eq!(
+            vec!["[::1]:80", "[::2]:80"],
+            to_string(AddrPreference::Ipv6Only.apply(addrs))
+        );
+    }
+}
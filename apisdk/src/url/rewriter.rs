@@ -1,10 +1,12 @@
 use std::{
     any::type_name,
+    borrow::Cow,
     net::{IpAddr, SocketAddr},
     sync::Arc,
 };
 
 use async_trait::async_trait;
+use regex::Regex;
 use url::Url;
 
 use crate::ApiError;
@@ -84,3 +86,55 @@ impl UrlRewriter for ReqwestUrlRewriter {
         self.rewriter.rewrite(url).await
     }
 }
+
+/// This struct is used to rewrite the relative path of request by regex, before it's merged with base_url
+///
+/// Rules are applied in order, and each rule replaces all matches in the output of previous one.
+/// The replacement supports `$1` / `$name` to refer capture groups, as `Regex::replace_all` does.
+///
+/// # Examples
+///
+/// ```
+/// let client = XxxApi::builder()
+///     .with_path_rewriter(PathRewriter::new().with_rule(r"^/internal(/|$)", "/")?)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PathRewriter {
+    /// The regex and replacement of rules
+    rules: Arc<Vec<(Regex, String)>>,
+}
+
+impl PathRewriter {
+    /// Create an instance without rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a rule
+    /// - pattern: the regex to match path
+    /// - replacement: the replacement of matches
+    ///
+    /// Return `ApiError::Config` if the pattern is invalid.
+    pub fn with_rule(self, pattern: &str, replacement: impl ToString) -> Result<Self, ApiError> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| ApiError::Config(format!("Invalid path pattern {}: {}", pattern, e)))?;
+        let mut rules = self.rules.as_ref().clone();
+        rules.push((regex, replacement.to_string()));
+        Ok(Self {
+            rules: Arc::new(rules),
+        })
+    }
+
+    /// Rewrite the path by all rules
+    /// - path: relative path to base_url
+    pub fn rewrite<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        let mut path = Cow::Borrowed(path);
+        for (regex, replacement) in self.rules.iter() {
+            if let Cow::Owned(rewritten) = regex.replace_all(&path, replacement.as_str()) {
+                path = Cow::Owned(rewritten);
+            }
+        }
+        path
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{ApiError, ApiResult};
+
+/// This struct is used to build a relative path from template, e.g. `/posts/{id}/comments/{cid}`
+///
+/// Each param is percent-encoded as a single path segment, so `/`, `?` and `#` in the value
+/// could not change the structure of url. The `.` and `..` values are rejected, as well as empty ones.
+///
+/// The rendered path is passed to `build_url` / `build_request` as is. The PathRewriter will see
+/// the encoded path, and `Url::set_path` keeps the `%XX` sequences, so they won't be encoded twice.
+///
+/// # Examples
+///
+/// ```
+/// let path = PathTemplate::new("/posts/{id}/comments/{cid}")
+///     .param("id", post_id)
+///     .param("cid", comment_id)
+///     .render()?;
+/// let req = self.get(path).await?;
+///
+/// // Or use `path!` macro
+/// let req = self.get(path!("/posts/{id}/comments/{cid}", id = post_id, cid = comment_id)?).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathTemplate {
+    /// The template of path
+    template: String,
+    /// The named params
+    params: Vec<(String, String)>,
+}
+
+impl PathTemplate {
+    /// Create a new instance
+    /// - template: the template of path, and `{name}` is the placeholder of param
+    pub fn new(template: impl ToString) -> Self {
+        Self {
+            template: template.to_string(),
+            params: vec![],
+        }
+    }
+
+    /// Set the value of param
+    /// - name: the name of param
+    /// - value: the value of param, which will be percent-encoded
+    pub fn param(self, name: impl ToString, value: impl ToString) -> Self {
+        let mut params = self.params;
+        params.push((name.to_string(), value.to_string()));
+        Self { params, ..self }
+    }
+
+    /// Render the path
+    ///
+    /// Return `ApiError::InvalidPath` if any placeholder is malformed or missing, any param is unused,
+    /// or any value is empty, `.` or `..`.
+    pub fn render(&self) -> ApiResult<String> {
+        let mut path = String::with_capacity(self.template.len());
+        let mut used = vec![false; self.params.len()];
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find(['{', '}']) {
+            path.push_str(&rest[..start]);
+            let (name, tail) = match rest[start..].strip_prefix('{') {
+                Some(tail) => match tail.split_once('}') {
+                    Some((name, tail)) if !name.contains('{') => (name, tail),
+                    _ => return Err(self.invalid("unclosed placeholder")),
+                },
+                None => return Err(self.invalid("unexpected '}'")),
+            };
+            let index = match self.params.iter().position(|(n, _)| n == name) {
+                Some(index) => index,
+                None => return Err(self.invalid(format!("missing param {:?}", name))),
+            };
+            let value = self.params[index].1.as_str();
+            if matches!(value, "" | "." | "..") {
+                return Err(self.invalid(format!("invalid value of {:?}: {:?}", name, value)));
+            }
+            encode_segment(value, &mut path);
+            used[index] = true;
+            rest = tail;
+        }
+        path.push_str(rest);
+
+        match self.params.iter().zip(used).find(|(_, used)| !used) {
+            Some(((name, _), _)) => Err(self.invalid(format!("unused param {:?}", name))),
+            None => Ok(path),
+        }
+    }
+
+    /// Build ApiError
+    fn invalid(&self, reason: impl std::fmt::Display) -> ApiError {
+        ApiError::InvalidPath(format!("{} ({})", self.template, reason))
+    }
+}
+
+/// Percent-encode all bytes but unreserved chars of RFC 3986
+fn encode_segment(value: &str, output: &mut String) {
+    for b in value.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                output.push(b as char)
+            }
+            b => output.push_str(&format!("%{:02X}", b)),
+        }
+    }
+}
+
+/// Build a relative path from template, and percent-encode each param
+///
+/// # Forms
+///
+/// - `path!("/posts/{id}", id = post_id)` -> `ApiResult<String>`
+///
+/// See `PathTemplate` for the details.
+///
+/// # Examples
+///
+/// ```
+/// let req = self.get(path!("/posts/{id}/comments/{cid}", id = post_id, cid = comment_id)?).await?;
+/// ```
+#[macro_export]
+macro_rules! path {
+    ($template:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::PathTemplate::new($template)
+            $(.param(stringify!($name), $value))*
+            .render()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PathTemplate;
+
+    #[test]
+    fn test_render() {
+        let path = PathTemplate::new("/posts/{id}/comments/{cid}")
+            .param("id", 1)
+            .param("cid", "a/b?c=d#e f")
+            .render()
+            .unwrap();
+        assert_eq!("/posts/1/comments/a%2Fb%3Fc%3Dd%23e%20f", path);
+
+        let path = PathTemplate::new("/users/{name}")
+            .param("name", "张三")
+            .render()
+            .unwrap();
+        assert_eq!("/users/%E5%BC%A0%E4%B8%89", path);
+
+        let path = path!("/posts/{id}.json", id = 42).unwrap();
+        assert_eq!("/posts/42.json", path);
+    }
+
+    #[test]
+    fn test_render_invalid() {
+        assert!(PathTemplate::new("/posts/{id")
+            .param("id", 1)
+            .render()
+            .is_err());
+        assert!(PathTemplate::new("/posts/id}").render().is_err());
+        assert!(PathTemplate::new("/posts/{id}").render().is_err());
+        assert!(PathTemplate::new("/posts").param("id", 1).render().is_err());
+        assert!(PathTemplate::new("/posts/{id}/x")
+            .param("id", "..")
+            .render()
+            .is_err());
+        assert!(PathTemplate::new("/posts/{id}")
+            .param("id", "")
+            .render()
+            .is_err());
+    }
+}
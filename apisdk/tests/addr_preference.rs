@@ -0,0 +1,71 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use apisdk::{send, AddrPreference, ApiResult, CodeDataMessage, SocketAddrs};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+/// Resolve to a dual-stack host, and the IPv6 address comes first
+fn dual_stack(_name: &str) -> Option<SocketAddrs> {
+    Some(SocketAddrs::new_multi(vec![
+        SocketAddr::from((IpAddr::V6(Ipv6Addr::LOCALHOST), 0)),
+        SocketAddr::from((IpAddr::V4(Ipv4Addr::LOCALHOST), 0)),
+    ]))
+}
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_addr_preference_ipv4() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    // The server only listens on IPv4
+    let api = TheApi::builder()
+        .with_resolver(dual_stack)
+        .with_addr_preference(AddrPreference::Ipv4First)
+        .build();
+    api.touch().await?;
+
+    let api = TheApi::builder()
+        .with_resolver(dual_stack)
+        .with_addr_preference(AddrPreference::Ipv4Only)
+        .build();
+    api.touch().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_addr_preference_ipv6_only() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_resolver(dual_stack)
+        .with_addr_preference(AddrPreference::Ipv6Only)
+        .build();
+    let result = api.touch().await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_addr_preference_without_resolver() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_addr_preference(AddrPreference::Ipv4First)
+        .build();
+    api.touch().await?;
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use apisdk::{api_method, send, ApiResult};
+use apisdk::{api_method, ApiResult};
 use serde_json::Value;
 
 use crate::common::{init_logger, start_server, TheApi};
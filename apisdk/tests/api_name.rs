@@ -0,0 +1,38 @@
+use std::sync::{Arc, Mutex};
+
+use apisdk::{send, ApiResult, CodeDataMessage};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_api_name() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let targets = Arc::new(Mutex::new(Vec::<String>::new()));
+    let sink = targets.clone();
+    let api = TheApi::builder()
+        .with_name("payments-api")
+        .with_log_sink(move |record| sink.lock().unwrap().push(record.target))
+        .build();
+    assert_eq!(Some("payments-api"), api.core.name());
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+
+    let targets = targets.lock().unwrap();
+    log::debug!("targets = {:?}", targets);
+    assert!(!targets.is_empty());
+    assert!(targets.iter().all(|t| t.starts_with("payments-api::")));
+
+    Ok(())
+}
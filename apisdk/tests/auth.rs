@@ -1,6 +1,6 @@
 use apisdk::{
-    send, AccessTokenAuth, ApiAuthenticator, ApiResult, Carrier, CodeDataMessage, HashedTokenAuth,
-    TokenGenerator, WithCarrier,
+    send, AccessTokenAuth, ApiAuthenticator, ApiResult, Carrier, CodeDataMessage, FixedClock,
+    HashAlgorithm, HashedTokenAuth, ParsedHashedToken, TokenGenerator, WithCarrier,
 };
 use async_trait::async_trait;
 use base64::{engine::general_purpose, Engine};
@@ -139,3 +139,40 @@ async fn test_hashed_token_auth() -> ApiResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_hashed_token_auth_fixed_clock() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_authenticator(
+            HashedTokenAuth::new_with_client_id(
+                "client_id",
+                "app_id",
+                "app_secret",
+                HashAlgorithm::Sha256,
+            )
+            .with_clock(FixedClock(1700000000)),
+        )
+        .build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+    let auth = res.headers.get("authorization").unwrap();
+    let token = auth.trim_start_matches("Bearer ");
+    let parsed = ParsedHashedToken::parse(token).unwrap();
+    assert_eq!(Some("client_id"), parsed.client_id.as_deref());
+    assert_eq!("app_id", parsed.app_id);
+    assert_eq!(1700000000, parsed.timestamp);
+    assert!(parsed.is_signed("app_secret", HashAlgorithm::Sha256));
+    assert!(!parsed.is_signed("other_secret", HashAlgorithm::Sha256));
+
+    // The expiration is checked by the clock of parsed token
+    let parsed = parsed.with_clock(FixedClock(1700000000 + 300));
+    assert!(!parsed.is_expired(300, None));
+    let parsed = parsed.with_clock(FixedClock(1700000000 + 3600));
+    assert!(parsed.is_expired(300, None));
+
+    Ok(())
+}
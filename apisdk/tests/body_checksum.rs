@@ -0,0 +1,91 @@
+use apisdk::{
+    async_trait, digest, send, send_json, ApiAuthenticator, ApiResult, BodyChecksum, Carrier,
+    CodeDataMessage, Request, TokenGenerator,
+};
+use serde_json::json;
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+/// This authenticator uses `Content-MD5` as token, to ensure the checksum is set before it runs
+struct EchoChecksum;
+
+#[async_trait]
+impl TokenGenerator for EchoChecksum {
+    async fn generate_token(&self, req: &Request) -> Result<String, reqwest_middleware::Error> {
+        Ok(req
+            .headers()
+            .get("content-md5")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string())
+    }
+}
+
+#[async_trait]
+impl ApiAuthenticator for EchoChecksum {
+    fn get_carrier(&self) -> &Carrier {
+        &Carrier::SchemalessAuth
+    }
+}
+
+impl TheApi {
+    async fn post_with_md5(&self) -> ApiResult<Payload> {
+        let req = self.post("/path/json").await?;
+        let req = req.with_extension(BodyChecksum::content_md5());
+        send_json!(req, json!({ "num": 1, "text": "string" }), CodeDataMessage).await
+    }
+
+    async fn get_with_sha256(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_content_md5() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    // `BodyChecksum` works for single request, without `with_body_checksum`
+    let api = TheApi::builder().with_authenticator(EchoChecksum).build();
+    let res = api.post_with_md5().await?;
+    let body = serde_json::to_vec(&json!({ "num": 1, "text": "string" }))?;
+    let expected = digest::md5_base64(body);
+    assert_eq!(Some(&expected), res.headers.get("content-md5"));
+    assert_eq!(Some(&expected), res.headers.get("authorization"));
+
+    let api = TheApi::builder()
+        .with_body_checksum(BodyChecksum::amz_content_sha256())
+        .with_authenticator(EchoChecksum)
+        .build();
+
+    // `BodyChecksum` overrides the default one for single request
+    let res = api.post_with_md5().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(None, res.headers.get("x-amz-content-sha256"));
+    assert_eq!(Some(&expected), res.headers.get("content-md5"));
+    assert_eq!(Some(&expected), res.headers.get("authorization"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_amz_content_sha256() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_body_checksum(BodyChecksum::amz_content_sha256())
+        .build();
+
+    let res = api.get_with_sha256().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        Some(&digest::sha256("")),
+        res.headers.get("x-amz-content-sha256")
+    );
+
+    Ok(())
+}
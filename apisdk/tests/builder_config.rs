@@ -0,0 +1,83 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use apisdk::{send, ApiError, ApiResult, CodeDataMessage};
+
+use crate::common::{init_logger, start_server, Payload, TheApi, TheApiBuilder};
+
+mod common;
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_default_header() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_default_header("X-Tenant", "apisdk")
+        .try_build()?;
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&"apisdk".to_string()), res.headers.get("x-tenant"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_default_header_name() -> ApiResult<()> {
+    init_logger();
+
+    let res = TheApi::builder()
+        .with_default_header("X Invalid", "value")
+        .try_build();
+    match res {
+        Err(ApiError::Config(message)) => {
+            log::debug!("message = {}", message);
+            assert!(message.contains("X Invalid"));
+        }
+        _ => panic!("should be Config error"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_conflicting_resolver_and_proxy() -> ApiResult<()> {
+    init_logger();
+
+    let res = TheApi::builder()
+        .with_resolver(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .with_proxy_url("http://127.0.0.1:3030")?
+        .try_build();
+    match res {
+        Err(ApiError::Config(message)) => {
+            log::debug!("message = {}", message);
+            assert!(message.contains("proxies"));
+        }
+        _ => panic!("should be Config error"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_base_url() -> ApiResult<()> {
+    init_logger();
+
+    assert!(matches!(
+        TheApiBuilder::try_new("not a url"),
+        Err(ApiError::Config(..))
+    ));
+    assert!(matches!(
+        TheApiBuilder::try_new("mailto:someone@example.com"),
+        Err(ApiError::Config(..))
+    ));
+
+    Ok(())
+}
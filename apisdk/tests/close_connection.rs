@@ -0,0 +1,36 @@
+use apisdk::{send, ApiResult, CloseConnection, CodeDataMessage};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch(&self, close: bool) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        let req = if close {
+            req.with_extension(CloseConnection)
+        } else {
+            req
+        };
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_close_connection() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.touch(true).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&"close".to_string()), res.headers.get("connection"));
+
+    // The closed connection may be pooled, so use a fresh client
+    let api = TheApi::builder().build();
+    let res = api.touch(false).await?;
+    assert_eq!(None, res.headers.get("connection"));
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use tracing::Level;
 use tracing_log::LogTracer;
 use tracing_subscriber::{
@@ -10,9 +12,12 @@ mod api;
 #[allow(unused)]
 mod server;
 
+#[allow(unused_imports)]
 pub use api::*;
+#[allow(unused_imports)]
 pub use server::*;
 
+#[allow(dead_code)]
 pub fn init_logger() {
     let registry = Registry::default().with(
         Layer::default()
@@ -24,3 +29,9 @@ pub fn init_logger() {
         let _ = LogTracer::init();
     }
 }
+
+/// Build a path in temp dir, which is unique to the test process
+#[allow(dead_code)]
+pub fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("apisdk-{}-{}", std::process::id(), name))
+}
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, time::Duration};
 
-use apisdk::{ApiError, ResponseBody};
+use apisdk::{digest, ApiError, ResponseBody};
+use bytes::Buf;
 use futures::StreamExt;
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::json;
@@ -42,48 +43,136 @@ pub async fn start_server() {
 }
 
 async fn do_start_server() {
-    tokio::spawn(async move {
-        let dump_json = warp::path!("v1" / "path" / "json")
-            .and(warp::path::full())
-            .and(warp::header::headers_cloned())
-            .and(warp::query())
-            .and_then(handle_json);
-        let dump_xml = warp::path!("v1" / "path" / "xml")
-            .and(warp::path::full())
-            .and(warp::header::headers_cloned())
-            .and(warp::query())
-            .and_then(handle_xml);
-        let dump_text = warp::path!("v1" / "path" / "text")
-            .and(warp::path::full())
-            .and(warp::header::headers_cloned())
-            .and(warp::query())
-            .and_then(handle_text);
-        let dump_form = warp::post()
-            .and(warp::path!("v1" / "path" / "form"))
-            .and(warp::path::full())
-            .and(warp::header::headers_cloned())
-            .and(warp::query())
-            .and(warp::body::form())
-            .and_then(handle_form);
-        let dump_multipart = warp::post()
-            .and(warp::path!("v1" / "path" / "multipart"))
-            .and(warp::path::full())
-            .and(warp::header::headers_cloned())
-            .and(warp::query())
-            .and(warp::multipart::form())
-            .and_then(handle_multipart);
-        let not_found = warp::path!("v1" / "not-found").and_then(handle_not_found);
-
-        warp::serve(
-            dump_json
-                .or(dump_xml)
-                .or(dump_text)
-                .or(dump_form)
-                .or(dump_multipart)
-                .or(not_found),
-        )
-        .run(([127, 0, 0, 1], PORT))
-        .await;
+    // Run the server in a dedicated runtime, so it outlives the runtime of each test
+    std::thread::spawn(|| {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to build runtime");
+        rt.block_on(async move {
+            let dump_json = warp::path!("v1" / "path" / "json")
+                .and(warp::path::full())
+                .and(warp::header::headers_cloned())
+                .and(warp::query())
+                .and_then(handle_json);
+            let dump_xml = warp::path!("v1" / "path" / "xml")
+                .and(warp::path::full())
+                .and(warp::header::headers_cloned())
+                .and(warp::query())
+                .and_then(handle_xml);
+            let dump_text = warp::path!("v1" / "path" / "text")
+                .and(warp::path::full())
+                .and(warp::header::headers_cloned())
+                .and(warp::query())
+                .and_then(handle_text);
+            let dump_form = warp::post()
+                .and(warp::path!("v1" / "path" / "form"))
+                .and(warp::path::full())
+                .and(warp::header::headers_cloned())
+                .and(warp::query())
+                .and(warp::body::form())
+                .and_then(handle_form);
+            let dump_multipart = warp::post()
+                .and(warp::path!("v1" / "path" / "multipart"))
+                .and(warp::path::full())
+                .and(warp::header::headers_cloned())
+                .and(warp::query())
+                .and(warp::multipart::form().max_length(None))
+                .and_then(handle_multipart);
+            let not_found = warp::path!("v1" / "not-found").and_then(handle_not_found);
+            let problem = warp::path!("v1" / "problem").and_then(handle_problem);
+            let empty = warp::path!("v1" / "path" / "empty").and_then(handle_empty);
+            let sse = warp::path!("v1" / "path" / "sse").and_then(handle_sse);
+            let html = warp::path!("v1" / "path" / "html").and_then(handle_html);
+            let text_json = warp::path!("v1" / "path" / "text-json").map(|| {
+                warp::reply::with_header(
+                    r#"{"code":0,"data":{"hello":"world"}}"#,
+                    "Content-Type",
+                    "text/plain",
+                )
+            });
+            let raw_json = warp::path!("v1" / "path" / "raw-json").map(|| {
+                warp::reply::with_header(
+                    "{\"zeta\": 1,\n  \"alpha\": [1, 2.50]}",
+                    "Content-Type",
+                    "application/json",
+                )
+            });
+            let bytes = warp::path!("v1" / "path" / "bytes").and_then(handle_bytes);
+            let untyped = warp::path!("v1" / "path" / "untyped" / String).and_then(handle_untyped);
+            let range = warp::path!("v1" / "path" / "range")
+                .and(warp::header::optional::<String>("range"))
+                .and_then(handle_range);
+            let signed = warp::path!("v1" / "path" / "signed").and_then(handle_signed);
+            let echo_body = warp::post()
+                .and(warp::path!("v1" / "path" / "echo-body"))
+                .and(warp::header::optional::<String>("content-type"))
+                .and(warp::body::bytes())
+                .and_then(handle_echo_body);
+            let resumable = warp::path!("v1" / "path" / "resumable")
+                .and(warp::header::optional::<String>("range"))
+                .and(warp::header::optional::<String>("if-range"))
+                .and_then(handle_resumable);
+            let items = warp::path!("v1" / "path" / "items")
+                .and(warp::query())
+                .and_then(handle_items);
+            let echo_ids = warp::path!("v1" / "path" / "echo-ids")
+                .and(warp::header::headers_cloned())
+                .and_then(handle_echo_ids);
+            let redirect = warp::path!("v1" / "path" / "redirect")
+                .map(|| warp::redirect::found(warp::http::Uri::from_static("/v1/path/json")));
+            let array = warp::path!("v1" / "path" / "array").map(|| {
+                warp::reply::with_header(
+                    warp::reply::json(&vec![1, 2, 3]),
+                    "X-RateLimit-Remaining",
+                    "42",
+                )
+            });
+            let etag = warp::path!("v1" / "path" / "etag")
+                .and(warp::header::optional::<String>("if-none-match"))
+                .and_then(handle_etag);
+            let protected = warp::path!("v1" / "path" / "protected")
+                .and(warp::header::optional::<String>("authorization"))
+                .and_then(handle_protected);
+            let allow = warp::path!("v1" / "path" / "allow")
+                .and(warp::options())
+                .and_then(handle_allow);
+            let graphql = warp::post()
+                .and(warp::path!("v1" / "graphql"))
+                .and(warp::body::json())
+                .and_then(handle_graphql);
+
+            warp::serve(
+                dump_json
+                    .or(dump_xml)
+                    .or(dump_text)
+                    .or(dump_form)
+                    .or(dump_multipart)
+                    .or(not_found)
+                    .or(problem)
+                    .or(empty)
+                    .or(sse)
+                    .or(html)
+                    .or(text_json)
+                    .or(raw_json)
+                    .or(untyped)
+                    .or(range)
+                    .or(echo_ids)
+                    .or(echo_body)
+                    .or(items)
+                    .or(resumable)
+                    .or(bytes)
+                    .or(signed)
+                    .or(redirect)
+                    .or(array)
+                    .or(etag)
+                    .or(protected)
+                    .or(allow)
+                    .or(graphql),
+            )
+            .run(([127, 0, 0, 1], PORT))
+            .await;
+        });
     });
 
     // Ensure the server is ready to work
@@ -148,6 +237,188 @@ async fn handle_text(
         .map_err(|_| warp::reject())
 }
 
+async fn handle_html() -> Result<impl Reply, warp::Rejection> {
+    warp::http::Response::builder()
+        .header("Content-Type", "text/html; charset=utf-8")
+        .body("<html><body>html goes here</body></html>")
+        .map_err(|_| warp::reject())
+}
+
+async fn handle_untyped(format: String) -> Result<impl Reply, warp::Rejection> {
+    let body = match format.as_str() {
+        "json" => r#"  {"code":0,"data":{"hello":"world"}}"#,
+        "xml" => "<root><code>0</code><data><hello>world</hello></data></root>",
+        _ => "text goes here",
+    };
+    warp::http::Response::builder()
+        .body(body)
+        .map_err(|_| warp::reject())
+}
+
+async fn handle_range(range: Option<String>) -> Result<impl Reply, warp::Rejection> {
+    const BODY: &[u8] = b"0123456789";
+    let range = range
+        .as_deref()
+        .and_then(|r| r.strip_prefix("bytes="))
+        .and_then(|r| r.split_once('-'))
+        .and_then(|(start, end)| {
+            let start: usize = start.parse().ok()?;
+            let end: usize = match end {
+                "" => BODY.len() - 1,
+                end => end.parse::<usize>().ok()?.min(BODY.len() - 1),
+            };
+            Some((start, end))
+        });
+    let res = match range {
+        Some((start, end)) => warp::http::Response::builder()
+            .status(206)
+            .header(
+                "Content-Range",
+                format!("bytes {}-{}/{}", start, end, BODY.len()),
+            )
+            .body(BODY[start..=end].to_vec()),
+        None => warp::http::Response::builder().body(BODY.to_vec()),
+    };
+    res.map_err(|_| warp::reject())
+}
+
+async fn handle_bytes() -> Result<impl Reply, warp::Rejection> {
+    warp::http::Response::builder()
+        .header("Content-Type", "image/png")
+        .header("Content-Disposition", "attachment; filename=\"image.png\"")
+        .header("Accept-Ranges", "bytes")
+        .header("ETag", "\"png-v1\"")
+        .body(vec![0x89, b'P', b'N', b'G', 0x00, 0xff])
+        .map_err(|_| warp::reject())
+}
+
+pub const SIGNATURE_SECRET: &str = "webhook-secret";
+
+async fn handle_resumable(
+    range: Option<String>,
+    if_range: Option<String>,
+) -> Result<impl Reply, warp::Rejection> {
+    const BODY: &[u8] = b"0123456789abcdef";
+    const ETAG: &str = "\"v1\"";
+    // Ignore `Range` if the resource has changed
+    let start = range
+        .filter(|_| if_range.as_deref().is_none_or(|v| v == ETAG))
+        .as_deref()
+        .and_then(|r| r.strip_prefix("bytes="))
+        .and_then(|r| r.strip_suffix('-'))
+        .and_then(|start| start.parse::<usize>().ok());
+    let res = warp::http::Response::builder().header("ETag", ETAG);
+    let res = match start {
+        Some(start) if start >= BODY.len() => res
+            .status(416)
+            .header("Content-Range", format!("bytes */{}", BODY.len()))
+            .body(vec![]),
+        Some(start) => res
+            .status(206)
+            .header(
+                "Content-Range",
+                format!("bytes {}-{}/{}", start, BODY.len() - 1, BODY.len()),
+            )
+            .body(BODY[start..].to_vec()),
+        None => res.body(BODY.to_vec()),
+    };
+    res.map_err(|_| warp::reject())
+}
+
+async fn handle_items(query: HashMap<String, String>) -> Result<impl Reply, warp::Rejection> {
+    // Only items with id in 1..=10 exist
+    let items: Vec<_> = query
+        .get("ids")
+        .map(|ids| ids.split(',').filter_map(|id| id.parse::<u64>().ok()))
+        .into_iter()
+        .flatten()
+        .filter(|id| (1..=10).contains(id))
+        .map(|id| json!({ "id": id, "name": format!("item-{}", id) }))
+        .collect();
+    Ok(warp::reply::json(&json!({ "code": 0, "data": items })))
+}
+
+async fn handle_echo_body(
+    content_type: Option<String>,
+    body: bytes::Bytes,
+) -> Result<impl Reply, warp::Rejection> {
+    let resp = json!({
+        "code": 0,
+        "data": {
+            "content_type": content_type,
+            "body": String::from_utf8_lossy(&body),
+        },
+    });
+    Ok(warp::reply::json(&resp))
+}
+
+async fn handle_echo_ids(headers: HeaderMap) -> Result<impl Reply, warp::Rejection> {
+    // Echo request id / trace id headers back, as a gateway does
+    let mut res = warp::http::Response::builder().header("Content-Type", "application/json");
+    for (name, value) in headers.iter() {
+        if name.as_str().ends_with("-id") || name.as_str() == "traceparent" {
+            res = res.header(name, value);
+        }
+    }
+    res.body(r#"{"code":0,"data":null}"#.to_string())
+        .map_err(|_| warp::reject())
+}
+
+async fn handle_signed() -> Result<impl Reply, warp::Rejection> {
+    let body = json!({ "event": "created", "id": 1 }).to_string();
+    warp::http::Response::builder()
+        .header("Content-Type", "application/json")
+        .header(
+            "X-Signature",
+            format!("sha256={}", digest::hmac_sha256(SIGNATURE_SECRET, &body)),
+        )
+        .body(body)
+        .map_err(|_| warp::reject())
+}
+
+pub const VALID_TOKEN: &str = "fresh-token";
+
+async fn handle_protected(authorization: Option<String>) -> Result<impl Reply, warp::Rejection> {
+    let status = match authorization {
+        Some(auth) if auth == format!("Bearer {}", VALID_TOKEN) => 200,
+        _ => 401,
+    };
+    warp::http::Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(json!({ "status": status }).to_string())
+        .map_err(|_| warp::reject())
+}
+
+async fn handle_allow() -> Result<impl Reply, warp::Rejection> {
+    warp::http::Response::builder()
+        .status(204)
+        .header("Allow", "GET, HEAD, OPTIONS")
+        .header("Access-Control-Allow-Methods", "POST, GET")
+        .body("")
+        .map_err(|_| warp::reject())
+}
+
+pub const ETAG: &str = "\"v1\"";
+
+async fn handle_etag(if_none_match: Option<String>) -> Result<impl Reply, warp::Rejection> {
+    let mut builder = warp::http::Response::builder().header("ETag", ETAG);
+    // Echo the conditional header, since it's set after all custom middlewares
+    if let Some(if_none_match) = if_none_match.as_deref() {
+        builder = builder.header("X-If-None-Match", if_none_match);
+    }
+    if if_none_match.as_deref() == Some(ETAG) {
+        return builder
+            .status(304)
+            .body(String::new())
+            .map_err(|_| warp::reject());
+    }
+    builder
+        .header("Content-Type", "application/json")
+        .body(json!({ "version": 1 }).to_string())
+        .map_err(|_| warp::reject())
+}
+
 async fn handle_form(
     path: FullPath,
     headers: HeaderMap,
@@ -191,13 +462,22 @@ async fn handle_multipart(
         }
     }
     let mut parts = HashMap::new();
-    while let Some(Ok(part)) = multipart.next().await {
+    let mut files = HashMap::new();
+    while let Some(Ok(mut part)) = multipart.next().await {
+        let name = part.name().to_string();
         parts.insert(
-            part.name().to_string(),
+            name.clone(),
             part.content_type()
                 .map(|v| v.to_string())
                 .unwrap_or_default(),
         );
+        if let Some(filename) = part.filename().map(|v| v.to_string()) {
+            let mut size = 0;
+            while let Some(Ok(chunk)) = part.data().await {
+                size += chunk.remaining();
+            }
+            files.insert(name, json!({ "filename": filename, "size": size }));
+        }
     }
     let resp = json!({
         "code": 0,
@@ -207,6 +487,7 @@ async fn handle_multipart(
             "headers": headers_map,
             "query": query,
             "multipart": parts,
+            "files": files,
         },
         "extra-field": "extra"
     });
@@ -217,6 +498,60 @@ async fn handle_not_found() -> Result<String, warp::Rejection> {
     Err(warp::reject::not_found())
 }
 
+async fn handle_problem() -> Result<impl Reply, warp::Rejection> {
+    warp::http::Response::builder()
+        .status(403)
+        .header("Content-Type", "application/problem+json")
+        .body(
+            json!({
+                "type": "https://example.com/probs/out-of-credit",
+                "title": "You do not have enough credit.",
+                "status": 403,
+                "detail": "Your current balance is 30, but that costs 50.",
+                "balance": 30
+            })
+            .to_string(),
+        )
+        .map_err(|_| warp::reject())
+}
+
+async fn handle_empty() -> Result<impl Reply, warp::Rejection> {
+    warp::http::Response::builder()
+        .status(200)
+        .body("")
+        .map_err(|_| warp::reject())
+}
+
+async fn handle_sse() -> Result<impl Reply, warp::Rejection> {
+    warp::http::Response::builder()
+        .header("Content-Type", "text/event-stream")
+        .body("event: message\ndata: hello\nid: 1\n\ndata: line 1\ndata: line 2\n\n")
+        .map_err(|_| warp::reject())
+}
+
+async fn handle_graphql(body: serde_json::Value) -> Result<impl Reply, warp::Rejection> {
+    let resp = match body.pointer("/variables/id").and_then(|v| v.as_i64()) {
+        Some(id) if id > 0 => json!({
+            "data": {
+                "user": {
+                    "id": id,
+                    "query": body["query"],
+                    "operationName": body["operationName"],
+                }
+            }
+        }),
+        _ => json!({
+            "data": null,
+            "errors": [{
+                "message": "User not found",
+                "locations": [{ "line": 1, "column": 3 }],
+                "path": ["user"],
+            }]
+        }),
+    };
+    Ok(warp::reply::json(&resp))
+}
+
 #[tokio::test]
 #[ignore]
 async fn standalone_server() {
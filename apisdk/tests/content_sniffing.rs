@@ -0,0 +1,82 @@
+use apisdk::{send, ApiResult, JsonOrXml};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct XmlData {
+    code: i64,
+    data: DataNode,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct DataNode {
+    hello: String,
+}
+
+impl TheApi {
+    async fn get_untyped(&self, format: &str) -> ApiResult<JsonOrXml<Value, XmlData>> {
+        let req = self.get(format!("/path/untyped/{}", format)).await?;
+        send!(req, Body).await
+    }
+
+    async fn get_untyped_text(&self) -> ApiResult<String> {
+        let req = self.get("/path/untyped/text").await?;
+        send!(req, Text).await
+    }
+
+    async fn get_text_json(&self) -> ApiResult<JsonOrXml<Value, XmlData>> {
+        let req = self.get("/path/text-json").await?;
+        send!(req, Body).await
+    }
+}
+
+#[tokio::test]
+async fn test_content_sniffing_disabled() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.get_untyped("json").await;
+    assert!(res.is_err(), "payload should be treated as text");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_content_sniffing() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_content_sniffing(true).build();
+
+    match api.get_untyped("json").await? {
+        JsonOrXml::Json(json) => assert_eq!(json!({ "hello": "world" }), json["data"]),
+        JsonOrXml::Xml(_) => panic!("payload should be sniffed as json"),
+    }
+
+    let res = api.get_untyped("xml").await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        JsonOrXml::Xml(XmlData {
+            code: 0,
+            data: DataNode {
+                hello: "world".to_string()
+            }
+        }),
+        res
+    );
+
+    let text = api.get_untyped_text().await?;
+    assert_eq!("text goes here", text);
+
+    // `text/plain` is respected
+    let res = api.get_text_json().await;
+    assert!(res.is_err(), "payload should be treated as text");
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
-use apisdk::ApiResult;
+use std::time::Duration;
 
-use crate::common::{init_logger, TheApi};
+use apisdk::{send, AccessTokenAuth, ApiResult, CodeDataMessage, Method};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
 
 mod common;
 
@@ -10,6 +12,19 @@ impl TheApi {
         log::info!("url = {:?}", url);
         Ok(())
     }
+
+    async fn touch_absolute(&self, url: &str, authenticate: bool) -> ApiResult<Payload> {
+        let req = self
+            .core
+            .build_request_absolute(Method::GET, url, authenticate)
+            .await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
 }
 
 // #[tokio::test]
@@ -41,3 +56,99 @@ async fn test_via_core() -> ApiResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_clone_core() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::default();
+    let core = api.core.as_ref().clone();
+
+    let handle = tokio::spawn(async move { core.build_url("path").await });
+    let url = handle.await.expect("task should not panic")?;
+    assert_eq!(api.core.build_url("path").await?, url);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_http1_only() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_http1_only().build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_http2_prior_knowledge() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_http2_prior_knowledge().build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pool_options() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_pool_max_idle_per_host(1)
+        .with_pool_idle_timeout(Duration::from_secs(10))
+        .with_tcp_keepalive(Duration::from_secs(30))
+        .build();
+
+    for _ in 0..3 {
+        let res = api.touch().await?;
+        log::debug!("res = {:?}", res);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_build_request_absolute() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_authenticator(AccessTokenAuth::new("fixed"))
+        .with_default_header("x-tenant", "apisdk")
+        .build();
+    let url = "http://127.0.0.1:3030/v1/path/json?page=2";
+
+    let res = api.touch_absolute(url, true).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res.path);
+    assert_eq!(Some("2"), res.query.get("page").map(|v| v.as_str()));
+    assert_eq!(
+        Some("Bearer fixed"),
+        res.headers.get("authorization").map(|v| v.as_str())
+    );
+    assert_eq!(
+        Some("apisdk"),
+        res.headers.get("x-tenant").map(|v| v.as_str())
+    );
+
+    // Skip authenticator, e.g. for pre-signed url
+    let res = api.touch_absolute(url, false).await?;
+    assert_eq!(None, res.headers.get("authorization"));
+    assert_eq!(
+        Some("apisdk"),
+        res.headers.get("x-tenant").map(|v| v.as_str())
+    );
+
+    assert!(api.touch_absolute("/relative/path", true).await.is_err());
+
+    Ok(())
+}
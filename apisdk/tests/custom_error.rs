@@ -0,0 +1,54 @@
+use apisdk::{send, ApiError, ApiResult, CodeDataMessage};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+#[derive(Debug, thiserror::Error)]
+enum MyError {
+    #[error("Api error: {0}")]
+    Api(#[from] ApiError),
+    #[error("Missing header: {0}")]
+    MissingHeader(String),
+}
+
+impl TheApi {
+    async fn touch_custom(&self, path: &str) -> Result<String, MyError> {
+        let req = self.get(path).await?;
+        let res: Payload = send!(req, CodeDataMessage).await?;
+        res.headers
+            .get("x-custom")
+            .cloned()
+            .ok_or_else(|| MyError::MissingHeader("x-custom".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn test_custom_error() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let e = api.touch_custom("/path/json").await.unwrap_err();
+    log::debug!("e = {:?}", e);
+    assert!(matches!(e, MyError::MissingHeader(_)));
+
+    let e = api.touch_custom("/not-found").await.unwrap_err();
+    log::debug!("e = {:?}", e);
+    assert!(matches!(e, MyError::Api(ApiError::HttpClientStatus(..))));
+
+    Ok(())
+}
+
+#[test]
+fn test_into_anyhow() {
+    let e = ApiError::from_status(503, "Service Unavailable").into_anyhow();
+    assert_eq!(
+        "HTTP Server status error: [503] Service Unavailable",
+        e.to_string()
+    );
+    assert!(e
+        .downcast_ref::<ApiError>()
+        .is_some_and(|e| e.is_server_error()));
+}
@@ -6,7 +6,7 @@ use common::init_logger;
 
 mod common;
 
-#[http_api("http://localhost:3030/v1", no_default)]
+#[http_api("http://localhost:3030/v1", no_default, clone)]
 #[derive(Debug)]
 struct ComplexApi {
     something_must_init: String,
@@ -30,3 +30,69 @@ async fn test_ctor() -> ApiResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_with_base_url() -> ApiResult<()> {
+    init_logger();
+
+    let api = ComplexApi::new("keep");
+    let rebased = api.with_base_url("http://other:3030/v2")?;
+    log::debug!("rebased = {:?}", rebased);
+    assert_eq!("keep", rebased.something_must_init);
+    assert_eq!(
+        "http://other:3030/v2/path",
+        rebased.build_url("/path").await?.as_str()
+    );
+    assert_eq!(
+        "http://localhost:3030/v1/path",
+        api.build_url("/path").await?.as_str()
+    );
+
+    assert!(api.with_base_url("not a url").is_err());
+
+    Ok(())
+}
+
+#[http_api("http://localhost:3030/v1", no_default)]
+#[derive(Debug)]
+struct NotCloneableApi {
+    file: Option<File>,
+}
+
+#[http_api("http://localhost:3030/v1")]
+#[derive(Debug)]
+struct ManualCloneApi;
+
+/// Clone is not generated without `clone`, so it could be implemented manually
+impl Clone for ManualCloneApi {
+    fn clone(&self) -> Self {
+        Self {
+            core: self.core.clone(),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_clone() -> ApiResult<()> {
+    init_logger();
+
+    // Clone is generated, since `clone` is set
+    let api = ComplexApi::new("keep");
+    let cloned = api.clone();
+    assert_eq!("keep", cloned.something_must_init);
+    assert!(std::sync::Arc::ptr_eq(&api.core, &cloned.core));
+
+    let api = ManualCloneApi::default();
+    let cloned = api.clone();
+    assert!(std::sync::Arc::ptr_eq(&api.core, &cloned.core));
+
+    // Clone and with_base_url are not available, but the api is still usable
+    let api = NotCloneableApi {
+        core: NotCloneableApi::builder().build_core(),
+        file: None,
+    };
+    assert!(api.file.is_none());
+    api.build_url("/path").await?;
+
+    Ok(())
+}
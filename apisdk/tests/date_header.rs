@@ -0,0 +1,84 @@
+use apisdk::{
+    header::DATE, send, ApiAuthenticator, ApiResult, CodeDataMessage, DateHeader, TokenGenerator,
+};
+use async_trait::async_trait;
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+struct DateSigner;
+
+#[async_trait]
+impl TokenGenerator for DateSigner {
+    async fn generate_token(
+        &self,
+        req: &apisdk::Request,
+    ) -> Result<String, apisdk::MiddlewareError> {
+        let dates: Vec<_> = req
+            .headers()
+            .get_all(DATE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .collect();
+        Ok(format!("signed:{}", dates.join(",")))
+    }
+}
+
+impl ApiAuthenticator for DateSigner {}
+
+impl TheApi {
+    async fn touch_signed(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn touch_signed_with_date(&self, date: &str) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req.header(DATE, date), CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_date_header_signed() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_initialiser(DateHeader)
+        .with_authenticator(DateSigner)
+        .build();
+
+    let res = api.touch_signed().await?;
+    log::debug!("res = {:?}", res);
+    let date = res.headers.get("date").expect("Date header should be sent");
+    assert_eq!(
+        Some(&format!("Bearer signed:{}", date)),
+        res.headers.get("authorization")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_date_header_explicit() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_initialiser(DateHeader)
+        .with_authenticator(DateSigner)
+        .build();
+
+    // The explicit `Date` is kept, and no other one is sent along with it
+    let date = "Wed, 21 Oct 2015 07:28:00 GMT";
+    let res = api.touch_signed_with_date(date).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&date.to_string()), res.headers.get("date"));
+    assert_eq!(
+        Some(&format!("Bearer signed:{}", date)),
+        res.headers.get("authorization")
+    );
+
+    Ok(())
+}
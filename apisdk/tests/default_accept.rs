@@ -0,0 +1,71 @@
+use apisdk::{header::ACCEPT, send, ApiResult, CodeDataMessage, DefaultAccept, MimeType};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch_accept(&self, accept: Option<&str>) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        let req = match accept {
+            Some(accept) => req.header(ACCEPT, accept),
+            None => req,
+        };
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_default_accept() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+    let res = api.touch_accept(None).await?;
+    assert_eq!(Some(&"*/*".to_string()), res.headers.get("accept"));
+
+    // `DefaultAccept` works for single request, without `with_default_accept`
+    let req = api.get("/path/json").await?;
+    let req = req.with_extension(DefaultAccept(MimeType::Xml));
+    let res: Payload = send!(req, CodeDataMessage).await?;
+    assert_eq!(
+        Some(&"application/xml".to_string()),
+        res.headers.get("accept")
+    );
+
+    // `DefaultAccept` works as initialiser
+    let api = TheApi::builder()
+        .with_initialiser(DefaultAccept(MimeType::Json))
+        .build();
+    let res = api.touch_accept(None).await?;
+    assert_eq!(
+        Some(&"application/json".to_string()),
+        res.headers.get("accept")
+    );
+
+    let api = TheApi::builder()
+        .with_default_accept(MimeType::Json)
+        .build();
+
+    let res = api.touch_accept(None).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        Some(&"application/json".to_string()),
+        res.headers.get("accept")
+    );
+
+    // Explicit `Accept` is not overridden
+    let res = api.touch_accept(Some("text/csv")).await?;
+    assert_eq!(Some(&"text/csv".to_string()), res.headers.get("accept"));
+
+    // `DefaultAccept` overrides the mime for single request
+    let req = api.get("/path/json").await?;
+    let req = req.with_extension(DefaultAccept(MimeType::Xml));
+    let res: Payload = send!(req, CodeDataMessage).await?;
+    assert_eq!(
+        Some(&"application/xml".to_string()),
+        res.headers.get("accept")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use std::sync::{Arc, Mutex};
+
+use apisdk::{
+    header::{HeaderMap, HeaderValue},
+    send, ApiResult, CodeDataMessage,
+};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch_headers(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_set_default_headers() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.touch_headers().await?;
+    assert_eq!(None, res.headers.get("x-tenant"));
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-tenant", HeaderValue::from_static("apisdk"));
+    api.core.set_default_headers(headers);
+
+    let res = api.touch_headers().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&"apisdk".to_string()), res.headers.get("x-tenant"));
+
+    api.core.remove_default_header("x-tenant");
+
+    let res = api.touch_headers().await?;
+    assert_eq!(None, res.headers.get("x-tenant"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_default_headers_override() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let captured = Arc::new(Mutex::new(vec![]));
+    let api = {
+        let captured = captured.clone();
+        TheApi::builder()
+            .with_middleware_fn(move |req, extensions, next| {
+                *captured.lock().unwrap() = req
+                    .headers()
+                    .get_all("x-tenant")
+                    .iter()
+                    .map(|v| v.to_str().unwrap_or_default().to_string())
+                    .collect();
+                Box::pin(next.run(req, extensions))
+            })
+            .build()
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-tenant", HeaderValue::from_static("apisdk"));
+    api.core.set_default_headers(headers);
+
+    let res = api.touch_headers().await?;
+    assert_eq!(Some(&"apisdk".to_string()), res.headers.get("x-tenant"));
+    assert_eq!(vec!["apisdk".to_string()], *captured.lock().unwrap());
+
+    // The header set on request replaces the default one
+    let req = api.get("/path/json").await?;
+    let req = req.header("x-tenant", "override");
+    let res: Payload = send!(req, CodeDataMessage).await?;
+    assert_eq!(Some(&"override".to_string()), res.headers.get("x-tenant"));
+    assert_eq!(vec!["override".to_string()], *captured.lock().unwrap());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_default_headers() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_default_header("x-tenant", "base")
+        .with_default_header("x-app", "apisdk")
+        .build();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-tenant", HeaderValue::from_static("tenant-a"));
+    headers.insert("x-region", HeaderValue::from_static("eu"));
+    let tenant = TheApi {
+        core: std::sync::Arc::new(api.core.with_default_headers(headers)),
+    };
+
+    let res = tenant.touch_headers().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&"tenant-a".to_string()), res.headers.get("x-tenant"));
+    assert_eq!(Some(&"eu".to_string()), res.headers.get("x-region"));
+    assert_eq!(Some(&"apisdk".to_string()), res.headers.get("x-app"));
+
+    // The headers set by caller are kept
+    let req = tenant.get("/path/json").await?;
+    let req = req.headers({
+        let mut headers = HeaderMap::new();
+        headers.insert("x-region", HeaderValue::from_static("us"));
+        headers
+    });
+    let res: Payload = send!(req, CodeDataMessage).await?;
+    assert_eq!(Some(&"us".to_string()), res.headers.get("x-region"));
+
+    // The base api is not affected
+    let res = api.touch_headers().await?;
+    assert_eq!(Some(&"base".to_string()), res.headers.get("x-tenant"));
+    assert_eq!(None, res.headers.get("x-region"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_default_headers_override() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let captured = Arc::new(Mutex::new(vec![]));
+    let api = {
+        let captured = captured.clone();
+        TheApi::builder()
+            .with_default_header("x-tenant", "base")
+            .with_middleware_fn(move |req, extensions, next| {
+                *captured.lock().unwrap() = req
+                    .headers()
+                    .get_all("x-tenant")
+                    .iter()
+                    .map(|v| v.to_str().unwrap_or_default().to_string())
+                    .collect();
+                Box::pin(next.run(req, extensions))
+            })
+            .build()
+    };
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-tenant", HeaderValue::from_static("tenant-a"));
+    let tenant = TheApi {
+        core: Arc::new(api.core.with_default_headers(headers)),
+    };
+
+    let res = tenant.touch_headers().await?;
+    assert_eq!(Some(&"tenant-a".to_string()), res.headers.get("x-tenant"));
+    assert_eq!(vec!["tenant-a".to_string()], *captured.lock().unwrap());
+
+    // The header set on request replaces both the overlay and default ones
+    let req = tenant.get("/path/json").await?;
+    let req = req.header("x-tenant", "override");
+    let res: Payload = send!(req, CodeDataMessage).await?;
+    assert_eq!(Some(&"override".to_string()), res.headers.get("x-tenant"));
+    assert_eq!(vec!["override".to_string()], *captured.lock().unwrap());
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use apisdk::{download_to_file, ApiError, ApiResult, MockServer, ResponseBody};
+
+use crate::common::{init_logger, start_server, temp_file, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn download_file(&self, path: &Path) -> ApiResult<u64> {
+        let req = self.get("/path/bytes").await?;
+        download_to_file!(req, path).await
+    }
+
+    async fn download_file_mock(&self, path: &Path) -> ApiResult<u64> {
+        let req = self.get("/path/bytes").await?;
+        let req = req.with_extension(MockServer::new(|_| {
+            Ok(ResponseBody::Text("mocked".to_string()))
+        }));
+        download_to_file!(req, path).await
+    }
+
+    async fn download_file_not_found(&self, path: &Path) -> ApiResult<u64> {
+        let req = self.get("/not-found").await?;
+        download_to_file!(req, path).await
+    }
+}
+
+#[tokio::test]
+async fn test_download_to_file() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let path = temp_file("download.png");
+    let written = api.download_file(&path).await?;
+    log::debug!("written = {}", written);
+    assert_eq!(6, written);
+    assert_eq!(
+        vec![0x89, b'P', b'N', b'G', 0x00, 0xff],
+        std::fs::read(&path)?
+    );
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_download_to_file_mock() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let path = temp_file("download.txt");
+    let written = api.download_file_mock(&path).await?;
+    assert_eq!(6, written);
+    assert_eq!(b"mocked".to_vec(), std::fs::read(&path)?);
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_download_to_file_not_found() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let path = temp_file("not-found.bin");
+    let res = api.download_file_not_found(&path).await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::HttpClientStatus(..))));
+    assert!(!path.exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_download_to_file_io_error() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let path = temp_file("missing-dir").join("download.png");
+    let res = api.download_file(&path).await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::Io(..))));
+
+    Ok(())
+}
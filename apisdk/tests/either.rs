@@ -0,0 +1,75 @@
+use apisdk::{send, ApiError, ApiResult, Either, MockServer, ResponseBody};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Single {
+    id: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Multiple {
+    items: Vec<Single>,
+}
+
+impl TheApi {
+    async fn get_either(&self, payload: Value) -> ApiResult<Either<Single, Multiple>> {
+        let req = self.get("/path/json").await?;
+        let req = req.with_extension(MockServer::new(move |_| {
+            Ok(ResponseBody::Json(payload.clone()))
+        }));
+        send!(req, Either<Single, Multiple>).await
+    }
+}
+
+#[tokio::test]
+async fn test_either_left() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.get_either(json!({"id": 1})).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Either::Left(Single { id: 1 }), res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_either_right() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api
+        .get_either(json!({"items": [{"id": 1}, {"id": 2}]}))
+        .await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.is_right());
+    assert_eq!(2, res.right().map(|m| m.items.len()).unwrap_or_default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_either_neither() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.get_either(json!({"name": "unknown"})).await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::DecodeJson(_)) => {}
+        _ => panic!("should be DecodeJson error"),
+    }
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
-use apisdk::{send, ApiError, ApiResult, CodeDataMessage, JsonExtractor};
+use apisdk::{
+    send, ApiError, ApiResult, CodeDataMessage, JsonExtractor, MimeType, MockServer, ResponseBody,
+    WithMessage,
+};
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::common::{init_logger, start_server, TheApi};
 
@@ -53,7 +56,57 @@ impl JsonExtractor for NoHeaders {
     }
 }
 
+/// This extractor reads headers from non-object payload
+#[derive(Debug, Deserialize)]
+struct ArrayWithHeaders(Value);
+
+impl JsonExtractor for ArrayWithHeaders {
+    fn require_headers() -> bool {
+        true
+    }
+
+    fn try_extract<T>(mut self) -> ApiResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        assert_eq!(
+            Some("42"),
+            self.0
+                .pointer("/__headers__/x-ratelimit-remaining")
+                .and_then(|v| v.as_str())
+        );
+        match self.0.get_mut("__body__") {
+            Some(body) => serde_json::from_value(body.take()).map_err(ApiError::DecodeJson),
+            None => Err(ApiError::IllegalJson(self.0)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: u64,
+}
+
 impl TheApi {
+    async fn extract_cdm_mocked<T>(&self, payload: Value) -> ApiResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let req = self.get("/path/json").await?;
+        let req = req.with_extension(MockServer::new(move |_| {
+            Ok(ResponseBody::Json(payload.clone()))
+        }));
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn extract_with_message_mocked(&self, payload: Value) -> ApiResult<WithMessage<User>> {
+        let req = self.get("/path/json").await?;
+        let req = req.with_extension(MockServer::new(move |_| {
+            Ok(ResponseBody::Json(payload.clone()))
+        }));
+        send!(req, WithMessage).await
+    }
+
     async fn get_json_2_string(&self) -> ApiResult<String> {
         let req = self.get("/path/json").await?;
         send!(req, Json).await
@@ -84,6 +137,26 @@ impl TheApi {
         send!(req, HasHeaders).await
     }
 
+    async fn extract_array_with_headers(&self) -> ApiResult<Vec<u32>> {
+        let req = self.get("/path/array").await?;
+        send!(req, ArrayWithHeaders).await
+    }
+
+    async fn extract_cdm_2_mismatch(&self) -> ApiResult<Vec<u32>> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn extract_value_2_mismatch(&self) -> ApiResult<u32> {
+        let req = self.get("/path/json").await?;
+        send!(req, Value).await
+    }
+
+    async fn get_array_as_body(&self) -> ApiResult<Value> {
+        let req = self.get("/path/array").await?;
+        send!(req, Body).await
+    }
+
     async fn extract_custom_no_headers(&self) -> ApiResult<Value> {
         let req = self.get("/path/json").await?;
         send!(req, NoHeaders).await
@@ -175,3 +248,127 @@ async fn test_extract_custom() -> ApiResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_extract_array_with_headers() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.extract_array_with_headers().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(vec![1, 2, 3], res);
+
+    let res = api.get_array_as_body().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(json!([1, 2, 3]), res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extract_mismatch() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.extract_cdm_2_mismatch().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::DecodeJson(_))));
+
+    let res = api.extract_value_2_mismatch().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::DecodeJson(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extract_cdm_data_shape() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // data: {}
+    let res = api
+        .extract_cdm_mocked::<User>(json!({ "code": 0, "data": {} }))
+        .await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::DecodeJson(_))));
+    let res = api
+        .extract_cdm_mocked::<Option<User>>(json!({ "code": 0, "data": {} }))
+        .await?;
+    assert_eq!(None, res);
+
+    // data: null
+    let res = api
+        .extract_cdm_mocked::<User>(json!({ "code": 0, "data": null }))
+        .await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(
+        res,
+        Err(ApiError::DecodeResponse(MimeType::Json, _))
+    ));
+    let res = api
+        .extract_cdm_mocked::<Option<User>>(json!({ "code": 0, "data": null }))
+        .await?;
+    assert_eq!(None, res);
+
+    // missing data
+    let res = api.extract_cdm_mocked::<User>(json!({ "code": 0 })).await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(
+        res,
+        Err(ApiError::DecodeResponse(MimeType::Json, _))
+    ));
+
+    // data: {...}
+    let res = api
+        .extract_cdm_mocked::<User>(json!({ "code": 0, "data": { "id": 1 } }))
+        .await?;
+    assert_eq!(User { id: 1 }, res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extract_with_message() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // code: 0
+    let res = api
+        .extract_with_message_mocked(json!({ "code": 0, "data": { "id": 1 }, "message": "ok" }))
+        .await?;
+    assert_eq!(User { id: 1 }, res.data);
+    assert_eq!(Some("ok"), res.message.as_deref());
+
+    // non-zero code with data
+    let res = api
+        .extract_with_message_mocked(json!({
+            "code": 1,
+            "data": { "id": 2 },
+            "msg": "Saved, but some items skipped"
+        }))
+        .await?;
+    assert_eq!(1, res.code);
+    assert_eq!(User { id: 2 }, res.data);
+    assert_eq!(
+        Some("Saved, but some items skipped"),
+        res.message.as_deref()
+    );
+
+    // non-zero code without data
+    let res = api
+        .extract_with_message_mocked(json!({ "code": 500, "message": "failed" }))
+        .await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::ServiceError(500, Some(m))) if m == "failed"));
+
+    Ok(())
+}
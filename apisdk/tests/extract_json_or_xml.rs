@@ -0,0 +1,78 @@
+use apisdk::{send, ApiError, ApiResult, JsonOrXml, MockServer, ResponseBody};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+struct Data {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename = "Error")]
+struct XmlError {
+    #[serde(rename = "Code")]
+    code: i64,
+    #[serde(rename = "Message")]
+    message: String,
+}
+
+impl From<XmlError> for ApiError {
+    fn from(e: XmlError) -> Self {
+        ApiError::new(e.code, e.message)
+    }
+}
+
+impl TheApi {
+    async fn touch(&self, body: ResponseBody) -> ApiResult<Data> {
+        let req = self.get("/path/json").await?;
+        let req = req.with_extension(MockServer::new(move |_| Ok(body.clone())));
+        let res: JsonOrXml<Data, XmlError> = send!(req, Body).await?;
+        res.into_result()
+    }
+}
+
+#[tokio::test]
+async fn test_json_success() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api
+        .touch(ResponseBody::Json(json!({ "key": "value" })))
+        .await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        Data {
+            key: "value".to_string()
+        },
+        res
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_xml_error() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api
+        .touch(ResponseBody::Xml(
+            "<Error><Code>1001</Code><Message>Invalid key</Message></Error>".to_string(),
+        ))
+        .await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::ServiceError(1001, Some(message))) => assert_eq!("Invalid key", message),
+        _ => panic!("should be ServiceError"),
+    }
+
+    Ok(())
+}
@@ -78,8 +78,7 @@ async fn test_touch_unit() -> ApiResult<()> {
 
     let api = TheApi::builder().build();
 
-    let res = api.touch_unit().await?;
-    log::debug!("res = {:?}", res);
+    api.touch_unit().await?;
 
     Ok(())
 }
@@ -1,4 +1,4 @@
-use apisdk::{send, ApiResult};
+use apisdk::{send, ApiError, ApiResult, MimeType};
 
 use crate::common::{init_logger, start_server, TheApi};
 
@@ -9,6 +9,21 @@ impl TheApi {
         let req = self.get("/path/text").await?;
         send!(req, Text).await
     }
+
+    async fn get_html(&self) -> ApiResult<String> {
+        let req = self.get("/path/html").await?;
+        send!(req, Html).await
+    }
+
+    async fn get_text_as_html(&self) -> ApiResult<String> {
+        let req = self.get("/path/text").await?;
+        send!(req, Html).await
+    }
+
+    async fn get_html_as_text(&self) -> ApiResult<String> {
+        let req = self.get("/path/html").await?;
+        send!(req, Text).await
+    }
 }
 
 #[tokio::test]
@@ -20,6 +35,35 @@ async fn test_extract_text_string() -> ApiResult<()> {
 
     let res = api.get_string().await?;
     log::debug!("res = {:?}", res);
+    assert_eq!("text goes here", res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_extract_html() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.get_html().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.starts_with("<html>"));
+
+    let res = api.get_html_as_text().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.starts_with("<html>"));
+
+    let res = api.get_text_as_html().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(
+        res,
+        Err(ApiError::IncompatibleContentType(
+            MimeType::Html,
+            MimeType::Text
+        ))
+    ));
 
     Ok(())
 }
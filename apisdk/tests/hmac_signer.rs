@@ -0,0 +1,37 @@
+use apisdk::{send_json, ApiResult, CodeDataMessage, HmacSigner, Method, Request, Url};
+use serde_json::json;
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn post_signed(&self) -> ApiResult<Payload> {
+        let req = self.post("/path/json").await?;
+        let req = req.query(&[("b", "2"), ("a", "1")]);
+        send_json!(req, json!({ "num": 1 }), CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_hmac_signer() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let signer = HmacSigner::new("secret");
+    let api = TheApi::builder().with_authenticator(signer.clone()).build();
+
+    let res = api.post_signed().await?;
+    log::debug!("res = {:?}", res);
+    let timestamp: u64 = res.headers.get("x-timestamp").unwrap().parse().unwrap();
+
+    let mut req = Request::new(
+        Method::POST,
+        Url::parse("http://localhost:3030/v1/path/json?b=2&a=1").unwrap(),
+    );
+    *req.body_mut() = Some(serde_json::to_vec(&json!({ "num": 1 }))?.into());
+    let expected = signer.sign(&req, timestamp).unwrap();
+    assert_eq!(Some(&expected), res.headers.get("x-signature"));
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::{net::SocketAddr, time::Duration};
+
+use apisdk::{send, ApiResult, CodeDataMessage, DnsResolver, HostsFile};
+
+use crate::common::{init_logger, start_server, temp_file, Payload, TheApi, TheApiBuilder};
+
+mod common;
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_hosts_file() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let path = temp_file("hosts");
+    std::fs::write(&path, "# test\napi.hosts.test 127.0.0.1\n")?;
+
+    let resolver = HostsFile::new(&path)?;
+    let api = TheApiBuilder::new("http://api.hosts.test:3030/v1")
+        .with_resolver(resolver)
+        .build();
+
+    let res = api.touch().await;
+    let _ = std::fs::remove_file(&path);
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res?.path);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hosts_file_reload() -> ApiResult<()> {
+    init_logger();
+
+    let path = temp_file("hosts-reload");
+    std::fs::write(&path, "api.hosts.test 127.0.0.1:80\n")?;
+
+    let resolver = HostsFile::new(&path)?.with_ttl(Duration::ZERO);
+    let first: Vec<SocketAddr> = resolver.resolve("api.hosts.test").await.unwrap().collect();
+    assert!(resolver.resolve("other.hosts.test").await.is_none());
+
+    std::fs::write(&path, "api.hosts.test 127.0.0.2:80\n")?;
+    let second: Vec<SocketAddr> = resolver.resolve("api.hosts.test").await.unwrap().collect();
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(vec!["127.0.0.1:80".parse::<SocketAddr>().unwrap()], first);
+    assert_eq!(vec!["127.0.0.2:80".parse::<SocketAddr>().unwrap()], second);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hosts_file_reload_failed() -> ApiResult<()> {
+    init_logger();
+
+    let path = temp_file("hosts-reload-failed");
+    std::fs::write(&path, "api.hosts.test 127.0.0.1:80\n")?;
+
+    let resolver = HostsFile::new(&path)?.with_ttl(Duration::ZERO);
+    std::fs::remove_file(&path)?;
+
+    // Previous entries are kept, if the file could not be reloaded
+    let addrs: Vec<SocketAddr> = resolver.resolve("api.hosts.test").await.unwrap().collect();
+    assert_eq!(vec!["127.0.0.1:80".parse::<SocketAddr>().unwrap()], addrs);
+
+    Ok(())
+}
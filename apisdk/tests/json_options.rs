@@ -0,0 +1,56 @@
+use apisdk::{send_json, ApiResult, JsonOptions, MockServer, ResponseBody};
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn echo_json(&self) -> ApiResult<Value> {
+        let req = self.post("/path/json").await?;
+        let req = req.with_extension(MockServer::new(|req: apisdk::Request| {
+            let body = req
+                .body()
+                .and_then(|b| b.as_bytes())
+                .map(|b| b.to_vec())
+                .unwrap_or_default();
+            Ok(ResponseBody::Json(serde_json::from_slice(&body)?))
+        }));
+        let payload = json!({
+            "num": 1,
+            "none": null,
+            "nested": { "none": null, "text": "string" },
+        });
+        send_json!(req, payload).await
+    }
+}
+
+#[tokio::test]
+async fn test_keep_nulls() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.echo_json().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&Value::Null), res.get("none"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_skip_nulls() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_json_options(JsonOptions::default().skip_nulls())
+        .build();
+
+    let res = api.echo_json().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(json!({ "num": 1, "nested": { "text": "string" } }), res);
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "jwt")]
+
+use apisdk::{send, ApiResult, CodeDataMessage, FixedClock, JwtAuth, TokenGenerator};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use reqwest::{Method, Request, Url};
+use serde_json::{json, Map, Value};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch_jwt(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_jwt_auth() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_authenticator(
+            JwtAuth::new("secret")
+                .with_issuer("apisdk")
+                .with_subject("tester")
+                .with_audience("the-api")
+                .with_claim("role", "admin"),
+        )
+        .build();
+
+    let res = api.touch_jwt().await?;
+    log::debug!("res = {:?}", res);
+    let auth = res.headers.get("authorization").unwrap();
+    let token = auth.trim_start_matches("Bearer ");
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.set_audience(&["the-api"]);
+    validation.set_issuer(&["apisdk"]);
+    let data =
+        decode::<Map<String, Value>>(token, &DecodingKey::from_secret(b"secret"), &validation)
+            .unwrap();
+    let claims = data.claims;
+    assert_eq!(Some(&json!("tester")), claims.get("sub"));
+    assert_eq!(Some(&json!("admin")), claims.get("role"));
+    let iat = claims["iat"].as_u64().unwrap();
+    assert_eq!(Some(iat + 300), claims["exp"].as_u64());
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.validate_aud = false;
+    assert!(decode::<Value>(token, &DecodingKey::from_secret(b"other"), &validation).is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_jwt_auth_cached() -> ApiResult<()> {
+    let req = Request::new(Method::GET, Url::parse("http://localhost/").unwrap());
+    let auth = JwtAuth::new("secret").with_clock(FixedClock(1700000000));
+
+    let token = auth.generate_token(&req).await?;
+    assert_eq!(auth.generate_token_at(1700000000).unwrap(), token);
+    assert_eq!(token, auth.generate_token(&req).await?);
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.validate_exp = false;
+    let data = decode::<Value>(&token, &DecodingKey::from_secret(b"secret"), &validation).unwrap();
+    assert_eq!(json!(1700000000), data.claims["iat"]);
+    assert_eq!(json!(1700000300), data.claims["exp"]);
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use apisdk::{send, ApiResult, CodeDataMessage, JsonOrXml, LenientJson};
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn get_text_json_as_body(&self, lenient: bool) -> ApiResult<JsonOrXml<Value, Value>> {
+        let req = self.get("/path/text-json").await?;
+        let req = match lenient {
+            true => req.with_extension(LenientJson),
+            false => req,
+        };
+        send!(req, Body).await
+    }
+
+    async fn get_text_json_as_cdm(&self) -> ApiResult<Value> {
+        let req = self.get("/path/text-json").await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn get_text(&self) -> ApiResult<String> {
+        let req = self.get("/path/text").await?;
+        send!(req, Text).await
+    }
+}
+
+#[tokio::test]
+async fn test_lenient_json() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+    let res = api.get_text_json_as_body(false).await;
+    assert!(res.is_err(), "text body should not be treated as json");
+
+    let body = api.get_text_json_as_body(true).await?;
+    log::debug!("body = {:?}", body);
+    match body {
+        JsonOrXml::Json(json) => {
+            assert_eq!(json!({ "hello": "world" }), json["data"]);
+            assert_eq!("text/plain", json["__headers__"]["content-type"]);
+        }
+        JsonOrXml::Xml(_) => panic!("body should be parsed as json"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lenient_json_for_all() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_initialiser(LenientJson).build();
+
+    let data = api.get_text_json_as_cdm().await?;
+    assert_eq!(json!({ "hello": "world" }), data);
+
+    // Fall back to text
+    let text = api.get_text().await?;
+    assert_eq!("text goes here", text);
+
+    Ok(())
+}
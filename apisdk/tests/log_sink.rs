@@ -0,0 +1,207 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use apisdk::{send, send_json, ApiResult, CodeDataMessage, LogConfig, LogRecord};
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn touch_json(&self) -> ApiResult<Payload> {
+        let req = self.post("/path/json").await?;
+        let json = json!({ "name": "apisdk", "tags": ["a", "b"] });
+        send_json!(req, &json, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_log_sink() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let records = Arc::new(Mutex::new(Vec::<LogRecord>::new()));
+    let sink = records.clone();
+    let api = TheApi::builder()
+        .with_log("info")
+        .with_log_sink(move |record| sink.lock().unwrap().push(record))
+        .build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+
+    let records = records.lock().unwrap();
+    log::debug!("records = {:?}", records);
+    assert!(records.len() >= 3);
+    assert!(records.iter().all(|r| r.level == log::Level::Info));
+    assert!(records.iter().all(|r| r.target.ends_with("touch")));
+    assert!(records
+        .iter()
+        .all(|r| r.request_id == res.headers["x-request-id"]));
+    assert_eq!(
+        "--> GET http://localhost:3030/v1/path/json",
+        records[0].message
+    );
+    assert!(records
+        .iter()
+        .any(|r| r.message.starts_with("Response Body(Json)")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_log_sink_json() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let records = Arc::new(Mutex::new(Vec::<LogRecord>::new()));
+    let sink = records.clone();
+    let api = TheApi::builder()
+        .with_log_json("info")
+        .with_log_sink(move |record| sink.lock().unwrap().push(record))
+        .build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+
+    let records = records.lock().unwrap();
+    log::debug!("records = {:?}", records);
+    let events: Vec<Value> = records
+        .iter()
+        .map(|r| serde_json::from_str(&r.message).unwrap())
+        .collect();
+    assert!(events.len() >= 3);
+    assert!(events
+        .iter()
+        .all(|e| e["request_id"] == res.headers["x-request-id"].as_str()));
+    assert!(events.iter().all(|e| e["elapsed_ms"].is_u64()));
+
+    assert_eq!("request", events[0]["phase"]);
+    assert_eq!("GET", events[0]["method"]);
+    assert_eq!("http://localhost:3030/v1/path/json", events[0]["url"]);
+
+    let response = events.iter().find(|e| e["phase"] == "response").unwrap();
+    assert_eq!("GET", response["method"]);
+    assert_eq!(200, response["status"]);
+
+    let body = events
+        .iter()
+        .find(|e| e["phase"] == "response.json")
+        .unwrap();
+    assert_eq!(0, body["body"]["code"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_log_sink_pretty_json() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let records = Arc::new(Mutex::new(Vec::<LogRecord>::new()));
+    let sink = records.clone();
+    let wire = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let captured = wire.clone();
+    let api = TheApi::builder()
+        .with_log_config(LogConfig::new("info").with_pretty_json(true))
+        .with_log_sink(move |record| sink.lock().unwrap().push(record))
+        .with_middleware_fn(move |req, extensions, next| {
+            if let Some(bytes) = req.body().and_then(|b| b.as_bytes()) {
+                *captured.lock().unwrap() = bytes.to_vec();
+            }
+            Box::pin(next.run(req, extensions))
+        })
+        .build();
+
+    let res = api.touch_json().await?;
+    log::debug!("res = {:?}", res);
+
+    // The wire body is compact
+    assert_eq!(
+        r#"{"name":"apisdk","tags":["a","b"]}"#,
+        String::from_utf8(wire.lock().unwrap().clone()).unwrap()
+    );
+
+    // The logs are pretty
+    let records = records.lock().unwrap();
+    log::debug!("records = {:?}", records);
+    let request = records
+        .iter()
+        .find(|r| r.message.starts_with("Request Json"))
+        .unwrap();
+    assert!(request
+        .message
+        .contains("{\n  \"name\": \"apisdk\",\n  \"tags\": [\n"));
+    let response = records
+        .iter()
+        .find(|r| r.message.starts_with("Response Body(Json)"))
+        .unwrap();
+    assert!(response.message.contains("{\n  \"code\": 0,"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_log_sink_slow_threshold() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    for (threshold, expected) in [(Duration::ZERO, true), (Duration::from_secs(60), false)] {
+        let records = Arc::new(Mutex::new(Vec::<LogRecord>::new()));
+        let sink = records.clone();
+        let api = TheApi::builder()
+            .with_log_config(LogConfig::new("info").with_slow_threshold(threshold))
+            .with_log_sink(move |record| sink.lock().unwrap().push(record))
+            .build();
+
+        let _ = api.touch().await?;
+
+        let records = records.lock().unwrap();
+        log::debug!("records = {:?}", records);
+        let slow = records
+            .iter()
+            .find(|r| r.message.starts_with("Slow request"));
+        assert_eq!(expected, slow.is_some());
+        if let Some(slow) = slow {
+            assert_eq!(log::Level::Warn, slow.level);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_log_sink_without_log_middleware() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let records = Arc::new(Mutex::new(Vec::<LogRecord>::new()));
+    let sink = records.clone();
+    let api = TheApi::builder()
+        .with_log("info")
+        .with_log_sink(move |record| sink.lock().unwrap().push(record))
+        .without_log_middleware()
+        .build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res.path);
+
+    // The request line is only logged by the middleware
+    let records = records.lock().unwrap();
+    log::debug!("records = {:?}", records);
+    assert!(!records.iter().any(|r| r.message.starts_with("-->")));
+    assert!(records
+        .iter()
+        .any(|r| r.message.starts_with("Response Body(Json)")));
+
+    Ok(())
+}
@@ -22,6 +22,8 @@ impl TheApi {
 pub struct TimeTrace;
 
 impl ReqwestOtelSpanBackend for TimeTrace {
+    // The macro checks a cfg of reqwest-tracing, which is unknown to this crate
+    #[allow(unexpected_cfgs)]
     fn on_request_start(req: &Request, extension: &mut Extensions) -> Span {
         extension.insert(Instant::now());
         reqwest_otel_span!(
@@ -38,7 +40,7 @@ impl ReqwestOtelSpanBackend for TimeTrace {
     ) {
         let time_elapsed = extension.get::<Instant>().unwrap().elapsed().as_millis() as i64;
         default_on_request_end(span, outcome);
-        span.record("time_elapsed", &time_elapsed);
+        span.record("time_elapsed", time_elapsed);
     }
 }
 
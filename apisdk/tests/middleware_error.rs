@@ -0,0 +1,63 @@
+use apisdk::{
+    async_trait, send, ApiResult, CodeDataMessage, Extensions, Middleware, MiddlewareError, Next,
+    Request, Response,
+};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+#[derive(Debug, PartialEq, Eq)]
+struct QuotaExceeded {
+    remaining: u32,
+}
+
+impl std::fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Quota exceeded, remaining {}", self.remaining)
+    }
+}
+
+impl std::error::Error for QuotaExceeded {}
+
+struct QuotaMiddleware;
+
+#[async_trait]
+impl Middleware for QuotaMiddleware {
+    async fn handle(
+        &self,
+        _req: Request,
+        _extensions: &mut Extensions,
+        _next: Next<'_>,
+    ) -> Result<Response, MiddlewareError> {
+        Err(MiddlewareError::middleware(QuotaExceeded { remaining: 0 }))
+    }
+}
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_downcast_middleware_error() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_middleware(QuotaMiddleware).build();
+
+    let e = api
+        .touch()
+        .await
+        .expect_err("should be rejected by middleware");
+    log::debug!("e = {:?}", e);
+    assert_eq!(
+        Some(&QuotaExceeded { remaining: 0 }),
+        e.downcast_middleware::<QuotaExceeded>()
+    );
+    assert!(e.downcast_middleware::<std::fmt::Error>().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use apisdk::{header::HeaderValue, send, ApiResult, CodeDataMessage};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_fn() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_middleware_fn(|mut req, extensions, next| {
+            req.headers_mut()
+                .insert("X-Closure", HeaderValue::from_static("closure"));
+            Box::pin(next.run(req, extensions))
+        })
+        .build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        Some("closure"),
+        res.headers.get("x-closure").map(|v| v.as_str())
+    );
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use apisdk::{send, ApiError, ApiResult, CodeDataMessage, MockServer, ResponseBody};
+use apisdk::{
+    send, ApiError, ApiResult, CodeDataMessage, Method, MockController, MockServer, ResponseBody,
+};
 use serde::Deserialize;
 use serde_json::json;
 
@@ -28,6 +30,11 @@ impl TheApi {
         send!(req, CodeDataMessage).await
     }
 
+    async fn touch_text(&self) -> ApiResult<()> {
+        let req = self.get("/path/text").await?;
+        send!(req, ()).await
+    }
+
     async fn touch_mock(&self) -> ApiResult<MockPayload> {
         let req = self.get("/path/json").await?;
         let req = req.with_extension(MockServer::new(|_| {
@@ -94,3 +101,58 @@ async fn test_mock_error() -> ApiResult<()> {
 
     Ok(())
 }
+
+fn mock_payload() -> ResponseBody {
+    ResponseBody::Json(json!({
+        "code": 0,
+        "data": {
+            "mock": true
+        }
+    }))
+}
+
+#[tokio::test]
+async fn test_mock_controller() -> ApiResult<()> {
+    init_logger();
+
+    let mock = MockController::new();
+    mock.expect(Method::GET, "/v1/path/json")
+        .times(2)
+        .respond(mock_payload());
+    let api = TheApi::builder().with_initialiser(mock.server()).build();
+
+    assert!(api.touch().await?.mock);
+    assert!(api.touch().await?.mock);
+    assert!(mock.verify().is_ok());
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "GET /v1/path/json is expected 1 time(s), but called 0 time(s)")]
+fn test_mock_controller_missing_call() {
+    init_logger();
+
+    let mock = MockController::new();
+    mock.expect(Method::GET, "/v1/path/json")
+        .respond(mock_payload());
+    let _api = TheApi::builder().with_initialiser(mock.server()).build();
+}
+
+#[tokio::test]
+#[should_panic(expected = "GET /v1/path/text is unexpected")]
+async fn test_mock_controller_unexpected_call() {
+    init_logger();
+
+    let mock = MockController::new();
+    mock.expect(Method::GET, "/v1/path/json")
+        .respond(mock_payload());
+    let api = TheApi::builder().with_initialiser(mock.server()).build();
+
+    assert!(api.touch().await.is_ok());
+    assert!(api.touch_text().await.is_err());
+    assert_eq!(
+        Err(vec!["GET /v1/path/text is unexpected".to_string()]),
+        mock.verify()
+    );
+}
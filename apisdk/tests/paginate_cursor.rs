@@ -0,0 +1,109 @@
+use apisdk::{ApiResult, CursorPaginator, MockServer, ResponseBody};
+use futures::TryStreamExt;
+use serde_json::json;
+
+use crate::common::{init_logger, TheApi};
+
+mod common;
+
+fn cursor_server() -> MockServer {
+    MockServer::new(|req: apisdk::Request| {
+        let cursor = req
+            .url()
+            .query_pairs()
+            .find(|(k, _)| k == "cursor")
+            .map(|(_, v)| v.to_string());
+        let page = match cursor.as_deref() {
+            None => json!({ "data": [1, 2], "next_cursor": "c1" }),
+            Some("c1") => json!({ "data": [3, 4], "next_cursor": "c2" }),
+            Some("c2") => json!({ "data": [5], "next_cursor": null }),
+            Some(_) => json!({ "data": [] }),
+        };
+        Ok(ResponseBody::Json(page))
+    })
+}
+
+fn next_cursor(page: &serde_json::Value) -> Option<String> {
+    page.get("next_cursor")
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+#[tokio::test]
+async fn test_cursor_paginator_collect() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+    let req = api.get("/items").await?.with_extension(cursor_server());
+
+    let items: Vec<u32> = CursorPaginator::new(next_cursor).collect(req).await?;
+    log::debug!("items = {:?}", items);
+    assert_eq!(vec![1, 2, 3, 4, 5], items);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cursor_paginator_stream() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+    let req = api.get("/items").await?.with_extension(cursor_server());
+
+    let mut stream = Box::pin(CursorPaginator::new(next_cursor).stream::<u32>(req));
+    let mut count = 0;
+    while let Some(item) = stream.try_next().await? {
+        log::debug!("item = {}", item);
+        count += 1;
+    }
+    assert_eq!(5, count);
+
+    Ok(())
+}
+
+fn cycle_server() -> MockServer {
+    MockServer::new(|req: apisdk::Request| {
+        let cursor = req
+            .url()
+            .query_pairs()
+            .find(|(k, _)| k == "cursor")
+            .map(|(_, v)| v.to_string());
+        let page = match cursor.as_deref() {
+            None => json!({ "data": [1], "next_cursor": "c1" }),
+            Some("c1") => json!({ "data": [2], "next_cursor": "c2" }),
+            Some(_) => json!({ "data": [3], "next_cursor": "c1" }),
+        };
+        Ok(ResponseBody::Json(page))
+    })
+}
+
+#[tokio::test]
+async fn test_cursor_paginator_cycle() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+    let req = api.get("/items").await?.with_extension(cycle_server());
+
+    let items: Vec<u32> = CursorPaginator::new(next_cursor).collect(req).await?;
+    log::debug!("items = {:?}", items);
+    assert_eq!(vec![1, 2, 3], items);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cursor_paginator_max_pages() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+    let req = api.get("/items").await?.with_extension(cursor_server());
+
+    let items: Vec<u32> = CursorPaginator::new(next_cursor)
+        .with_max_pages(2)
+        .collect(req)
+        .await?;
+    log::debug!("items = {:?}", items);
+    assert_eq!(vec![1, 2, 3, 4], items);
+
+    Ok(())
+}
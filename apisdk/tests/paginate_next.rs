@@ -0,0 +1,79 @@
+use apisdk::{ApiError, ApiResult, CursorPaginator, MockServer, ResponseBody};
+use serde_json::json;
+
+use crate::common::{init_logger, TheApi};
+
+mod common;
+
+fn graph_server() -> MockServer {
+    paging_server("http://localhost:3030/v1/me/media?after=p2")
+}
+
+fn paging_server(next: &'static str) -> MockServer {
+    MockServer::new(move |req: apisdk::Request| {
+        let page = match req.url().query() {
+            None => json!({
+                "data": [{ "id": "1" }, { "id": "2" }],
+                "paging": {
+                    "cursors": { "after": "p2" },
+                    "next": next
+                }
+            }),
+            Some(_) => json!({
+                "data": [{ "id": "3" }],
+                "paging": {
+                    "cursors": { "before": "p2" }
+                }
+            }),
+        };
+        Ok(ResponseBody::Json(page))
+    })
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Media {
+    id: String,
+}
+
+#[tokio::test]
+async fn test_paging_next() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+    let req = api.get("/me/media").await?.with_extension(graph_server());
+
+    let items: Vec<Media> = CursorPaginator::paging_next().collect(req).await?;
+    log::debug!("items = {:?}", items);
+    let ids: Vec<_> = items.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(vec!["1", "2", "3"], ids);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_paging_next_cross_origin() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+    let next = "https://example.com/v1/me/media?after=p2";
+
+    let req = api
+        .get("/me/media")
+        .await?
+        .with_extension(paging_server(next));
+    let result = CursorPaginator::paging_next().collect::<Media>(req).await;
+    log::debug!("result = {:?}", result);
+    assert!(matches!(result, Err(ApiError::CrossOrigin(url)) if url == next));
+
+    let req = api
+        .get("/me/media")
+        .await?
+        .with_extension(paging_server(next));
+    let items: Vec<Media> = CursorPaginator::paging_next()
+        .with_cross_origin(true)
+        .collect(req)
+        .await?;
+    assert_eq!(3, items.len());
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use apisdk::{path, send, ApiResult, PathTemplate};
+use serde_json::Value;
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn get_untyped(&self, format: &str) -> ApiResult<Value> {
+        let req = self
+            .get(path!("/path/untyped/{format}", format = format)?)
+            .await?;
+        send!(req, Json).await
+    }
+}
+
+#[tokio::test]
+async fn test_path_template() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let url = api
+        .build_url(path!("/posts/{id}/comments/{cid}", id = 1, cid = "a/b?c")?)
+        .await?;
+    assert_eq!(
+        "http://localhost:3030/v1/posts/1/comments/a%2Fb%3Fc",
+        url.as_str()
+    );
+
+    let path = PathTemplate::new("/posts/{id}")
+        .param("id", "../admin")
+        .render()?;
+    let url = api.build_url(path).await?;
+    assert_eq!("http://localhost:3030/v1/posts/..%2Fadmin", url.as_str());
+
+    let res = api.get_untyped("json").await?;
+    assert_eq!("world", res["data"]["hello"]);
+
+    Ok(())
+}
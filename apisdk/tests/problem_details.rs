@@ -0,0 +1,71 @@
+use apisdk::{send, ApiError, ApiResult, MimeType, ProblemDetails};
+use serde_json::Value;
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch_problem(&self) -> ApiResult<Value> {
+        let req = self.get("/problem").await?;
+        send!(req).await
+    }
+}
+
+#[tokio::test]
+async fn test_problem_details_error() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.touch_problem().await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::HttpClientStatus(403, message)) => {
+            assert_eq!(
+                "You do not have enough credit.: Your current balance is 30, but that costs 50.",
+                message
+            );
+        }
+        _ => panic!("Should be HttpClientStatus"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_problem_details_parse() {
+    let problem: ProblemDetails = serde_json::from_str(
+        r#"
+        {
+            "type": "https://example.com/probs/out-of-credit",
+            "title": "You do not have enough credit.",
+            "status": 403,
+            "balance": 30
+        }
+        "#,
+    )
+    .unwrap();
+    assert_eq!(Some(403), problem.status);
+    assert_eq!(Some(30), problem.get_extra::<u32>("balance"));
+
+    let e: ApiError = problem.into();
+    assert_eq!(403, e.as_error_code());
+}
+
+#[test]
+fn test_problem_details_without_message() {
+    let problem: ProblemDetails = serde_json::from_str(r#"{ "status": 404 }"#).unwrap();
+    let e: ApiError = problem.into();
+    assert!(matches!(e, ApiError::HttpClientStatus(404, message) if message == "404 Not Found"));
+}
+
+#[test]
+fn test_problem_json_mime_type() {
+    assert!(matches!(
+        MimeType::from("application/problem+json; charset=utf-8"),
+        MimeType::Json
+    ));
+    assert!(ProblemDetails::is_problem_json("Application/Problem+JSON"));
+}
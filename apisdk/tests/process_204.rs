@@ -22,7 +22,7 @@ async fn test_send_form_via_hashmap() -> ApiResult<()> {
 
     let api = SampleAPI::default();
 
-    let res = api.reply_204().await?;
+    api.reply_204().await?;
 
     Ok(())
 }
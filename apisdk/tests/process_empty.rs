@@ -0,0 +1,162 @@
+use apisdk::{send, ApiError, ApiResult, CodeDataMessage, MimeType};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn empty_as_body(&self) -> ApiResult<String> {
+        let req = self.get("/path/empty").await?;
+        send!(req, Body).await
+    }
+
+    async fn empty_as_json(&self) -> ApiResult<Option<Payload>> {
+        let req = self.get("/path/empty").await?;
+        send!(req, Json).await
+    }
+
+    async fn empty_as_xml(&self) -> ApiResult<Option<Payload>> {
+        let req = self.get("/path/empty").await?;
+        send!(req, Xml).await
+    }
+
+    async fn empty_as_required(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/empty").await?;
+        send!(req, Json).await
+    }
+
+    async fn empty_as_auto(&self) -> ApiResult<Option<Payload>> {
+        let req = self.get("/path/empty").await?;
+        send!(req).await
+    }
+
+    async fn empty_as_text(&self) -> ApiResult<String> {
+        let req = self.get("/path/empty").await?;
+        send!(req, Text).await
+    }
+
+    async fn empty_as_unit(&self) -> ApiResult<()> {
+        let req = self.get("/path/empty").await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn empty_as_extractor(&self) -> ApiResult<Option<Payload>> {
+        let req = self.get("/path/empty").await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn empty_as_required_extractor(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/empty").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_empty_as_body() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.empty_as_body().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_as_json() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.empty_as_json().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_as_xml() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.empty_as_xml().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_as_required() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.empty_as_required().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(
+        res,
+        Err(ApiError::DecodeResponse(MimeType::Empty, _))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_as_auto() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.empty_as_auto().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_as_text() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.empty_as_text().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_as_extractor() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    api.empty_as_unit().await?;
+
+    let res = api.empty_as_extractor().await?;
+    log::debug!("res = {:?}", res);
+    assert!(res.is_none());
+
+    let res = api.empty_as_required_extractor().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(
+        res,
+        Err(ApiError::DecodeResponse(MimeType::Empty, _))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::net::SocketAddr;
+
+use apisdk::{send, ApiError, ApiResult, CodeDataMessage, NoProxy, Proxy, SocketAddrs};
+
+use crate::common::{init_logger, start_server, Payload, TheApi, TheApiBuilder};
+
+mod common;
+
+fn localhost(_name: &str) -> Option<SocketAddrs> {
+    Some(SocketAddrs::new_multi(vec![SocketAddr::from((
+        [127, 0, 0, 1],
+        0,
+    ))]))
+}
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_proxy_url() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    // The host is unreachable, so the request must go through proxy
+    let api = TheApiBuilder::new("http://apisdk.invalid/v1")
+        .with_proxy_url("http://127.0.0.1:3030")?
+        .build();
+
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res.path);
+    assert_eq!(
+        Some("apisdk.invalid"),
+        res.headers.get("host").map(|h| h.as_str())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_proxy() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    // The system proxy is unreachable, so the request must bypass it
+    std::env::set_var("HTTP_PROXY", "http://127.0.0.1:1");
+
+    let api = TheApi::builder()
+        .with_no_proxy("localhost,127.0.0.1")
+        .build();
+    let res = api.touch().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res.path);
+
+    let api = TheApi::builder().build();
+    assert!(api.touch().await.is_err());
+
+    // The proxy added by caller is kept as is
+    let api = TheApi::builder()
+        .with_proxy_url("http://127.0.0.1:1")?
+        .with_no_proxy("localhost,127.0.0.1")
+        .build();
+    assert!(api.touch().await.is_err());
+
+    let api = TheApi::builder()
+        .with_proxy(
+            Proxy::all("http://127.0.0.1:1")?.no_proxy(NoProxy::from_string("localhost,127.0.0.1")),
+        )
+        .build();
+    let res = api.touch().await?;
+    assert_eq!("/v1/path/json", res.path);
+
+    // DnsResolver can be used with the system proxy
+    let api = TheApi::builder()
+        .with_resolver(localhost)
+        .with_no_proxy("localhost,127.0.0.1")
+        .try_build()?;
+    let res = api.touch().await?;
+    assert_eq!("/v1/path/json", res.path);
+
+    // But not with the proxy added by caller
+    assert!(TheApi::builder()
+        .with_resolver(localhost)
+        .with_proxy_url("http://127.0.0.1:1")?
+        .try_build()
+        .is_err());
+
+    // `HTTP_PROXY` is ignored in CGI, but `ALL_PROXY` is still used
+    std::env::set_var("REQUEST_METHOD", "GET");
+    std::env::set_var("ALL_PROXY", "http://127.0.0.1:3030");
+    let api = TheApiBuilder::new("http://apisdk.invalid/v1")
+        .with_no_proxy("example.com")
+        .build();
+    let res = api.touch().await?;
+    assert_eq!("/v1/path/json", res.path);
+    std::env::remove_var("REQUEST_METHOD");
+    std::env::remove_var("ALL_PROXY");
+
+    // The invalid system proxy is reported
+    std::env::set_var("HTTP_PROXY", "http://[::1");
+    assert!(matches!(
+        TheApi::builder().with_no_proxy("localhost").try_build(),
+        Err(ApiError::Config(_))
+    ));
+
+    std::env::remove_var("HTTP_PROXY");
+
+    Ok(())
+}
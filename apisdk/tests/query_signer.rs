@@ -0,0 +1,55 @@
+use apisdk::{digest, send_json, ApiResult, CodeDataMessage, HashAlgorithm, QuerySigner};
+use serde_json::json;
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn post_query_signed(&self) -> ApiResult<Payload> {
+        let req = self.post("/path/json").await?;
+        let req = req.query(&[("b", "2"), ("a", "1")]);
+        send_json!(req, json!({ "num": 1 }), CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_query_signer() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_authenticator(QuerySigner::new("secret", HashAlgorithm::Md5))
+        .build();
+
+    let res = api.post_query_signed().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&digest::md5("a=1&b=2secret")), res.query.get("sign"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_signer_with_body() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_authenticator(
+            QuerySigner::new("secret", HashAlgorithm::Sha256)
+                .with_sign_param("signature")
+                .with_body(true),
+        )
+        .build();
+
+    let res = api.post_query_signed().await?;
+    log::debug!("res = {:?}", res);
+    let body = serde_json::to_string(&json!({ "num": 1 }))?;
+    assert_eq!(
+        Some(&digest::sha256(format!("a=1&b=2{}secret", body))),
+        res.query.get("signature")
+    );
+    assert_eq!(None, res.query.get("sign"));
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use apisdk::{digest, send, ApiResult, MockServer, ResponseBody};
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi, SIGNATURE_SECRET};
+
+mod common;
+
+impl TheApi {
+    async fn get_signed_raw(&self) -> ApiResult<ResponseBody> {
+        let req = self.get("/path/signed").await?;
+        send!(req, Raw).await
+    }
+
+    async fn get_empty_raw(&self) -> ApiResult<ResponseBody> {
+        let req = self.get("/path/empty").await?;
+        send!(req, Raw).await
+    }
+
+    async fn get_mock_raw(&self) -> ApiResult<ResponseBody> {
+        let req = self.get("/path/json").await?;
+        let req = req.with_extension(MockServer::new(|_| {
+            Ok(ResponseBody::Text("{ \"id\" :  1 }".to_string()))
+        }));
+        send!(req, Raw).await
+    }
+}
+
+#[tokio::test]
+async fn test_raw_bytes() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let body = api.get_signed_raw().await?;
+    log::debug!("body = {:?}", body);
+    let expected = json!({ "event": "created", "id": 1 }).to_string();
+    let raw = body.raw_bytes().expect("raw bytes should be retained");
+    assert_eq!(expected.as_bytes(), raw.as_ref());
+    assert_eq!(
+        digest::hmac_sha256(SIGNATURE_SECRET, &expected),
+        digest::hmac_sha256(SIGNATURE_SECRET, raw)
+    );
+
+    let value: Value = body.parse_json()?;
+    assert_eq!(json!({ "event": "created", "id": 1 }), value);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_raw_bytes_empty() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // The empty payload is retained as raw bytes as well
+    let body = api.get_empty_raw().await?;
+    log::debug!("body = {:?}", body);
+    assert_eq!(Some(&b""[..]), body.raw_bytes().map(|b| b.as_ref()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_raw_bytes_mock() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let body = api.get_mock_raw().await?;
+    log::debug!("body = {:?}", body);
+    assert_eq!(
+        Some(&b"{ \"id\" :  1 }"[..]),
+        body.raw_bytes().map(|b| b.as_ref())
+    );
+
+    let value: Value = body.parse_json()?;
+    assert_eq!(json!({ "id": 1 }), value);
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use apisdk::{api_method, send, send_form, send_json, ApiResult};
+use serde_json::{json, value::RawValue, Value};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn get_raw_value(&self) -> ApiResult<Box<RawValue>> {
+        let req = self.get("/path/raw-json").await?;
+        send!(req, RawJson).await
+    }
+
+    async fn post_raw_value(&self) -> ApiResult<Box<RawValue>> {
+        let req = self.post("/path/raw-json").await?;
+        send_json!(req, json!({ "key": "value" }), RawJson).await
+    }
+
+    async fn get_raw_value_by_json(&self) -> ApiResult<Box<RawValue>> {
+        let req = self.get("/path/raw-json").await?;
+        send!(req, Json).await
+    }
+
+    async fn post_raw_value_by_json(&self) -> ApiResult<Box<RawValue>> {
+        let req = self.post("/path/raw-json").await?;
+        send_json!(req, json!({ "key": "value" }), Json).await
+    }
+
+    async fn post_form_raw_value_by_json(&self) -> ApiResult<Box<RawValue>> {
+        let req = self.post("/path/raw-json").await?;
+        let form = HashMap::from([("key", "value")]);
+        send_form!(req, form, Json).await
+    }
+
+    #[api_method(log = "off")]
+    async fn get_raw_value_in_api_method(&self) -> ApiResult<Box<RawValue>> {
+        let req = self.get("/path/raw-json").await?;
+        send!(req, Json).await
+    }
+
+    async fn get_value_by_json(&self) -> ApiResult<Value> {
+        let req = self.get("/path/raw-json").await?;
+        send!(req, Json).await
+    }
+}
+
+#[tokio::test]
+async fn test_raw_value() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // The key order and formatting are preserved
+    let raw = api.get_raw_value().await?;
+    assert_eq!("{\"zeta\": 1,\n  \"alpha\": [1, 2.50]}", raw.get());
+
+    let raw = api.post_raw_value().await?;
+    assert_eq!("{\"zeta\": 1,\n  \"alpha\": [1, 2.50]}", raw.get());
+
+    // `Json` keeps the payload verbatim for `RawValue` as well
+    let raw = api.get_raw_value_by_json().await?;
+    assert_eq!("{\"zeta\": 1,\n  \"alpha\": [1, 2.50]}", raw.get());
+
+    let raw = api.post_raw_value_by_json().await?;
+    assert_eq!("{\"zeta\": 1,\n  \"alpha\": [1, 2.50]}", raw.get());
+
+    let raw = api.post_form_raw_value_by_json().await?;
+    assert_eq!("{\"zeta\": 1,\n  \"alpha\": [1, 2.50]}", raw.get());
+
+    let raw = api.get_raw_value_in_api_method().await?;
+    assert_eq!("{\"zeta\": 1,\n  \"alpha\": [1, 2.50]}", raw.get());
+
+    // Other types are not affected
+    let value = api.get_value_by_json().await?;
+    assert_eq!(1, value["zeta"]);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use apisdk::{
+    download_to_file, redirect::Policy, resumable_download, send, send_bytes, send_range, send_raw,
+    send_with_trailers, ApiError, ApiResult, CodeDataMessage,
+};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn follow(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/redirect").await?;
+        send!(req, CodeDataMessage).await
+    }
+
+    async fn follow_bytes(&self) -> ApiResult<Vec<u8>> {
+        let req = self.get("/path/redirect").await?;
+        send_bytes!(req).await
+    }
+
+    async fn follow_range(&self) -> ApiResult<()> {
+        let req = self.get("/path/redirect").await?;
+        send_range!(req, 0, 1).await.map(|_| ())
+    }
+
+    async fn follow_download(&self, path: &Path, resumable: bool) -> ApiResult<()> {
+        let req = self.get("/path/redirect").await?;
+        match resumable {
+            true => resumable_download!(req, path).await.map(|_| ()),
+            false => download_to_file!(req, path).await.map(|_| ()),
+        }
+    }
+
+    async fn follow_trailers(&self) -> ApiResult<()> {
+        let req = self.get("/path/redirect").await?;
+        send_with_trailers!(req).await.map(|_| ())
+    }
+
+    async fn follow_raw(&self) -> ApiResult<String> {
+        let req = self.get("/path/redirect").await?;
+        let res = send_raw!(req).await?;
+        Ok(res.url().to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_follow_redirect() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.follow().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res.path);
+
+    let url = api.follow_raw().await?;
+    assert_eq!("http://localhost:3030/v1/path/json", url);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_redirects() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_no_redirects().build();
+
+    let res = api.follow().await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::Redirect(302, location)) => {
+            assert_eq!(Some("/v1/path/json".to_string()), location)
+        }
+        _ => panic!("should be Redirect error"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_redirects_unparsed() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_no_redirects().build();
+
+    let res = api.follow_bytes().await;
+    assert!(matches!(res, Err(ApiError::Redirect(302, _))), "{:?}", res);
+
+    let res = api.follow_range().await;
+    assert!(matches!(res, Err(ApiError::Redirect(302, _))), "{:?}", res);
+
+    // The body of redirect should not be written as payload
+    let path = std::env::temp_dir().join(format!("redirect-{}.bin", std::process::id()));
+    for resumable in [false, true] {
+        let res = api.follow_download(&path, resumable).await;
+        assert!(matches!(res, Err(ApiError::Redirect(302, _))), "{:?}", res);
+        assert!(!path.exists());
+    }
+
+    let res = api.follow_trailers().await;
+    assert!(matches!(res, Err(ApiError::Redirect(302, _))), "{:?}", res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_redirect_policy() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_redirect_policy(Policy::limited(3))
+        .build();
+
+    let res = api.follow().await?;
+    assert_eq!("/v1/path/json", res.path);
+
+    Ok(())
+}
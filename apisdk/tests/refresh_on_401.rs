@@ -0,0 +1,76 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use apisdk::{send, ApiError, ApiResult, RefreshOn401Middleware};
+use serde_json::Value;
+
+use crate::common::{init_logger, start_server, TheApi, VALID_TOKEN};
+
+mod common;
+
+impl TheApi {
+    async fn get_protected(&self) -> ApiResult<Value> {
+        let req = self.get("/path/protected").await?;
+        send!(req, Json).await
+    }
+}
+
+/// Build a middleware, which counts the times of refreshing
+fn build_middleware(new_token: &'static str) -> (RefreshOn401Middleware, Arc<AtomicUsize>) {
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    let middleware = RefreshOn401Middleware::new(move || {
+        let counter = counter.clone();
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            Ok(new_token.to_string())
+        }
+    });
+    (middleware, count)
+}
+
+#[tokio::test]
+async fn test_refresh_on_401() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let (middleware, count) = build_middleware(VALID_TOKEN);
+    let middleware = middleware.with_token("stale-token");
+    let api = TheApi::builder()
+        .with_middleware(middleware.clone())
+        .build();
+
+    let results = futures::future::join_all((0..5).map(|_| api.get_protected())).await;
+    for res in results {
+        log::debug!("res = {:?}", res);
+        assert_eq!(200, res?["status"]);
+    }
+    assert_eq!(1, count.load(Ordering::SeqCst));
+    assert_eq!(Some(VALID_TOKEN.to_string()), middleware.token());
+
+    // No more refresh once the token is valid
+    api.get_protected().await?;
+    assert_eq!(1, count.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_refresh_on_401_once() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let (middleware, count) = build_middleware("still-invalid");
+    let api = TheApi::builder().with_middleware(middleware).build();
+
+    let res = api.get_protected().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::HttpClientStatus(401, _))));
+    // One for the first token, and one for the retry
+    assert_eq!(2, count.load(Ordering::SeqCst));
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use apisdk::{send, ApiError, ApiResult, CodeDataMessage, RequestBatcher};
+use futures::future::join_all;
+use serde::Deserialize;
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+#[derive(Debug, Clone, Deserialize)]
+struct Item {
+    id: u64,
+    name: String,
+}
+
+/// Build an api, which counts upstream calls and records the `ids` query param
+fn build_api(calls: Arc<AtomicUsize>, queries: Arc<Mutex<Vec<String>>>) -> TheApi {
+    TheApi::builder()
+        .with_middleware_fn(move |req, extensions, next| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            if let Some(query) = req.url().query() {
+                queries.lock().unwrap().push(query.to_string());
+            }
+            Box::pin(next.run(req, extensions))
+        })
+        .build()
+}
+
+fn build_batcher(api: TheApi) -> RequestBatcher<u64, Item> {
+    let api = Arc::new(api);
+    RequestBatcher::new(move |ids: Vec<u64>| {
+        let api = api.clone();
+        async move {
+            let ids: Vec<_> = ids.iter().map(|id| id.to_string()).collect();
+            let req = api.get("/path/items").await?;
+            let req = req.query(&[("ids", ids.join(","))]);
+            let items: Vec<Item> = send!(req, CodeDataMessage).await?;
+            Ok(items.into_iter().map(|item| (item.id, item)).collect())
+        }
+    })
+    .with_window(Duration::from_millis(20))
+}
+
+#[tokio::test]
+async fn test_request_batcher() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let queries = Arc::new(Mutex::new(vec![]));
+    let batcher = build_batcher(build_api(calls.clone(), queries.clone()));
+
+    let results = join_all([1, 2, 3, 2, 42].map(|id| batcher.get(id))).await;
+    log::debug!("results = {:?}", results);
+    assert_eq!(1, calls.load(Ordering::SeqCst));
+    assert_eq!(vec!["ids=1%2C2%2C3%2C42"], *queries.lock().unwrap());
+
+    let names: Vec<_> = results
+        .into_iter()
+        .map(|r| r.map(|item| item.map(|item| item.name)))
+        .collect::<ApiResult<_>>()?;
+    assert_eq!(
+        vec![
+            Some("item-1".to_string()),
+            Some("item-2".to_string()),
+            Some("item-3".to_string()),
+            Some("item-2".to_string()),
+            None
+        ],
+        names
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_request_batcher_max_batch_size() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let queries = Arc::new(Mutex::new(vec![]));
+    let batcher = build_batcher(build_api(calls.clone(), queries.clone())).with_max_batch_size(2);
+
+    let results = join_all([1, 2, 3, 4, 5].map(|id| batcher.get(id))).await;
+    log::debug!("results = {:?}", results);
+    assert!(results.iter().all(|r| matches!(r, Ok(Some(_)))));
+    assert_eq!(3, calls.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_request_batcher_error() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = Arc::new(TheApi::builder().build());
+    let batcher = RequestBatcher::<u64, Item>::new(move |_: Vec<u64>| {
+        let api = api.clone();
+        async move {
+            let req = api.get("/not-found").await?;
+            let _: Vec<Item> = send!(req, CodeDataMessage).await?;
+            Ok(Default::default())
+        }
+    });
+
+    let results = join_all([1, 2].map(|id| batcher.get(id))).await;
+    log::debug!("results = {:?}", results);
+    for result in results {
+        // The original error is kept, along with its status
+        let e = result.unwrap_err();
+        assert!(matches!(
+            &e,
+            ApiError::Batch(inner) if matches!(inner.as_ref(), ApiError::HttpClientStatus(405, _))
+        ));
+        assert!(e.is_client_error());
+        assert!(!e.is_retryable());
+        assert_eq!(405, e.as_error_code());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use apisdk::{
+    header::HeaderValue, AccessTokenAuth, ApiResult, CodeDataMessage, JsonExtractor, Method,
+    RequestSender,
+};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+#[tokio::test]
+async fn test_request_split() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_authenticator(AccessTokenAuth::new("fixed"))
+        .build();
+
+    let (mut req, sender) = api.request_split(Method::GET, "/path/json").await?;
+    assert_eq!("/v1/path/json", req.url().path());
+    req.headers_mut()
+        .insert("x-custom", HeaderValue::from_static("scheduled"));
+
+    let body = sender.send(req).await?;
+    let res: Payload = CodeDataMessage::try_from(body)?.try_extract()?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res.path);
+    assert_eq!(
+        Some("scheduled"),
+        res.headers.get("x-custom").map(|v| v.as_str())
+    );
+    assert_eq!(
+        Some("Bearer fixed"),
+        res.headers.get("authorization").map(|v| v.as_str())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_request_split_builder() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let req = api.get("/path/json").await?.query(&[("a", "1")]);
+    let (req, sender) = RequestSender::split(req)?;
+    assert_eq!(Some("a=1"), req.url().query());
+
+    let res = sender.send_raw(req).await?;
+    assert!(res.status().is_success());
+
+    Ok(())
+}
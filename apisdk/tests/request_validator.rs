@@ -0,0 +1,119 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use apisdk::{
+    send_json, ApiError, ApiResult, MimeType, MockServer, RequestValidator, ResponseBody,
+};
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+fn require_name(json: &Value) -> Result<(), String> {
+    match json.get("name") {
+        Some(Value::String(_)) => Ok(()),
+        _ => Err("name is required".to_string()),
+    }
+}
+
+impl TheApi {
+    async fn post_validated(&self, payload: Value, counter: Arc<AtomicUsize>) -> ApiResult<Value> {
+        let req = self.post("/path/json").await?;
+        let req = req.with_extension(MockServer::new(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(ResponseBody::Json(json!({ "ok": true })))
+        }));
+        send_json!(req, payload).await
+    }
+}
+
+#[tokio::test]
+async fn test_valid_request() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_initialiser(RequestValidator::new(require_name))
+        .build();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let res = api
+        .post_validated(json!({ "name": "apisdk" }), counter.clone())
+        .await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(1, counter.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_invalid_request() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_initialiser(RequestValidator::new(require_name))
+        .build();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let res = api
+        .post_validated(json!({ "name": 1 }), counter.clone())
+        .await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::SerializeRequest {
+            mime: MimeType::Json,
+            detail,
+        }) => assert_eq!("name is required", detail),
+        _ => panic!("should be SerializeRequest error"),
+    }
+    assert_eq!(0, counter.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+#[cfg(feature = "jsonschema")]
+#[tokio::test]
+async fn test_schema_request() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let schema = json!({
+        "type": "object",
+        "properties": { "name": { "type": "string" } },
+        "required": ["name"]
+    });
+    let api = TheApi::builder()
+        .with_initialiser(RequestValidator::schema(&schema)?)
+        .build();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let _ = api
+        .post_validated(json!({ "name": "apisdk" }), counter.clone())
+        .await?;
+    assert_eq!(1, counter.load(Ordering::SeqCst));
+
+    let res = api
+        .post_validated(json!({ "name": 1 }), counter.clone())
+        .await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::SerializeRequest {
+            mime: MimeType::Json,
+            detail,
+        }) => assert_eq!("1 is not of type \"string\" at \"/name\"", detail),
+        _ => panic!("should be SerializeRequest error"),
+    }
+    assert_eq!(1, counter.load(Ordering::SeqCst));
+
+    // The invalid schema is rejected
+    assert!(matches!(
+        RequestValidator::schema(&json!({ "type": 1 })),
+        Err(ApiError::Config(_))
+    ));
+
+    Ok(())
+}
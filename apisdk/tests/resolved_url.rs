@@ -0,0 +1,47 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use apisdk::{send, ApiResult, CodeDataMessage, ResolvedUrl, Url};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch_resolved(&self) -> ApiResult<(Option<Url>, Payload)> {
+        let mut req = self.get("/path/json").await?;
+        let resolved = req.extensions().get::<ResolvedUrl>().map(|u| u.0.clone());
+        let res = send!(req, CodeDataMessage).await?;
+        Ok((resolved, res))
+    }
+}
+
+#[tokio::test]
+async fn test_resolved_url() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let seen = Arc::new(Mutex::new(None));
+    let seen_in_middleware = seen.clone();
+    let api = TheApi::builder()
+        .with_rewriter(SocketAddr::from(([127, 0, 0, 1], 3030)))
+        .with_middleware_fn(move |req, extensions, next| {
+            let resolved = extensions.get::<ResolvedUrl>().cloned();
+            *seen_in_middleware.lock().unwrap() = resolved.map(|u| (u.0, req.url().clone()));
+            Box::pin(next.run(req, extensions))
+        })
+        .build();
+
+    let (resolved, res) = api.touch_resolved().await?;
+    log::debug!("resolved = {:?}, res = {:?}", resolved, res);
+    let resolved = resolved.expect("ResolvedUrl should be attached");
+    assert_eq!("http://127.0.0.1:3030/v1/path/json", resolved.as_str());
+
+    let (in_middleware, sent) = seen.lock().unwrap().clone().expect("middleware should run");
+    assert_eq!(resolved, in_middleware);
+    assert_eq!(resolved, sent);
+
+    Ok(())
+}
@@ -0,0 +1,221 @@
+use std::sync::{Arc, Mutex};
+
+use apisdk::{send, AccessTokenAuth, ApiError, ApiResult, JsonExtractor, ResponseBody};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi, TheApiBuilder, ETAG};
+
+mod common;
+
+/// This extractor returns the whole payload along with `__headers__`
+#[derive(Debug, Deserialize)]
+struct WithHeaders(Value);
+
+impl JsonExtractor for WithHeaders {
+    fn require_headers() -> bool {
+        true
+    }
+
+    fn try_extract<T>(self) -> ApiResult<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        serde_json::from_value(self.0).map_err(ApiError::DecodeJson)
+    }
+}
+
+impl TheApi {
+    async fn get_with_etag(&self) -> ApiResult<Value> {
+        let req = self.get("/path/etag").await?;
+        send!(req, Json).await
+    }
+
+    async fn get_with_if_none_match(&self, etag: &str) -> ApiResult<Value> {
+        let req = self.get("/path/etag").await?;
+        let req = req.header("If-None-Match", etag);
+        send!(req, Json).await
+    }
+
+    async fn get_raw_with_etag(&self) -> ApiResult<ResponseBody> {
+        let req = self.get("/path/etag").await?;
+        send!(req, Raw).await
+    }
+
+    async fn get_with_etag_and_headers(&self) -> ApiResult<Value> {
+        let req = self.get("/path/etag").await?;
+        send!(req, WithHeaders).await
+    }
+}
+
+/// The (If-None-Match, status) of each request
+type Records = Arc<Mutex<Vec<(Option<String>, u16)>>>;
+
+/// Build api which records each request
+fn build_api(builder: TheApiBuilder, cache: bool) -> (TheApi, Records) {
+    let records = Arc::new(Mutex::new(vec![]));
+    let records_in_middleware = records.clone();
+    let builder = builder.with_middleware_fn(move |req, extensions, next| {
+        let records = records_in_middleware.clone();
+        Box::pin(async move {
+            let res = next.run(req, extensions).await?;
+            let if_none_match = res
+                .headers()
+                .get("x-if-none-match")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string());
+            records
+                .lock()
+                .unwrap()
+                .push((if_none_match, res.status().as_u16()));
+            Ok(res)
+        })
+    });
+    let builder = if cache {
+        builder.with_response_cache(16)
+    } else {
+        builder
+    };
+    (builder.build(), records)
+}
+
+#[tokio::test]
+async fn test_response_cache() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let (api, records) = build_api(TheApi::builder(), true);
+
+    let res = api.get_with_etag().await?;
+    assert_eq!(json!({ "version": 1 }), res);
+    let res = api.get_with_etag().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(json!({ "version": 1 }), res);
+
+    assert_eq!(
+        vec![(None, 200), (Some(ETAG.to_string()), 304)],
+        *records.lock().unwrap()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_response_cache_across_extractors() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let (api, records) = build_api(TheApi::builder(), true);
+
+    let res = api.get_with_etag_and_headers().await?;
+    assert_eq!(
+        Some(ETAG),
+        res.pointer("/__headers__/etag").and_then(|v| v.as_str())
+    );
+
+    // The cached body doesn't carry the headers injected for other extractor
+    let res = api.get_with_etag().await?;
+    assert_eq!(json!({ "version": 1 }), res);
+
+    let res = api.get_with_etag_and_headers().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(1), res.get("version").and_then(|v| v.as_i64()));
+    assert_eq!(
+        Some(ETAG),
+        res.pointer("/__headers__/etag").and_then(|v| v.as_str())
+    );
+
+    assert_eq!(
+        vec![
+            (None, 200),
+            (Some(ETAG.to_string()), 304),
+            (Some(ETAG.to_string()), 304)
+        ],
+        *records.lock().unwrap()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_not_modified_without_cached_body() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    // The explicit If-None-Match can't be served by the empty cache
+    for cache in [true, false] {
+        let (api, _) = build_api(TheApi::builder(), cache);
+        let res = api.get_with_if_none_match(ETAG).await;
+        log::debug!("res = {:?}", res);
+        assert!(matches!(res, Err(ApiError::Redirect(304, _))), "{:?}", res);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_response_cache_skips_raw() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let (api, records) = build_api(TheApi::builder(), true);
+
+    api.get_with_etag().await?;
+    // The cached body is parsed, so it can't be returned as raw bytes
+    let res = api.get_raw_with_etag().await?;
+    assert!(matches!(res, ResponseBody::Raw(_)));
+
+    assert_eq!(vec![(None, 200), (None, 200)], *records.lock().unwrap());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_response_cache_per_token() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let builder = TheApi::builder().with_authenticator(AccessTokenAuth::new("anonymous"));
+    let (api, records) = build_api(builder, true);
+    let alice = api.core.with_authenticator(AccessTokenAuth::new("alice"));
+    let bob = api.core.with_authenticator(AccessTokenAuth::new("bob"));
+    let alice = TheApi {
+        core: Arc::new(alice),
+    };
+    let bob = TheApi {
+        core: Arc::new(bob),
+    };
+
+    alice.get_with_etag().await?;
+    // The response of alice is not reused by bob
+    bob.get_with_etag().await?;
+    alice.get_with_etag().await?;
+    bob.get_with_etag().await?;
+
+    assert_eq!(
+        vec![
+            (None, 200),
+            (None, 200),
+            (Some(ETAG.to_string()), 304),
+            (Some(ETAG.to_string()), 304)
+        ],
+        *records.lock().unwrap()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_without_response_cache() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let (api, records) = build_api(TheApi::builder(), false);
+
+    api.get_with_etag().await?;
+    api.get_with_etag().await?;
+
+    assert_eq!(vec![(None, 200), (None, 200)], *records.lock().unwrap());
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use apisdk::{resumable_download, ApiResult, ResumedDownload};
+
+use crate::common::{init_logger, start_server, temp_file, TheApi};
+
+mod common;
+
+const BODY: &[u8] = b"0123456789abcdef";
+
+impl TheApi {
+    async fn resume(&self, path: &Path, etag: Option<&str>) -> ApiResult<ResumedDownload> {
+        let req = self.get("/path/resumable").await?;
+        resumable_download!(req, path, etag).await
+    }
+}
+
+#[tokio::test]
+async fn test_resumable_download() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // The first download is interrupted after 6 bytes
+    let path = temp_file("resumable.bin");
+    std::fs::write(&path, &BODY[..6])?;
+
+    let res = api.resume(&path, Some("\"v1\"")).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        ResumedDownload {
+            written: 10,
+            length: 16,
+            resumed: true,
+            etag: Some("\"v1\"".to_string()),
+        },
+        res
+    );
+    assert_eq!(BODY.to_vec(), std::fs::read(&path)?);
+
+    // Completed already
+    let res = api.resume(&path, Some("\"v1\"")).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(0, res.written);
+    assert_eq!(16, res.length);
+    assert!(res.resumed);
+    assert_eq!(BODY.to_vec(), std::fs::read(&path)?);
+
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resumable_download_changed() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // The downloaded part belongs to an old version
+    let path = temp_file("resumable-changed.bin");
+    std::fs::write(&path, b"old")?;
+
+    let res = api.resume(&path, Some("\"v0\"")).await?;
+    log::debug!("res = {:?}", res);
+    assert!(!res.resumed);
+    assert_eq!(16, res.written);
+    assert_eq!(16, res.length);
+    assert_eq!(BODY.to_vec(), std::fs::read(&path)?);
+
+    // No file at all
+    std::fs::remove_file(&path)?;
+    let res = api.resume(&path, None).await?;
+    assert!(!res.resumed);
+    assert_eq!(BODY.to_vec(), std::fs::read(&path)?);
+
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
@@ -1,11 +1,11 @@
 use std::net::IpAddr;
 
-use apisdk::{send, ApiResult, DnsResolver, SocketAddrs, UrlOps};
+use apisdk::{send, ApiResult, CodeDataMessage, DnsResolver, PathRewriter, SocketAddrs, UrlOps};
 use apisdk_macros::http_api;
 use async_trait::async_trait;
 use url::Url;
 
-use crate::common::{init_logger, start_server, TheApi};
+use crate::common::{init_logger, start_server, Payload, TheApi};
 
 mod common;
 
@@ -191,3 +191,32 @@ async fn test_rewrite() -> ApiResult<()> {
 
 //     Ok(())
 // }
+
+#[tokio::test]
+async fn test_path_rewriter() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let rewriter = PathRewriter::new().with_rule(r"^/internal(/|$)", "/")?;
+    assert_eq!("/foo", rewriter.rewrite("/internal/foo"));
+    assert_eq!("/internalfoo", rewriter.rewrite("/internalfoo"));
+    assert!(PathRewriter::new().with_rule("(", "").is_err());
+
+    // Rules are applied in order
+    let ordered = PathRewriter::new()
+        .with_rule("^/a/", "/b/")?
+        .with_rule("^/b/", "/c/")?;
+    assert_eq!("/c/foo", ordered.rewrite("/a/foo"));
+
+    let api = TheApi::builder().with_path_rewriter(rewriter).build();
+
+    let url = api.core.build_url("/internal/foo").await?;
+    assert_eq!("http://localhost:3030/v1/foo", url.as_str());
+
+    let req = api.get("/internal/path/json").await?;
+    let res: Payload = send!(req, CodeDataMessage).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("/v1/path/json", res.path);
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::sync::{Arc, Mutex};
+
+use apisdk::{
+    send_body_stream, ApiResult, CodeDataMessage, LogRecord, MockServer, Request, ResponseBody,
+};
+use bytes::Bytes;
+use futures::stream;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+#[derive(Debug, Deserialize)]
+struct Echo {
+    content_type: Option<String>,
+    body: String,
+}
+
+fn lines() -> impl futures::Stream<Item = Result<Bytes, std::io::Error>> {
+    stream::iter(
+        ["{\"id\":1}\n", "{\"id\":2}\n"]
+            .into_iter()
+            .map(|line| Ok(Bytes::from(line))),
+    )
+}
+
+impl TheApi {
+    async fn upload_lines(&self) -> ApiResult<Echo> {
+        let req = self.post("/path/echo-body").await?;
+        send_body_stream!(req, lines(), "application/x-ndjson", CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_body_stream() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let records = Arc::new(Mutex::new(Vec::<LogRecord>::new()));
+    let sink = records.clone();
+    let api = TheApi::builder()
+        .with_log("info")
+        .with_log_sink(move |record| sink.lock().unwrap().push(record))
+        .build();
+
+    let res = api.upload_lines().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some("application/x-ndjson"), res.content_type.as_deref());
+    assert_eq!("{\"id\":1}\n{\"id\":2}\n", res.body);
+
+    let records = records.lock().unwrap();
+    assert!(records
+        .iter()
+        .any(|r| r.message == "Request Stream\n<streamed body of application/x-ndjson>"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_body_stream_mock() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder()
+        .with_initialiser(MockServer::new(|req: Request| {
+            // The streamed body could not be read as bytes
+            let streaming = req.body().map(|b| b.as_bytes().is_none());
+            Ok(ResponseBody::Json(json!({
+                "code": 0,
+                "data": {
+                    "content_type": req.headers()["content-type"].to_str()?,
+                    "body": format!("{:?}", streaming),
+                }
+            })))
+        }))
+        .build();
+
+    let res = api.upload_lines().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some("application/x-ndjson"), res.content_type.as_deref());
+    assert_eq!("Some(true)", res.body);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use apisdk::{send_bytes, ApiError, ApiResult, MockServer, ResponseBody};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn download(&self) -> ApiResult<Vec<u8>> {
+        let req = self.get("/path/bytes").await?;
+        send_bytes!(req).await
+    }
+
+    async fn download_mock(&self) -> ApiResult<Vec<u8>> {
+        let req = self.get("/path/bytes").await?;
+        let req = req.with_extension(MockServer::new(|_| {
+            Ok(ResponseBody::Text("mocked".to_string()))
+        }));
+        send_bytes!(req).await
+    }
+
+    async fn download_not_found(&self) -> ApiResult<Vec<u8>> {
+        let req = self.get("/not-found").await?;
+        send_bytes!(req).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_bytes() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.download().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(vec![0x89, b'P', b'N', b'G', 0x00, 0xff], res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_bytes_mock() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.download_mock().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(b"mocked".to_vec(), res);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_bytes_not_found() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.download_not_found().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::HttpClientStatus(..))));
+
+    Ok(())
+}
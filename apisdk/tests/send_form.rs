@@ -1,9 +1,15 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
-use apisdk::{send_form, ApiResult, CodeDataMessage, DynamicForm, MultipartForm, MultipartFormOps};
+use apisdk::{
+    send_form, ApiError, ApiResult, ArrayStyle, BracketForm, CodeDataMessage, DynamicForm,
+    JsonForm, MultipartForm, MultipartFormOps, NullPolicy, OrderedForm,
+};
 use serde_json::{json, Value};
 
-use crate::common::{init_logger, start_server, TheApi};
+use crate::common::{init_logger, start_server, Payload, TheApi};
 
 mod common;
 
@@ -33,6 +39,43 @@ impl TheApi {
         send_form!(req, form, CodeDataMessage).await
     }
 
+    async fn form_via_json_null(&self, null_policy: Option<NullPolicy>) -> ApiResult<Payload> {
+        let req = self.post("/path/form").await?;
+        let form = json!({ "a": null, "b": 1 });
+        match null_policy {
+            Some(null_policy) => {
+                let form = JsonForm::new(form).with_null_policy(null_policy);
+                send_form!(req, form, CodeDataMessage).await
+            }
+            None => send_form!(req, form, CodeDataMessage).await,
+        }
+    }
+
+    async fn form_via_bracket_form(&self, array_style: ArrayStyle) -> ApiResult<Payload> {
+        let req = self.post("/path/form").await?;
+        let form = BracketForm::new(json!({
+            "items": [1, 2],
+            "user": { "name": "x", "tags": ["a"] },
+        }))
+        .with_array_style(array_style);
+        send_form!(req, form, CodeDataMessage).await
+    }
+
+    async fn form_via_ordered_form(&self) -> ApiResult<Payload> {
+        let req = self.post("/path/form").await?;
+        let form = OrderedForm::new()
+            .append("tags", "a")
+            .append("q", "x y")
+            .append("tags", "b");
+        send_form!(req, form, CodeDataMessage).await
+    }
+
+    async fn form_via_pairs(&self) -> ApiResult<Payload> {
+        let req = self.post("/path/form").await?;
+        let pairs = [("tags", "a"), ("tags", "b")];
+        send_form!(req, &pairs[..], CodeDataMessage).await
+    }
+
     async fn form_via_dynamic_form(&self) -> ApiResult<Value> {
         let req = self.post("/path/form").await?;
         let form = DynamicForm::new()
@@ -50,6 +93,11 @@ impl TheApi {
             .text("key3", 3.to_string());
         send_form!(req, form, CodeDataMessage).await
     }
+
+    async fn form_via_json_array(&self) -> ApiResult<Value> {
+        let req = self.post("/path/form").await?;
+        send_form!(req, json!([1, 2]), CodeDataMessage).await
+    }
 }
 
 #[tokio::test]
@@ -91,6 +139,113 @@ async fn test_send_form_via_json() -> ApiResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_send_form_via_json_null() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // Default
+    let res = api.form_via_json_null(None).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        HashMap::from([("b".to_string(), "1".to_string())]),
+        res.form
+    );
+
+    // Skip
+    let res = api.form_via_json_null(Some(NullPolicy::Skip)).await?;
+    assert_eq!(None, res.form.get("a"));
+    assert_eq!(Some("1"), res.form.get("b").map(|v| v.as_str()));
+
+    // Empty
+    let res = api.form_via_json_null(Some(NullPolicy::Empty)).await?;
+    assert_eq!(Some(""), res.form.get("a").map(|v| v.as_str()));
+    assert_eq!(Some("1"), res.form.get("b").map(|v| v.as_str()));
+
+    // Literal
+    let res = api.form_via_json_null(Some(NullPolicy::Literal)).await?;
+    assert_eq!(Some("null"), res.form.get("a").map(|v| v.as_str()));
+    assert_eq!(Some("1"), res.form.get("b").map(|v| v.as_str()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_form_via_bracket_form() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let wire = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let captured = wire.clone();
+    let api = TheApi::builder()
+        .with_middleware_fn(move |req, extensions, next| {
+            if let Some(bytes) = req.body().and_then(|b| b.as_bytes()) {
+                *captured.lock().unwrap() = bytes.to_vec();
+            }
+            Box::pin(next.run(req, extensions))
+        })
+        .build();
+
+    // Brackets
+    let res = api.form_via_bracket_form(ArrayStyle::Brackets).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        "items%5B%5D=1&items%5B%5D=2&user%5Bname%5D=x&user%5Btags%5D%5B%5D=a",
+        String::from_utf8(wire.lock().unwrap().clone()).unwrap()
+    );
+    assert_eq!(Some("x"), res.form.get("user[name]").map(|v| v.as_str()));
+
+    // Indexed
+    let res = api.form_via_bracket_form(ArrayStyle::Indexed).await?;
+    assert_eq!(
+        HashMap::from([
+            ("items[0]".to_string(), "1".to_string()),
+            ("items[1]".to_string(), "2".to_string()),
+            ("user[name]".to_string(), "x".to_string()),
+            ("user[tags][0]".to_string(), "a".to_string()),
+        ]),
+        res.form
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_form_via_ordered_form() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let wire = Arc::new(Mutex::new(Vec::<u8>::new()));
+    let captured = wire.clone();
+    let api = TheApi::builder()
+        .with_middleware_fn(move |req, extensions, next| {
+            if let Some(bytes) = req.body().and_then(|b| b.as_bytes()) {
+                *captured.lock().unwrap() = bytes.to_vec();
+            }
+            Box::pin(next.run(req, extensions))
+        })
+        .build();
+
+    let res = api.form_via_ordered_form().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        "tags=a&q=x+y&tags=b",
+        String::from_utf8(wire.lock().unwrap().clone()).unwrap()
+    );
+    assert_eq!(Some("x y"), res.form.get("q").map(|v| v.as_str()));
+
+    let res = api.form_via_pairs().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        "tags=a&tags=b",
+        String::from_utf8(wire.lock().unwrap().clone()).unwrap()
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_send_form_via_dynamic_form() -> ApiResult<()> {
     init_logger();
@@ -115,3 +270,23 @@ async fn test_send_form_via_multipart_form() {
     let res = api.form_via_multipart_form().await.unwrap();
     log::debug!("res = {:?}", res);
 }
+
+#[tokio::test]
+async fn test_send_form_not_object() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.form_via_json_array().await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::SerializeRequest { detail, .. }) => assert_eq!(
+            "expected a json object to send as form, but got an array",
+            detail
+        ),
+        _ => panic!("should be SerializeRequest error"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use apisdk::{
+    send, ApiResponse, ApiResult, ClientBuilder, CodeDataMessage, MockServer, ResponseBody,
+    StatusCode, Version,
+};
+use serde_json::json;
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn get_full_array(&self) -> ApiResult<ApiResponse<Vec<u32>>> {
+        let req = self.get("/path/array").await?;
+        send!(req, Full<Vec<u32>>).await
+    }
+
+    async fn get_full_object(&self) -> ApiResult<ApiResponse<CodeDataMessage<Payload>>> {
+        let req = self.get("/path/json").await?;
+        send!(req, Full<CodeDataMessage<Payload>>).await
+    }
+
+    async fn get_full_mock(&self) -> ApiResult<ApiResponse<Vec<u32>>> {
+        let req = self.get("/path/json").await?;
+        let req = req.with_extension(MockServer::new(|_| Ok(ResponseBody::Json(json!([7])))));
+        send!(req, Full<Vec<u32>>).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_full_array() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.get_full_array().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(StatusCode::OK, res.status);
+    assert_eq!(Some("42"), res.get_header("X-RateLimit-Remaining"));
+    assert_eq!(vec![1, 2, 3], res.body);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_full_object() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.get_full_object().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(StatusCode::OK, res.status);
+    assert_eq!(Version::HTTP_11, res.version);
+    assert!(res.headers.contains_key("content-type"));
+    assert_eq!("/v1/path/json", res.body.data.path);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_full_mock() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.get_full_mock().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(StatusCode::OK, res.status);
+    assert!(res.headers.is_empty());
+    assert_eq!(vec![7], res.body);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_full_h2() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    // Use h2c with prior knowledge, since the test server doesn't support TLS
+    let api = TheApi::builder()
+        .with_client(ClientBuilder::new().http2_prior_knowledge())
+        .build();
+
+    let res = api.get_full_object().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(StatusCode::OK, res.status);
+    assert_eq!(Version::HTTP_2, res.version);
+
+    Ok(())
+}
@@ -133,8 +133,7 @@ async fn test_send_get_as_unit() -> ApiResult<()> {
 
     let api = TheApi::builder().build();
 
-    let res = api.get_as_unit().await?;
-    log::debug!("res = {:?}", res);
+    api.get_as_unit().await?;
 
     Ok(())
 }
@@ -0,0 +1,108 @@
+use std::sync::{Arc, Mutex};
+
+use apisdk::{api_method, send_graphql, ApiError, ApiResult, GraphQLQuery, LogRecord};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+#[derive(Debug, Deserialize)]
+struct UserData {
+    user: Value,
+}
+
+impl TheApi {
+    async fn query_user(&self, id: i64) -> ApiResult<UserData> {
+        let req = self.post("/graphql").await?;
+        let query = "query ($id: ID!) { user(id: $id) { id } }";
+        send_graphql!(req, query, json!({ "id": id })).await
+    }
+
+    async fn query_user_with_operation(&self) -> ApiResult<Value> {
+        let req = self.post("/graphql").await?;
+        let query = GraphQLQuery::new("query GetUser($id: ID!) { user(id: $id) { id } }")
+            .with_variables(&json!({ "id": 2 }))?
+            .with_operation_name("GetUser");
+        send_graphql!(req, query).await
+    }
+
+    #[api_method(log = "off")]
+    async fn query_user_without_log(&self, id: i64) -> ApiResult<UserData> {
+        let req = self.post("/graphql").await?;
+        let query = "query ($id: ID!) { user(id: $id) { id } }";
+        send_graphql!(req, query, json!({ "id": id })).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_graphql() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.query_user(1).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(1, res.user["id"].as_i64().unwrap_or_default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_graphql_with_operation() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.query_user_with_operation().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("GetUser", res["user"]["operationName"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_graphql_errors() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.query_user(0).await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::GraphQL(errors)) => {
+            assert_eq!(1, errors.len());
+            assert_eq!("User not found", errors[0].message);
+        }
+        _ => panic!("should be GraphQL error"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_graphql_with_api_method() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let records = Arc::new(Mutex::new(Vec::<LogRecord>::new()));
+    let sink = records.clone();
+    let api = TheApi::builder()
+        .with_log_sink(move |record| sink.lock().unwrap().push(record))
+        .build();
+
+    // The log is turned off by `api_method`
+    let res = api.query_user_without_log(1).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(1, res.user["id"].as_i64().unwrap_or_default());
+    assert!(records.lock().unwrap().is_empty());
+
+    let _ = api.query_user(1).await?;
+    assert!(!records.lock().unwrap().is_empty());
+
+    Ok(())
+}
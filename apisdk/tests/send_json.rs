@@ -1,10 +1,30 @@
-use apisdk::{send_json, ApiResult, CodeDataMessage};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use apisdk::{send_json, ApiError, ApiResult, CodeDataMessage, MimeType, MockServer, ResponseBody};
+use serde::{Serialize, Serializer};
 use serde_json::{json, Value};
 
 use crate::common::{init_logger, start_server, TheApi};
 
 mod common;
 
+struct FailToSerialize;
+
+impl Serialize for FailToSerialize {
+    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Err(serde::ser::Error::custom("always fail"))
+    }
+}
+
 impl TheApi {
     async fn post_as_value(&self) -> ApiResult<Value> {
         let req = self.post("/path/json").await?;
@@ -32,6 +52,22 @@ impl TheApi {
         });
         send_json!(req, payload, CodeDataMessage).await
     }
+
+    async fn post_non_serializable(&self) -> ApiResult<Value> {
+        let req = self.post("/path/json").await?;
+        let mut payload = HashMap::new();
+        payload.insert((1, 2), "tuple key is not allowed");
+        send_json!(req, payload).await
+    }
+
+    async fn post_fail_to_serialize(&self, counter: Arc<AtomicUsize>) -> ApiResult<Value> {
+        let req = self.post("/path/json").await?;
+        let req = req.with_extension(MockServer::new(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(ResponseBody::Json(Value::Null))
+        }));
+        send_json!(req, FailToSerialize).await
+    }
 }
 
 #[tokio::test]
@@ -54,8 +90,7 @@ async fn test_send_post_as_unit() -> ApiResult<()> {
 
     let api = TheApi::builder().build();
 
-    let res = api.post_as_unit().await?;
-    log::debug!("res = {:?}", res);
+    api.post_as_unit().await?;
 
     Ok(())
 }
@@ -72,3 +107,42 @@ async fn test_send_post_and_extract_cdm() -> ApiResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_send_post_non_serializable() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let res = api.post_non_serializable().await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::SerializeRequest {
+            mime: MimeType::Json,
+            ..
+        }) => {}
+        _ => panic!("should be SerializeRequest error"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_post_fail_to_serialize() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+    let res = api.post_fail_to_serialize(counter.clone()).await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(ApiError::SerializeRequest { detail, .. }) => assert_eq!("always fail", detail),
+        _ => panic!("should be SerializeRequest error"),
+    }
+    assert_eq!(0, counter.load(Ordering::SeqCst));
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use apisdk::{send_json_seq, ApiError, ApiResult, MockServer, ResponseBody};
+use bytes::Bytes;
+use futures::StreamExt;
+use serde::Deserialize;
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Record {
+    id: u32,
+}
+
+impl TheApi {
+    async fn touch_json_seq(&self) -> ApiResult<Vec<ApiResult<Record>>> {
+        let req = self.get("/path/json-seq").await?;
+        let req = req.with_extension(MockServer::new(|_| {
+            Ok(ResponseBody::Raw(Bytes::from_static(
+                b"\x1e{\"id\":1}\n\x1e{\"id\":\n\x1e{\"id\":3}\n",
+            )))
+        }));
+        let records = send_json_seq!(req, Record).await?;
+        Ok(records.collect().await)
+    }
+
+    async fn touch_json_seq_redirect(&self) -> ApiResult<()> {
+        let req = self.get("/path/redirect").await?;
+        send_json_seq!(req, Record).await.map(|_| ())
+    }
+}
+
+#[tokio::test]
+async fn test_send_json_seq() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+
+    let records = api.touch_json_seq().await?;
+    log::debug!("records = {:?}", records);
+    assert_eq!(3, records.len());
+    assert_eq!(&Record { id: 1 }, records[0].as_ref().unwrap());
+    assert!(matches!(records[1], Err(ApiError::DecodeJson(_))));
+    assert_eq!(&Record { id: 3 }, records[2].as_ref().unwrap());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_json_seq_no_redirects() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_no_redirects().build();
+
+    let res = api.touch_json_seq_redirect().await;
+    assert!(matches!(res, Err(ApiError::Redirect(302, _))), "{:?}", res);
+
+    Ok(())
+}
@@ -1,12 +1,22 @@
 use apisdk::{
-    send_multipart, ApiResult, CodeDataMessage, DynamicForm, MultipartForm, MultipartFormOps,
+    multipart::Part, send_multipart, ApiResult, CodeDataMessage, DynamicForm, FormLike, MimeType,
+    MultipartForm, MultipartFormOps,
 };
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::common::{init_logger, start_server, TheApi};
 
 mod common;
 
+/// The expected content-type of file part
+fn file_mime(guessed: &'static str) -> &'static str {
+    if cfg!(feature = "mime_guess") {
+        guessed
+    } else {
+        "application/octet-stream"
+    }
+}
+
 impl TheApi {
     async fn multipart_via_dynamic_form(&self) -> ApiResult<Value> {
         let req = self.post("/path/multipart").await?;
@@ -25,6 +35,16 @@ impl TheApi {
             .text("key3", 3.to_string());
         send_multipart!(req, form, CodeDataMessage).await
     }
+
+    async fn multipart_with_json_part(&self, form: DynamicForm) -> ApiResult<Value> {
+        let req = self.post("/path/multipart").await?;
+        send_multipart!(req, form, CodeDataMessage).await
+    }
+
+    async fn multipart_with_form(&self, form: MultipartForm) -> ApiResult<Value> {
+        let req = self.post("/path/multipart").await?;
+        send_multipart!(req, form, CodeDataMessage).await
+    }
 }
 
 #[tokio::test]
@@ -52,3 +72,113 @@ async fn test_send_multipart_via_multipart_form() -> ApiResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_send_multipart_with_json_part() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let form = DynamicForm::new()
+        .text("key1", 1.to_string())
+        .text_with_mime(
+            "payload",
+            json!({ "key": "value" }).to_string(),
+            MimeType::Json,
+        );
+    let meta = form.get_meta();
+    log::debug!("meta = {:?}", meta);
+    assert_eq!(
+        Some("(application/json) {\"key\":\"value\"}"),
+        meta.get("payload").map(|v| v.as_str())
+    );
+
+    let res = api.multipart_with_json_part(form).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("application/json", res["multipart"]["payload"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_multipart_with_file() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let path = std::env::temp_dir().join(format!("apisdk-{}-upload.json", std::process::id()));
+    std::fs::write(&path, b"{\"key\":\"value\"}")?;
+
+    let form = MultipartForm::new()
+        .text("key1", 1.to_string())
+        .file("file", &path)?;
+    let meta = form.get_meta();
+    log::debug!("meta = {:?}", meta);
+    assert_eq!(
+        Some(format!(
+            "({}) @{}",
+            file_mime("application/json"),
+            path.file_name().unwrap().to_string_lossy()
+        )),
+        meta.get("file").cloned()
+    );
+
+    let res = api.multipart_with_form(form).await;
+    std::fs::remove_file(&path)?;
+    let res = res?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(file_mime("application/json"), res["multipart"]["file"]);
+    assert_eq!(15, res["files"]["file"]["size"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_multipart_with_stream() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let chunks: Vec<Result<Vec<u8>, std::io::Error>> =
+        vec![Ok(b"hello ".to_vec()), Ok(b"world".to_vec())];
+    let form = MultipartForm::new().stream("doc", "hello.txt", futures::stream::iter(chunks));
+    assert_eq!(
+        Some(format!("({}) @hello.txt", file_mime("text/plain"))),
+        form.get_meta().get("doc").cloned()
+    );
+
+    let res = api.multipart_with_form(form).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(file_mime("text/plain"), res["multipart"]["doc"]);
+    assert_eq!("hello.txt", res["files"]["doc"]["filename"]);
+    assert_eq!(11, res["files"]["doc"]["size"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_multipart_with_part_mime() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let png = Part::bytes(vec![0x89, b'P', b'N', b'G']).file_name("image.png");
+    let form = MultipartForm::new()
+        .part_with_mime("image", png, MimeType::Other("image/png".to_string()))
+        .part_with_mime(
+            "invalid",
+            Part::text("value"),
+            MimeType::Other("???".to_string()),
+        );
+
+    let res = api.multipart_with_form(form).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("image/png", res["multipart"]["image"]);
+    assert_eq!("", res["multipart"]["invalid"]);
+
+    Ok(())
+}
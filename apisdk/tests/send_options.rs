@@ -0,0 +1,56 @@
+use apisdk::{send, ApiResult, Method, ResourceInfo};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn allowed_methods(&self) -> ApiResult<Vec<Method>> {
+        let req = self.options("/path/allow").await?;
+        send!(req, Allow).await
+    }
+
+    async fn resource_info(&self) -> ApiResult<ResourceInfo> {
+        let req = self.head("/path/bytes").await?;
+        send!(req, ResourceInfo).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_options_allow() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let methods = api.allowed_methods().await?;
+    log::debug!("methods = {:?}", methods);
+    assert_eq!(
+        vec![Method::GET, Method::HEAD, Method::OPTIONS, Method::POST],
+        methods
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_head_resource_info() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let info = api.resource_info().await?;
+    log::debug!("info = {:?}", info);
+    assert_eq!(
+        ResourceInfo {
+            content_length: Some(6),
+            accepts_ranges: true,
+            content_type: Some("image/png".to_string()),
+            etag: Some("\"png-v1\"".to_string()),
+        },
+        info
+    );
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use apisdk::{send_range, ApiError, ApiResult, ContentRange, PartialContent};
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn get_range(&self, start: u64, end: Option<u64>) -> ApiResult<PartialContent> {
+        let req = self.get("/path/range").await?;
+        match end {
+            Some(end) => send_range!(req, start, end).await,
+            None => send_range!(req, start).await,
+        }
+    }
+
+    async fn get_range_ignored(&self) -> ApiResult<PartialContent> {
+        let req = self.get("/path/bytes").await?;
+        send_range!(req, 0, 3).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_range() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let part = api.get_range(2, Some(4)).await?;
+    log::debug!("part = {:?}", part);
+    assert_eq!(
+        ContentRange {
+            start: 2,
+            end: 4,
+            total: Some(10)
+        },
+        part.range
+    );
+    assert_eq!(b"234".to_vec(), part.body);
+    assert!(!part.range.is_last());
+
+    let part = api.get_range(7, None).await?;
+    assert_eq!(b"789".to_vec(), part.body);
+    assert!(part.range.is_last());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_range_ignored() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    // The server returns 200 OK with the whole body
+    let res = api.get_range_ignored().await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::InvalidRange(_))));
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "sse")]
+
+use apisdk::{send, send_sse, ApiError, ApiResult, SseEvent};
+use futures::TryStreamExt;
+
+use crate::common::{init_logger, start_server, TheApi};
+
+mod common;
+
+impl TheApi {
+    async fn touch_sse(&self) -> ApiResult<Vec<SseEvent>> {
+        let req = self.get("/path/sse").await?;
+        let events = send_sse!(req).await?;
+        events.try_collect().await
+    }
+
+    async fn touch_sse_redirect(&self) -> ApiResult<()> {
+        let req = self.get("/path/redirect").await?;
+        send_sse!(req).await.map(|_| ())
+    }
+
+    async fn touch_sse_as_text(&self) -> ApiResult<String> {
+        let req = self.get("/path/sse").await?;
+        send!(req, Text).await
+    }
+}
+
+#[tokio::test]
+async fn test_send_sse() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let events = api.touch_sse().await?;
+    log::debug!("events = {:?}", events);
+    assert_eq!(2, events.len());
+    assert_eq!(Some("message"), events[0].event.as_deref());
+    assert_eq!("hello", events[0].data);
+    assert_eq!(Some("1"), events[0].id.as_deref());
+    assert_eq!("line 1\nline 2", events[1].data);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_sse_as_text() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let text = api.touch_sse_as_text().await?;
+    log::debug!("text = {:?}", text);
+    assert!(text.starts_with("event: message"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_send_sse_no_redirects() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().with_no_redirects().build();
+
+    let res = api.touch_sse_redirect().await;
+    assert!(matches!(res, Err(ApiError::Redirect(302, _))), "{:?}", res);
+
+    Ok(())
+}
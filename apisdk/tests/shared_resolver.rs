@@ -0,0 +1,62 @@
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use apisdk::{async_trait, send, ApiResult, CodeDataMessage, DnsResolver, SocketAddrs};
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+/// This resolver caches the result of lookup
+#[derive(Default)]
+struct CachedResolver {
+    queries: AtomicUsize,
+    lookups: AtomicUsize,
+    cache: Mutex<Option<IpAddr>>,
+}
+
+#[async_trait]
+impl DnsResolver for CachedResolver {
+    async fn resolve(&self, _name: &str) -> Option<SocketAddrs> {
+        self.queries.fetch_add(1, Ordering::SeqCst);
+        let mut cache = self.cache.lock().unwrap();
+        let ip = cache.get_or_insert_with(|| {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        });
+        Some(SocketAddrs::from(*ip))
+    }
+}
+
+impl TheApi {
+    async fn touch(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_shared_resolver() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let resolver = Arc::new(CachedResolver::default());
+    let shared: Arc<dyn DnsResolver> = resolver.clone();
+
+    let api1 = TheApi::builder().with_resolver(shared.clone()).build();
+    let api2 = TheApi::builder().with_resolver(shared).build();
+
+    api1.touch().await?;
+    api2.touch().await?;
+
+    // Each core has its own connection pool, but the lookup is cached by the shared resolver
+    assert_eq!(2, resolver.queries.load(Ordering::SeqCst));
+    assert_eq!(1, resolver.lookups.load(Ordering::SeqCst));
+
+    Ok(())
+}
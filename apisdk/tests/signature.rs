@@ -0,0 +1,88 @@
+use apisdk::{send, ApiError, ApiResult, SignatureVerifier};
+use serde::Deserialize;
+
+use crate::common::{init_logger, start_server, TheApi, SIGNATURE_SECRET};
+
+mod common;
+
+#[derive(Debug, Deserialize)]
+struct Event {
+    event: String,
+    id: u32,
+}
+
+impl TheApi {
+    async fn get_signed(&self, verifier: SignatureVerifier) -> ApiResult<Event> {
+        let req = self.get("/path/signed").await?;
+        let req = req.with_extension(verifier);
+        send!(req, Json).await
+    }
+
+    async fn get_signed_by_initialiser(&self) -> ApiResult<Event> {
+        let req = self.get("/path/signed").await?;
+        send!(req, Json).await
+    }
+}
+
+#[tokio::test]
+async fn test_signature_verified() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let verifier = SignatureVerifier::new(SIGNATURE_SECRET, "X-Signature").with_prefix("sha256=");
+    let res = api.get_signed(verifier).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!("created", res.event);
+    assert_eq!(1, res.id);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_mismatch() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let verifier = SignatureVerifier::new("wrong-secret", "X-Signature").with_prefix("sha256=");
+    let res = api.get_signed(verifier).await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::InvalidSignature(..))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_missing_header() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+
+    let verifier = SignatureVerifier::new(SIGNATURE_SECRET, "X-Missing-Signature");
+    let res = api.get_signed(verifier).await;
+    log::debug!("res = {:?}", res);
+    assert!(matches!(res, Err(ApiError::InvalidSignature(..))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_via_initialiser() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_initialiser(
+            SignatureVerifier::new(SIGNATURE_SECRET, "X-Signature").with_prefix("sha256="),
+        )
+        .build();
+
+    let res = api.get_signed_by_initialiser().await?;
+    assert_eq!("created", res.event);
+
+    Ok(())
+}
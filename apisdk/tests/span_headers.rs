@@ -0,0 +1,126 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use apisdk::{send, AccessTokenAuth, ApiResult, CodeDataMessage, SpanHeaders};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Subscriber,
+};
+use tracing_subscriber::{layer::Context, layer::SubscriberExt, Layer, Registry};
+
+use crate::common::{start_server, Payload, TheApi, TheApiBuilder};
+
+mod common;
+
+/// Collect all recorded fields of spans
+#[derive(Clone, Default)]
+struct FieldsLayer {
+    fields: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl Visit for FieldsLayer {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.fields
+            .lock()
+            .unwrap()
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .lock()
+            .unwrap()
+            .insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl<S: Subscriber> Layer<S> for FieldsLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        attrs.record(&mut self.clone());
+    }
+
+    fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+        values.record(&mut self.clone());
+    }
+}
+
+/// The base url with IP literal, so no DNS lookup runs on another thread, out of the scoped subscriber
+const BASE_URL: &str = "http://127.0.0.1:3030/v1";
+
+impl TheApi {
+    async fn touch_span_headers(&self) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        let req = req.header("x-correlation-id", "corr-1");
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_span_headers() -> ApiResult<()> {
+    // The subscriber is scoped to this thread, so no global logger is installed
+    start_server().await;
+
+    let layer = FieldsLayer::default();
+    let fields = layer.fields.clone();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(layer));
+
+    let api = TheApiBuilder::new(BASE_URL)
+        .with_default_header("x-tenant", "t1")
+        .with_authenticator(AccessTokenAuth::new("secret-token"))
+        .with_initialiser(SpanHeaders::new([
+            "x-correlation-id",
+            "x-tenant",
+            "authorization",
+        ]))
+        .build();
+
+    let res = api.touch_span_headers().await?;
+    assert_eq!(
+        Some("Bearer secret-token"),
+        res.headers.get("authorization").map(|v| v.as_str())
+    );
+
+    let fields = fields.lock().unwrap();
+    let headers = fields.get("req.headers").unwrap();
+    assert!(headers.contains("x-correlation-id=corr-1"));
+    assert!(headers.contains("x-tenant=t1"));
+    assert!(!headers.to_lowercase().contains("authorization"));
+    assert!(!headers.contains("secret-token"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_span_http_fields() -> ApiResult<()> {
+    // The subscriber is scoped to this thread, so no global logger is installed
+    start_server().await;
+
+    let layer = FieldsLayer::default();
+    let fields = layer.fields.clone();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(layer));
+
+    let api = TheApiBuilder::new(BASE_URL).build();
+    let _ = api.touch_span_headers().await?;
+
+    let fields = fields.lock().unwrap();
+    assert_eq!(Some("GET"), fields.get("http.method").map(|v| v.as_str()));
+    assert_eq!(
+        Some("127.0.0.1"),
+        fields.get("http.host").map(|v| v.as_str())
+    );
+    assert_eq!(
+        Some("200"),
+        fields.get("http.status_code").map(|v| v.as_str())
+    );
+    assert_eq!(
+        Some("HTTP/1.1"),
+        fields.get("http.version").map(|v| v.as_str())
+    );
+
+    Ok(())
+}
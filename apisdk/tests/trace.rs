@@ -1,4 +1,9 @@
-use apisdk::{send, ApiResult, CodeDataMessage, RequestId, TraceId};
+use std::collections::HashMap;
+
+use apisdk::{
+    header::HeaderName, send, ApiResult, CodeDataMessage, RequestId, TraceHeaderNames, TraceId,
+    TracePropagation,
+};
 use serde::Deserialize;
 
 use crate::common::{init_logger, start_server, Payload, TheApi};
@@ -126,3 +131,118 @@ async fn test_trace_all_with_log() -> ApiResult<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_trace_header_names() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_trace_headers(TraceHeaderNames {
+            request_id: HeaderName::from_static("x-correlation-id"),
+            trace_id: HeaderName::from_static("x-b3-traceid"),
+            span_id: HeaderName::from_static("x-b3-spanid"),
+        })
+        .build();
+
+    let req = api.get("/path/json").await?;
+    let req = req.with_extension(TraceId::new("tr", Some("sp")));
+    let res: Payload<HashMap<String, String>> = send!(req, CodeDataMessage).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some(&"tr".to_string()), res.headers.get("x-correlation-id"));
+    assert_eq!(Some(&"tr".to_string()), res.headers.get("x-b3-traceid"));
+    assert_eq!(Some(&"sp".to_string()), res.headers.get("x-b3-spanid"));
+    assert_eq!(None, res.headers.get("x-request-id"));
+    assert_eq!(None, res.headers.get("x-trace-id"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_trace_header_names_echoed() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder()
+        .with_trace_headers(TraceHeaderNames {
+            request_id: HeaderName::from_static("request-id"),
+            trace_id: HeaderName::from_static("x-b3-traceid"),
+            span_id: HeaderName::from_static("x-b3-spanid"),
+        })
+        .build();
+
+    // The server echoes `*-id` headers only, so `x-b3-traceid` is not returned
+    let req = api.get("/path/echo-ids").await?;
+    let req = req.with_extension(RequestId::new("req"));
+    let res: CodeDataMessage = send!(req, Body).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(Some("req"), res.get_header("Request-Id"));
+    assert_eq!(Some("req"), res.get_request_id());
+    assert_eq!(None, res.get_trace_id());
+
+    // W3C only
+    let api = TheApi::builder()
+        .with_initialiser(TracePropagation::W3c)
+        .build();
+    let req = api.get("/path/echo-ids").await?;
+    let req = req.with_extension(
+        TraceId::try_w3c("4bf92f3577b34da6a3ce929d0e0e4736", Some("00f067aa0ba902b7")).unwrap(),
+    );
+    let res: CodeDataMessage = send!(req, Body).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(None, res.get_request_id());
+    assert_eq!(Some("4bf92f3577b34da6a3ce929d0e0e4736"), res.get_trace_id());
+    assert_eq!(Some("00f067aa0ba902b7"), res.get_span_id());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_trace_w3c() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    assert!(TraceId::try_w3c("tr", None::<&str>).is_none());
+    assert!(TraceId::try_w3c("00000000000000000000000000000000", None::<&str>).is_none());
+    assert!(TraceId::try_w3c("4bf92f3577b34da6a3ce929d0e0e4736", Some("sp")).is_none());
+
+    // Both
+    let api = TheApi::builder()
+        .with_initialiser(TracePropagation::Both)
+        .build();
+    let req = api.get("/path/json").await?;
+    let req = req.with_extension(
+        TraceId::try_w3c("4BF92F3577B34DA6A3CE929D0E0E4736", Some("00f067aa0ba902b7")).unwrap(),
+    );
+    let res: Payload<HashMap<String, String>> = send!(req, CodeDataMessage).await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(
+        Some(&"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string()),
+        res.headers.get("traceparent")
+    );
+    assert_eq!(
+        Some(&"4bf92f3577b34da6a3ce929d0e0e4736".to_string()),
+        res.headers.get("x-trace-id")
+    );
+
+    // W3C only, with generated ids
+    let api = TheApi::builder()
+        .with_initialiser(TracePropagation::W3c)
+        .build();
+    let req = api.get("/path/json").await?;
+    let res: Payload<HashMap<String, String>> = send!(req, CodeDataMessage).await?;
+    log::debug!("res = {:?}", res);
+    let traceparent = res.headers.get("traceparent").unwrap();
+    let parts: Vec<_> = traceparent.split('-').collect();
+    assert_eq!(
+        vec![2, 32, 16, 2],
+        parts.iter().map(|p| p.len()).collect::<Vec<_>>()
+    );
+    assert!(parts
+        .iter()
+        .all(|p| p.bytes().all(|b| b.is_ascii_hexdigit())));
+    assert_eq!(None, res.headers.get("x-request-id"));
+    assert_eq!(None, res.headers.get("x-trace-id"));
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::net::SocketAddr;
+
+use apisdk::{send_with_trailers, ApiResult, BodyWithTrailers, MockServer, ResponseBody};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use crate::common::{init_logger, TheApi, TheApiBuilder};
+
+mod common;
+
+/// Start a raw HTTP/1.1 server, which emits a chunked response with trailers
+async fn start_trailer_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\n\
+                        Content-Type: application/octet-stream\r\n\
+                        Transfer-Encoding: chunked\r\n\
+                        Trailer: grpc-status, grpc-message\r\n\
+                        Connection: close\r\n\r\n\
+                        5\r\nhello\r\n\
+                        0\r\n\
+                        grpc-status: 0\r\n\
+                        grpc-message: OK\r\n\r\n",
+                    )
+                    .await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+    addr
+}
+
+impl TheApi {
+    async fn read_trailers(&self) -> ApiResult<BodyWithTrailers> {
+        let req = self.get("/path/trailers").await?;
+        send_with_trailers!(req).await
+    }
+
+    async fn read_trailers_mock(&self) -> ApiResult<BodyWithTrailers> {
+        let req = self.get("/path/trailers").await?;
+        let req = req.with_extension(MockServer::new(|_| {
+            Ok(ResponseBody::Text("mocked".to_string()))
+        }));
+        send_with_trailers!(req).await
+    }
+}
+
+#[tokio::test]
+async fn test_read_trailers() -> ApiResult<()> {
+    init_logger();
+    let addr = start_trailer_server().await;
+
+    let api = TheApiBuilder::new(format!("http://{}/v1", addr)).build();
+
+    let res = api.read_trailers().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(b"hello".to_vec(), res.body);
+    assert_eq!(Some("0"), res.trailer("grpc-status"));
+    assert_eq!(Some("OK"), res.trailer("grpc-message"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_trailers_mock() -> ApiResult<()> {
+    init_logger();
+
+    let api = TheApi::builder().build();
+
+    let res = api.read_trailers_mock().await?;
+    log::debug!("res = {:?}", res);
+    assert_eq!(b"mocked".to_vec(), res.body);
+    assert!(res.trailers.is_none());
+
+    Ok(())
+}
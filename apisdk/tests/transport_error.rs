@@ -0,0 +1,76 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::{net::SocketAddr, time::Duration};
+
+use apisdk::{send, ApiError, ApiResult, CodeDataMessage};
+use tokio::net::TcpListener;
+
+use crate::common::{init_logger, Payload, TheApi, TheApiBuilder};
+
+mod common;
+
+/// Start a raw TCP server, which accepts connections but never responds
+async fn start_silent_server() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut streams = vec![];
+        while let Ok((stream, _)) = listener.accept().await {
+            streams.push(stream);
+        }
+    });
+    addr
+}
+
+/// Find a local port, which nobody listens on
+async fn find_closed_port() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap()
+}
+
+impl TheApi {
+    async fn touch(&self, timeout: Duration) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?.timeout(timeout);
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_timeout_error() -> ApiResult<()> {
+    init_logger();
+    let addr = start_silent_server().await;
+
+    let api = TheApiBuilder::new(format!("http://{}/v1", addr)).build();
+
+    let res = api.touch(Duration::from_millis(200)).await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(e @ ApiError::Timeout(_)) => {
+            assert_eq!(504, e.as_error_code());
+            assert!(e.is_retryable());
+        }
+        _ => panic!("should be Timeout error"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_connect_error() -> ApiResult<()> {
+    init_logger();
+    let addr = find_closed_port().await;
+
+    let api = TheApiBuilder::new(format!("http://{}/v1", addr)).build();
+
+    let res = api.touch(Duration::from_secs(5)).await;
+    log::debug!("res = {:?}", res);
+    match res {
+        Err(e @ ApiError::Connect(_)) => {
+            assert_eq!(503, e.as_error_code());
+            assert!(e.is_retryable());
+        }
+        _ => panic!("should be Connect error"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use apisdk::{send, ApiResult, CodeDataMessage, QueryOps};
+use serde::Serialize;
+
+use crate::common::{init_logger, start_server, Payload, TheApi};
+
+mod common;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PostFilter {
+    user_id: Option<u64>,
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+impl TheApi {
+    async fn filter(&self, filter: &PostFilter) -> ApiResult<Payload> {
+        let req = self.get("/path/json").await?;
+        let req = req.query(&[("page", 1)]).with_query(filter)?;
+        send!(req, CodeDataMessage).await
+    }
+}
+
+#[tokio::test]
+async fn test_with_query() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+    let filter = PostFilter {
+        user_id: Some(1),
+        title: None,
+        tags: vec![],
+    };
+    let payload = api.filter(&filter).await?;
+    log::debug!("payload = {:?}", payload);
+
+    assert_eq!(2, payload.query.len());
+    assert_eq!(Some("1"), payload.query.get("page").map(String::as_str));
+    assert_eq!(Some("1"), payload.query.get("userId").map(String::as_str));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_with_query_error() -> ApiResult<()> {
+    init_logger();
+    start_server().await;
+
+    let api = TheApi::builder().build();
+    let filter = PostFilter {
+        user_id: None,
+        title: Some("rust".to_string()),
+        tags: vec!["a".to_string()],
+    };
+    let req = api.get("/path/json").await?;
+    let result = req.with_query(&filter);
+    assert!(result.is_err());
+
+    Ok(())
+}
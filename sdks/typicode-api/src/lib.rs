@@ -1,6 +1,7 @@
 use apisdk::http_api;
 
 mod albums;
+#[allow(dead_code)]
 mod dto;
 mod posts;
 mod users;